@@ -2,7 +2,8 @@ use cosmwasm_std::{
     ensure, entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply,
     Response, StdError,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 use mantra_dex_std::farm_manager::{
     Config, ExecuteMsg, FarmAction, InstantiateMsg, MigrateMsg, PositionAction, QueryMsg,
@@ -13,13 +14,16 @@ use crate::error::ContractError;
 use crate::helpers::{
     validate_emergency_unlock_penalty, validate_farm_expiration_time, validate_unlocking_duration,
 };
+use crate::migrations::migrate_to_v210;
+use crate::position::DEFAULT_PENALTY_SPLIT;
 use crate::state::{CONFIG, FARM_COUNTER};
-use crate::{farm, manager, position, queries};
+use crate::{farm, gauge, manager, position, queries};
 
 const CONTRACT_NAME: &str = "mantra:farm-manager";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const CLOSE_FARMS_ERR_REPLY_CODE: u64 = 1u64;
+pub const COMPOUND_SWAP_REPLY_CODE: u64 = 2u64;
 
 #[entry_point]
 pub fn instantiate(
@@ -56,6 +60,7 @@ pub fn instantiate(
         max_unlocking_duration: msg.max_unlocking_duration,
         farm_expiration_time: msg.farm_expiration_time,
         emergency_unlock_penalty: validate_emergency_unlock_penalty(msg.emergency_unlock_penalty)?,
+        penalty_split: DEFAULT_PENALTY_SPLIT,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -92,7 +97,7 @@ pub fn instantiate(
 }
 
 #[entry_point]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         // this would only get triggered if the bank transfer fails, which can happen
         // if a TF token uses hooks to freeze token transfers
@@ -111,6 +116,8 @@ pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, Contract
                 ("reason", reason),
             ]))
         }
+        // a reward was swapped into an asset of the pool when compounding, provide it as liquidity
+        COMPOUND_SWAP_REPLY_CODE => farm::commands::compound_swapped_reward(deps, env),
         _ => Err(StdError::generic_err("reply id not found").into()),
     }
 }
@@ -133,12 +140,63 @@ pub fn execute(
             FarmAction::Close { farm_identifier } => {
                 manager::commands::close_farm(deps, info, farm_identifier)
             }
+            FarmAction::Pause { farm_identifier } => {
+                manager::commands::pause_farm(deps, info, farm_identifier)
+            }
+            FarmAction::Resume { farm_identifier } => {
+                manager::commands::resume_farm(deps, env, info, farm_identifier)
+            }
+            FarmAction::Cancel { farm_identifier } => {
+                manager::commands::cancel_farm(deps, info, farm_identifier)
+            }
+            FarmAction::Contribute {
+                farm_identifier,
+                asset,
+            } => manager::commands::contribute_to_farm(deps, env, info, farm_identifier, asset),
         },
         ExecuteMsg::UpdateOwnership(action) => {
             cw_utils::nonpayable(&info)?;
             mantra_utils::ownership::update_ownership(deps, env, info, action).map_err(Into::into)
         }
-        ExecuteMsg::Claim { until_epoch } => farm::commands::claim(deps, env, info, until_epoch),
+        ExecuteMsg::Claim {
+            until_epoch,
+            lp_denoms,
+            farm_identifiers,
+            owner,
+            receiver,
+        } => farm::commands::claim(
+            deps,
+            env,
+            info,
+            until_epoch,
+            lp_denoms,
+            farm_identifiers,
+            owner,
+            receiver,
+        ),
+        ExecuteMsg::ClaimAndCompound {
+            identifier,
+            swap_max_slippage,
+            reward_swap_operations,
+        } => farm::commands::claim_and_compound(
+            deps,
+            env,
+            info,
+            identifier,
+            swap_max_slippage,
+            reward_swap_operations,
+        ),
+        ExecuteMsg::ApproveClaimOperator {
+            operator,
+            expires,
+            receiver,
+        } => farm::commands::approve_claim_operator(deps, env, info, operator, expires, receiver),
+        ExecuteMsg::RevokeClaimOperator { operator } => {
+            farm::commands::revoke_claim_operator(deps, info, operator)
+        }
+        ExecuteMsg::PruneHistory { address, limit } => {
+            farm::commands::prune_history(deps, info, address, limit)
+        }
         ExecuteMsg::ManagePosition { action } => match action {
             PositionAction::Create {
                 identifier,
@@ -165,7 +223,67 @@ pub fn execute(
             } => {
                 position::commands::withdraw_position(deps, env, info, identifier, emergency_unlock)
             }
+            PositionAction::ExtendLock {
+                identifier,
+                new_unlocking_duration,
+            } => position::commands::extend_position_lock(
+                deps,
+                env,
+                info,
+                identifier,
+                new_unlocking_duration,
+            ),
+            PositionAction::Transfer {
+                identifier,
+                new_receiver,
+            } => position::commands::transfer_position(deps, env, info, identifier, new_receiver),
+            PositionAction::Split {
+                identifier,
+                amount,
+                new_identifier,
+            } => position::commands::split_position(
+                deps,
+                env,
+                info,
+                identifier,
+                amount,
+                new_identifier,
+            ),
+            PositionAction::Merge {
+                identifiers,
+                unlocking_duration,
+            } => position::commands::merge_positions(
+                deps,
+                env,
+                info,
+                identifiers,
+                unlocking_duration,
+            ),
         },
+        ExecuteMsg::FundGauge { emission_per_epoch } => {
+            gauge::commands::fund_gauge(deps, info, emission_per_epoch)
+        }
+        ExecuteMsg::GaugeVote { votes } => gauge::commands::gauge_vote(deps, env, info, votes),
+        ExecuteMsg::DistributeGauges {} => gauge::commands::distribute_gauges(deps, env, info),
+        ExecuteMsg::UpdateFarmAssets { add, remove } => {
+            cw_utils::nonpayable(&info)?;
+            manager::commands::update_farm_assets(deps, info, add, remove)
+        }
+        ExecuteMsg::EvictFarms { farm_identifiers } => {
+            cw_utils::nonpayable(&info)?;
+            manager::commands::evict_farms(deps, info, farm_identifiers)
+        }
+        ExecuteMsg::RefreshBoost { address, lp_denom } => {
+            position::commands::refresh_boost(deps, env, info, address, lp_denom)
+        }
+        ExecuteMsg::UpdateBoostConfig { boost_config } => {
+            cw_utils::nonpayable(&info)?;
+            manager::commands::update_boost_config(deps, info, boost_config)
+        }
+        ExecuteMsg::UpdateStakeableDenoms { add, remove } => {
+            cw_utils::nonpayable(&info)?;
+            manager::commands::update_stakeable_denoms(deps, info, add, remove)
+        }
         ExecuteMsg::UpdateConfig {
             fee_collector_addr,
             epoch_manager_addr,
@@ -177,6 +295,7 @@ pub fn execute(
             max_unlocking_duration,
             farm_expiration_time,
             emergency_unlock_penalty,
+            penalty_split,
         } => {
             cw_utils::nonpayable(&info)?;
             manager::commands::update_config(
@@ -192,6 +311,7 @@ pub fn execute(
                 max_unlocking_duration,
                 farm_expiration_time,
                 emergency_unlock_penalty,
+                penalty_split,
             )
         }
     }
@@ -227,11 +347,28 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         QueryMsg::Rewards {
             address,
             until_epoch,
+            lp_denom,
+            farm_identifier,
         } => Ok(to_json_binary(&queries::query_rewards(
             deps,
             &env,
             address,
             until_epoch,
+            lp_denom,
+            farm_identifier,
+        )?)?),
+        QueryMsg::DetailedRewards {
+            address,
+            until_epoch,
+            lp_denom,
+            farm_identifier,
+        } => Ok(to_json_binary(&queries::query_detailed_rewards(
+            deps,
+            &env,
+            address,
+            until_epoch,
+            lp_denom,
+            farm_identifier,
         )?)?),
         QueryMsg::LpWeight {
             address,
@@ -240,12 +377,77 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         } => Ok(to_json_binary(&queries::query_lp_weight(
             deps, address, denom, epoch_id,
         )?)?),
+        QueryMsg::TotalLpWeight { denom, epoch_id } => Ok(to_json_binary(
+            &queries::query_total_lp_weight(deps, &env, denom, epoch_id)?,
+        )?),
+        QueryMsg::LpWeightHistory {
+            address,
+            denom,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_lp_weight_history(
+            deps,
+            address,
+            denom,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::Gauge {} => Ok(to_json_binary(&queries::query_gauge(deps)?)?),
+        QueryMsg::GaugeVotes { epoch_id, voter } => Ok(to_json_binary(
+            &queries::query_gauge_votes(deps, epoch_id, voter)?,
+        )?),
+        QueryMsg::ClaimOperators {
+            address,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_claim_operators(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::ClaimHistory {
+            address,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_claim_history(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::FarmAssets { start_after, limit } => Ok(to_json_binary(
+            &queries::query_farm_assets(deps, start_after, limit)?,
+        )?),
+        QueryMsg::BoostConfig {} => Ok(to_json_binary(&queries::query_boost_config(deps)?)?),
+        QueryMsg::StakeableDenoms { start_after, limit } => Ok(to_json_binary(
+            &queries::query_stakeable_denoms(deps, start_after, limit)?,
+        )?),
+        QueryMsg::FarmEmissions {
+            farm_identifier,
+            from_epoch,
+            to_epoch,
+        } => Ok(to_json_binary(&queries::query_farm_emissions(
+            deps,
+            farm_identifier,
+            from_epoch,
+            to_epoch,
+        )?)?),
+        QueryMsg::FarmApr { lp_denom } => Ok(to_json_binary(&queries::query_farm_apr(
+            deps, &env, lp_denom,
+        )?)?),
     }
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     validate_contract!(deps, CONTRACT_NAME, CONTRACT_VERSION);
+
+    let storage_version: Version = get_contract_version(deps.storage)?.version.parse()?;
+    if storage_version < Version::parse("2.1.0")? {
+        migrate_to_v210(deps.branch())?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
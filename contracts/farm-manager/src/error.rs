@@ -92,6 +92,9 @@ pub enum ContractError {
     #[error("The asset sent doesn't match the asset expected")]
     AssetMismatch,
 
+    #[error("The denom {denom} is not allowed to be used as a farm asset")]
+    FarmAssetNotAllowed { denom: String },
+
     #[error("Attempt to create a new farm, which exceeds the maximum of {max} farms allowed per LP at a time")]
     TooManyFarms {
         /// The maximum amount of farms that can exist
@@ -119,9 +122,6 @@ pub enum ContractError {
     #[error("The amount of LP specified when closing the position is invalid. Expected at most {expected}, actual {actual}.")]
     InvalidLpAmount { expected: Uint128, actual: Uint128 },
 
-    #[error("Maximum number of open/close positions per user exceeded, max is {max}. If you are trying to open a position, close some and try again. If you are trying to close a position, withdraw some and try again.")]
-    MaxPositionsPerUserExceeded { max: u32 },
-
     #[error("The position with the identifier {identifier} already exists")]
     PositionAlreadyExists { identifier: String },
 
@@ -151,6 +151,9 @@ pub enum ContractError {
     #[error("The emergency unlock penalty provided is invalid")]
     InvalidEmergencyUnlockPenalty,
 
+    #[error("The boost config provided is invalid")]
+    InvalidBoostConfig,
+
     #[error("There are pending rewards to be claimed before this action can be executed")]
     PendingRewards,
 
@@ -168,6 +171,48 @@ pub enum ContractError {
 
     #[error("Invalid epoch provided when claiming rewards: {until_epoch}. The epoch can't be ahead of the current epoch, nor can it be before the last claimed epoch.")]
     InvalidUntilEpoch { until_epoch: EpochId },
+
+    #[error("The emission curve provided for the farm is invalid: {reason}")]
+    InvalidFarmCurve { reason: String },
+
+    #[error("The farm can't be expanded with its current emission curve, {curve}")]
+    FarmNotExpandable { curve: String },
+
+    #[error("The gauge emission per epoch must be greater than zero")]
+    InvalidGaugeEmission,
+
+    #[error("The gauge votes are invalid: {reason}")]
+    InvalidGaugeVotes { reason: String },
+
+    #[error("The sender doesn't have voting power on the current epoch")]
+    NoVotingPower,
+
+    #[error("The claim operator approval has expired")]
+    ClaimOperatorExpired,
+
+    #[error("The reward swap operations are invalid: {reason}")]
+    InvalidRewardSwapOperations { reason: String },
+
+    #[error("At least two different positions must be provided to be merged")]
+    InvalidPositionsToMerge,
+
+    #[error("The shares of the emergency unlock penalty split must add up to 100%")]
+    InvalidPenaltySplit,
+
+    #[error("The farm is paused")]
+    FarmPaused,
+
+    #[error("The farm is not paused")]
+    FarmNotPaused,
+
+    #[error("The farm has no emissions left to be stopped")]
+    FarmNotEmitting,
+
+    #[error("The farm has been evicted")]
+    FarmEvicted,
+
+    #[error("The farm can't have more than {max} contributors")]
+    TooManyFarmContributors { max: u32 },
 }
 
 impl From<semver::Error> for ContractError {
@@ -1,60 +1,487 @@
 use std::collections::HashMap;
 
 use cosmwasm_std::{
-    ensure, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, Storage,
-    Uint128,
+    coin, ensure, wasm_execute, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128,
 };
 
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
 use mantra_dex_std::coin::aggregate_coins;
-use mantra_dex_std::farm_manager::{EpochId, Farm, RewardsResponse};
+use mantra_dex_std::farm_manager::{Curve, EpochId, EpochRewards, Farm, RewardsResponse};
+use mantra_dex_std::pool_manager::{
+    PoolsResponse, SimulateProvideLiquidityResponse, SwapOperation,
+};
 
-use crate::helpers::get_unique_lp_asset_denoms_from_positions;
+use crate::contract::COMPOUND_SWAP_REPLY_CODE;
+use crate::farm::{DEFAULT_COMPOUND_SLIPPAGE, LP_WEIGHT_HISTORY_PRUNE_LIMIT};
+use crate::gauge::commands::distribute_gauge_emissions;
+use crate::position::commands::refresh_boosts;
 use crate::state::{
-    get_earliest_address_lp_weight, get_farms_by_lp_denom, get_latest_address_lp_weight,
-    get_positions_by_receiver, CONFIG, FARMS, LAST_CLAIMED_EPOCH, LP_WEIGHT_HISTORY,
-    MAX_POSITIONS_LIMIT,
+    get_address_lp_denoms, get_address_lp_weight_at_epoch, get_earliest_address_lp_weight,
+    get_farm_by_identifier, get_farm_pauses, get_farms_by_lp_denom, get_latest_address_lp_weight,
+    get_position, ClaimOperatorApproval, CompoundSwapBuffer, FarmPause, CLAIMED_FARM_REWARDS,
+    CLAIMED_REWARDS, CLAIM_OPERATORS, COMPOUND_SWAP_BUFFER, CONFIG, FARMS, FARM_COUNTER,
+    LAST_CLAIMED_EPOCH, LAST_CLAIMED_FARM_EPOCH, LP_WEIGHT_HISTORY, MAX_FARMS_LIMIT,
+    MAX_LP_WEIGHT_HISTORY_LIMIT,
 };
 use crate::{helpers, ContractError};
 
-/// Claims pending rewards for farms where the user has LP
+/// Claims pending rewards for farms where the user has LP. The claim can be restricted to the given
+/// LP denoms and/or farms, in which case only the rewards of those are claimed.
+/// An approved claim operator can claim on behalf of the owner, in which case the rewards can only
+/// be sent to the owner or to the receiver allowed in the operator's approval.
 pub(crate) fn claim(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     until_epoch: Option<EpochId>,
+    lp_denoms: Option<Vec<String>>,
+    farm_identifiers: Option<Vec<String>>,
+    owner: Option<String>,
+    receiver: Option<String>,
 ) -> Result<Response, ContractError> {
     cw_utils::nonpayable(&info)?;
 
+    let owner = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+    let receiver = receiver
+        .map(|receiver| deps.api.addr_validate(&receiver))
+        .transpose()?
+        .unwrap_or_else(|| owner.clone());
+
+    if owner != info.sender {
+        let approval = assert_claim_operator(deps.storage, &env, &owner, &info.sender)?;
+
+        ensure!(
+            receiver == owner || approval.receiver.as_ref() == Some(&receiver),
+            ContractError::Unauthorized
+        );
+    }
+
     // check if the user has any open LP positions
-    let open_positions = get_positions_by_receiver(
-        deps.storage,
-        info.sender.as_str(),
-        Some(true),
-        None,
-        Some(MAX_POSITIONS_LIMIT),
+    let mut address_lp_denoms = get_address_lp_denoms(deps.storage, &owner)?;
+    ensure!(
+        !address_lp_denoms.is_empty(),
+        ContractError::NoOpenPositions
+    );
+
+    if let Some(lp_denoms) = &lp_denoms {
+        address_lp_denoms.retain(|lp_denom| lp_denoms.contains(lp_denom));
+    }
+
+    if let Some(farm_identifiers) = &farm_identifiers {
+        let farm_lp_denoms = farm_identifiers
+            .iter()
+            .map(|identifier| Ok(get_farm_by_identifier(deps.storage, identifier)?.lp_denom))
+            .collect::<Result<Vec<_>, ContractError>>()?;
+
+        address_lp_denoms.retain(|lp_denom| farm_lp_denoms.contains(lp_denom));
+    }
+
+    // make sure there's something to claim after filtering
+    ensure!(
+        !address_lp_denoms.is_empty(),
+        ContractError::NoOpenPositions
+    );
+    let lp_denoms = address_lp_denoms;
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.to_string(),
+    )?;
+
+    // distribute the gauge emissions for the current epoch so they can be claimed right away
+    let gauge_distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
+    let until_epoch = helpers::until_epoch_or_current(until_epoch, &current_epoch)?;
+
+    let total_rewards = claim_rewards(
+        deps.branch(),
+        &env,
+        &owner,
+        &lp_denoms,
+        until_epoch,
+        farm_identifiers.as_ref(),
+    )?;
+
+    refresh_boosts(deps, &env, &owner, &lp_denoms)?;
+
+    let mut messages = vec![];
+
+    // don't send any bank message if there's nothing to send
+    if !total_rewards.is_empty() {
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount: total_rewards,
+        }));
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "claim".to_string()),
+            ("owner", owner.to_string()),
+            ("receiver", receiver.to_string()),
+        ])
+        .add_attributes(gauge_distributions))
+}
+
+/// Claims the pending rewards of a position's LP denom and compounds them back into the position.
+/// The rewards matching the pool assets are provided as single-sided liquidity through the pool
+/// manager, which swaps half of them and locks the minted LP into the position. Rewards that are not
+/// assets of the pool are first swapped into one of them through the given swap operations, and
+/// provided as liquidity once the swap is done. Rewards that can't be compounded are sent to the
+/// receiver of the position.
+pub(crate) fn claim_and_compound(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    identifier: String,
+    swap_max_slippage: Option<Decimal>,
+    reward_swap_operations: Option<Vec<Vec<SwapOperation>>>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
+        ContractError::NoPositionFound {
+            identifier: identifier.clone(),
+        },
     )?;
-    ensure!(!open_positions.is_empty(), ContractError::NoOpenPositions);
+
+    ensure!(
+        position.open,
+        ContractError::PositionAlreadyClosed {
+            identifier: position.identifier.clone(),
+        }
+    );
+
+    // approved claim operators, i.e. auto-compounders, can compound on behalf of the receiver, but
+    // not with a higher slippage than the default one
+    let max_slippage = if position.receiver != info.sender {
+        assert_claim_operator(deps.storage, &env, &position.receiver, &info.sender)?;
+
+        swap_max_slippage
+            .unwrap_or(DEFAULT_COMPOUND_SLIPPAGE)
+            .min(DEFAULT_COMPOUND_SLIPPAGE)
+    } else {
+        swap_max_slippage.unwrap_or(DEFAULT_COMPOUND_SLIPPAGE)
+    };
 
     let config = CONFIG.load(deps.storage)?;
     let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
         deps.as_ref(),
-        config.epoch_manager_addr.into_string(),
+        config.epoch_manager_addr.to_string(),
     )?;
 
-    let mut total_rewards = vec![];
+    // distribute the gauge emissions for the current epoch so they can be compounded right away
+    let gauge_distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
 
-    let lp_denoms = get_unique_lp_asset_denoms_from_positions(open_positions);
+    let lp_denom = position.lp_asset.denom.clone();
+    let rewards = claim_rewards(
+        deps.branch(),
+        &env,
+        &position.receiver,
+        std::slice::from_ref(&lp_denom),
+        current_epoch.id,
+        None,
+    )?;
 
-    let until_epoch = helpers::until_epoch_or_current(until_epoch, &current_epoch)?;
+    refresh_boosts(
+        deps.branch(),
+        &env,
+        &position.receiver,
+        std::slice::from_ref(&lp_denom),
+    )?;
+
+    let pool_identifier = helpers::get_pool_identifier(&lp_denom)?;
+    let pools_response: PoolsResponse = deps.querier.query_wasm_smart(
+        config.pool_manager_addr.to_string(),
+        &mantra_dex_std::pool_manager::QueryMsg::Pools {
+            pool_identifier: Some(pool_identifier.clone()),
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    let pool_assets = pools_response
+        .pools
+        .first()
+        .ok_or(ContractError::AssetMismatch)?
+        .pool_info
+        .assets
+        .clone();
+
+    let (pool_rewards, mut uncompounded_rewards): (Vec<Coin>, Vec<Coin>) =
+        rewards.into_iter().partition(|reward| {
+            pool_assets
+                .iter()
+                .any(|pool_asset| pool_asset.denom == reward.denom)
+        });
+
+    // the pool rewards too small to be provided as liquidity are sent to the receiver instead
+    let mut compounded_rewards = vec![];
+    for reward in pool_rewards {
+        if is_compoundable(
+            deps.as_ref(),
+            &config.pool_manager_addr,
+            &pool_identifier,
+            &reward,
+        ) {
+            compounded_rewards.push(reward);
+        } else {
+            uncompounded_rewards.push(reward);
+        }
+    }
+
+    // the swaps are dispatched before anything else, and each swapped reward is provided as
+    // liquidity on the reply, so the balance of the ask asset before each swap is the current one
+    let mut submessages: Vec<SubMsg> = vec![];
+    let mut swapped_rewards: Vec<Coin> = vec![];
+    let mut swaps: Vec<(String, Uint128)> = vec![];
+
+    for operations in reward_swap_operations.unwrap_or_default() {
+        let (Some(first_operation), Some(last_operation)) = (operations.first(), operations.last())
+        else {
+            return Err(ContractError::InvalidRewardSwapOperations {
+                reason: "no swap operations provided".to_string(),
+            });
+        };
+
+        let offer_asset_denom = first_operation.get_input_asset_info().clone();
+        let ask_asset_denom = last_operation.get_target_asset_info();
+
+        ensure!(
+            pool_assets
+                .iter()
+                .any(|pool_asset| pool_asset.denom == ask_asset_denom),
+            ContractError::InvalidRewardSwapOperations {
+                reason: format!("{ask_asset_denom} is not an asset of the pool"),
+            }
+        );
+
+        // taking the reward out of the uncompounded ones also rejects duplicated routes
+        let reward_index = uncompounded_rewards
+            .iter()
+            .position(|reward| reward.denom == offer_asset_denom)
+            .ok_or_else(|| ContractError::InvalidRewardSwapOperations {
+                reason: format!("there are no {offer_asset_denom} rewards left to swap"),
+            })?;
+        let reward = uncompounded_rewards.remove(reward_index);
+
+        let ask_asset_balance = deps
+            .querier
+            .query_balance(&env.contract.address, &ask_asset_denom)?
+            .amount;
+
+        submessages.push(SubMsg::reply_on_success(
+            wasm_execute(
+                config.pool_manager_addr.to_string(),
+                &mantra_dex_std::pool_manager::ExecuteMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive: None,
+                    receiver: None,
+                    max_slippage: Some(max_slippage),
+                    referral: None,
+                },
+                vec![reward.clone()],
+            )?,
+            COMPOUND_SWAP_REPLY_CODE,
+        ));
+
+        swaps.push((ask_asset_denom, ask_asset_balance));
+        swapped_rewards.push(reward);
+    }
+
+    if !swaps.is_empty() {
+        COMPOUND_SWAP_BUFFER.save(
+            deps.storage,
+            &CompoundSwapBuffer {
+                position_identifier: position.identifier.clone(),
+                receiver: position.receiver.clone(),
+                unlocking_duration: position.unlocking_duration,
+                pool_identifier: pool_identifier.clone(),
+                max_slippage,
+                swaps,
+            },
+        )?;
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    for reward in compounded_rewards.iter() {
+        messages.push(
+            wasm_execute(
+                config.pool_manager_addr.to_string(),
+                &mantra_dex_std::pool_manager::ExecuteMsg::ProvideLiquidity {
+                    liquidity_max_slippage: Some(max_slippage),
+                    swap_max_slippage: Some(max_slippage),
+                    receiver: Some(position.receiver.to_string()),
+                    pool_identifier: pool_identifier.clone(),
+                    unlocking_duration: Some(position.unlocking_duration),
+                    lock_position_identifier: Some(position.identifier.clone()),
+                },
+                vec![reward.clone()],
+            )?
+            .into(),
+        );
+    }
+
+    if !uncompounded_rewards.is_empty() {
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: position.receiver.to_string(),
+            amount: uncompounded_rewards,
+        }));
+    }
+
+    Ok(Response::default()
+        .add_submessages(submessages)
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "claim_and_compound".to_string()),
+            ("identifier", position.identifier),
+            ("receiver", position.receiver.to_string()),
+            (
+                "compounded_rewards",
+                compounded_rewards
+                    .iter()
+                    .map(|reward| reward.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            (
+                "swapped_rewards",
+                swapped_rewards
+                    .iter()
+                    .map(|reward| reward.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ])
+        .add_attributes(gauge_distributions))
+}
+
+/// Provides the reward swapped into an asset of the pool when compounding a position as liquidity,
+/// locking the minted LP into the position. Called on the reply of each swap, in the order the
+/// swaps were dispatched.
+pub(crate) fn compound_swapped_reward(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut buffer = COMPOUND_SWAP_BUFFER.load(deps.storage)?;
+
+    ensure!(
+        !buffer.swaps.is_empty(),
+        StdError::generic_err("there are no pending compound swaps")
+    );
+
+    let (ask_asset_denom, ask_asset_balance) = buffer.swaps.remove(0);
+
+    if buffer.swaps.is_empty() {
+        COMPOUND_SWAP_BUFFER.remove(deps.storage);
+    } else {
+        COMPOUND_SWAP_BUFFER.save(deps.storage, &buffer)?;
+    }
+
+    let swapped_amount = deps
+        .querier
+        .query_balance(&env.contract.address, &ask_asset_denom)?
+        .amount
+        .saturating_sub(ask_asset_balance);
+    let swapped_reward = coin(swapped_amount.u128(), ask_asset_denom);
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    if !swapped_amount.is_zero() {
+        let config = CONFIG.load(deps.storage)?;
+
+        if is_compoundable(
+            deps.as_ref(),
+            &config.pool_manager_addr,
+            &buffer.pool_identifier,
+            &swapped_reward,
+        ) {
+            messages.push(
+                wasm_execute(
+                    config.pool_manager_addr.to_string(),
+                    &mantra_dex_std::pool_manager::ExecuteMsg::ProvideLiquidity {
+                        liquidity_max_slippage: Some(buffer.max_slippage),
+                        swap_max_slippage: Some(buffer.max_slippage),
+                        receiver: Some(buffer.receiver.to_string()),
+                        pool_identifier: buffer.pool_identifier,
+                        unlocking_duration: Some(buffer.unlocking_duration),
+                        lock_position_identifier: Some(buffer.position_identifier.clone()),
+                    },
+                    vec![swapped_reward.clone()],
+                )?
+                .into(),
+            );
+        } else {
+            // the swapped reward is too small to be provided as liquidity, send it to the receiver
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: buffer.receiver.to_string(),
+                amount: vec![swapped_reward.clone()],
+            }));
+        }
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "compound_swapped_reward".to_string()),
+            ("identifier", buffer.position_identifier),
+            ("swapped_reward", swapped_reward.to_string()),
+        ]))
+}
+
+/// Whether the given reward can be provided as single-sided liquidity to the pool. Rewards so small
+/// that the half swapped into the other asset, or the LP minted for them, would be zero can't be
+/// compounded, as the liquidity provision would revert.
+fn is_compoundable(
+    deps: Deps,
+    pool_manager_addr: &Addr,
+    pool_identifier: &str,
+    reward: &Coin,
+) -> bool {
+    deps.querier
+        .query_wasm_smart::<SimulateProvideLiquidityResponse>(
+            pool_manager_addr.to_string(),
+            &mantra_dex_std::pool_manager::QueryMsg::SimulateProvideLiquidity {
+                pool_identifier: pool_identifier.to_string(),
+                assets: vec![reward.clone()],
+            },
+        )
+        .is_ok_and(|simulation| {
+            !simulation.lp_asset.amount.is_zero()
+                && simulation
+                    .swap_offer_asset
+                    .is_some_and(|offer_asset| !offer_asset.amount.is_zero())
+                && simulation
+                    .swap_simulation
+                    .is_some_and(|swap| !swap.return_amount.is_zero())
+        })
+}
+
+/// Claims the rewards of the given LP denoms for the owner until the given epoch, updating the
+/// farms and the owner's claim state. Returns the claimed rewards.
+fn claim_rewards(
+    deps: DepsMut,
+    env: &Env,
+    owner: &Addr,
+    lp_denoms: &[String],
+    until_epoch: EpochId,
+    farm_identifiers: Option<&Vec<String>>,
+) -> Result<Vec<Coin>, ContractError> {
+    let mut total_rewards = vec![];
 
-    for lp_denom in &lp_denoms {
+    for lp_denom in lp_denoms {
         // calculate the rewards for the lp denom
         let rewards_response = calculate_rewards(
             deps.as_ref(),
-            &env,
+            env,
             lp_denom,
-            &info.sender,
+            owner,
             until_epoch,
+            farm_identifiers,
             true,
         )?;
 
@@ -67,7 +494,7 @@ pub(crate) fn claim(
 
                 // update the farms with the claimed rewards
                 for (farm_identifier, claimed_reward) in modified_farms {
-                    FARMS.update(
+                    let farm = FARMS.update(
                         deps.storage,
                         &farm_identifier,
                         |farm| -> Result<_, ContractError> {
@@ -84,37 +511,187 @@ pub(crate) fn claim(
                             Ok(farm)
                         },
                     )?;
+
+                    record_claimed_reward(
+                        deps.storage,
+                        owner,
+                        &farm_identifier,
+                        &Coin {
+                            denom: farm.farm_asset.denom,
+                            amount: claimed_reward,
+                        },
+                    )?;
                 }
 
-                // sync the address lp weight history for the user
-                sync_address_lp_weight_history(
-                    deps.storage,
-                    &info.sender,
-                    lp_denom,
-                    &until_epoch,
-                    true,
-                )?;
+                if let Some(farm_identifiers) = farm_identifiers {
+                    // only some farms were claimed, keep track of them individually. The lp weight
+                    // history is kept, as the rest of the farms still need it
+                    for farm in
+                        get_farms_by_lp_denom(deps.storage, lp_denom, None, Some(MAX_FARMS_LIMIT))?
+                            .into_iter()
+                            .filter(|farm| farm_identifiers.contains(&farm.identifier))
+                    {
+                        LAST_CLAIMED_FARM_EPOCH.update::<_, ContractError>(
+                            deps.storage,
+                            (owner, lp_denom, &farm.identifier),
+                            |last_claimed_epoch| {
+                                Ok(last_claimed_epoch.unwrap_or_default().max(until_epoch))
+                            },
+                        )?;
+                    }
+
+                    // the entries of the epochs all the farms were claimed for are not needed though
+                    prune_address_lp_weight_history(
+                        deps.storage,
+                        owner,
+                        lp_denom,
+                        LP_WEIGHT_HISTORY_PRUNE_LIMIT,
+                    )?;
+                } else {
+                    // sync the address lp weight history for the user
+                    sync_address_lp_weight_history(
+                        deps.storage,
+                        owner,
+                        lp_denom,
+                        &until_epoch,
+                        true,
+                    )?;
+
+                    // update the last claimed epoch for the user, which supersedes the epochs
+                    // the farms of this lp denom were individually claimed until
+                    LAST_CLAIMED_EPOCH.save(deps.storage, (owner, lp_denom), &until_epoch)?;
+                    clear_last_claimed_farm_epochs(
+                        deps.storage,
+                        owner,
+                        lp_denom,
+                        Some(until_epoch),
+                    )?;
+                }
             }
             _ => return Err(ContractError::Unauthorized),
         }
     }
 
-    // update the last claimed epoch for the user
-    LAST_CLAIMED_EPOCH.save(deps.storage, &info.sender, &until_epoch)?;
-
-    let mut messages = vec![];
+    Ok(aggregate_coins(total_rewards)?)
+}
 
-    // don't send any bank message if there's nothing to send
-    if !total_rewards.is_empty() {
-        messages.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: aggregate_coins(total_rewards)?,
-        }));
+/// Adds the given reward to the lifetime rewards the address claimed, both from the given farm and
+/// overall.
+fn record_claimed_reward(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    farm_identifier: &str,
+    reward: &Coin,
+) -> Result<(), ContractError> {
+    if reward.amount.is_zero() {
+        return Ok(());
     }
 
-    Ok(Response::default()
-        .add_messages(messages)
-        .add_attributes(vec![("action", "claim".to_string())]))
+    CLAIMED_FARM_REWARDS.update::<_, ContractError>(
+        storage,
+        (address, farm_identifier),
+        |claimed| {
+            let mut claimed = claimed.unwrap_or_default();
+            claimed.push(reward.clone());
+            Ok(aggregate_coins(claimed)?)
+        },
+    )?;
+
+    CLAIMED_REWARDS.update::<_, ContractError>(storage, (address, &reward.denom), |claimed| {
+        Ok(claimed.unwrap_or_default().checked_add(reward.amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Approves an operator to claim rewards on behalf of the sender until the approval expires,
+/// optionally allowing it to send the rewards to the given receiver besides the sender.
+pub(crate) fn approve_claim_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Expiration,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let operator = deps.api.addr_validate(&operator)?;
+    ensure!(operator != info.sender, ContractError::Unauthorized);
+
+    ensure!(
+        !expires.is_expired(&env.block),
+        ContractError::ClaimOperatorExpired
+    );
+
+    let receiver = receiver
+        .map(|receiver| deps.api.addr_validate(&receiver))
+        .transpose()?;
+
+    CLAIM_OPERATORS.save(
+        deps.storage,
+        (&info.sender, &operator),
+        &ClaimOperatorApproval {
+            expires,
+            receiver: receiver.clone(),
+        },
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "approve_claim_operator".to_string()),
+        ("owner", info.sender.to_string()),
+        ("operator", operator.to_string()),
+        ("expires", expires.to_string()),
+        (
+            "receiver",
+            receiver
+                .map(|receiver| receiver.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
+/// Revokes a claim operator previously approved by the sender.
+pub(crate) fn revoke_claim_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let operator = deps.api.addr_validate(&operator)?;
+    ensure!(
+        CLAIM_OPERATORS.has(deps.storage, (&info.sender, &operator)),
+        ContractError::Unauthorized
+    );
+
+    CLAIM_OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "revoke_claim_operator".to_string()),
+        ("owner", info.sender.to_string()),
+        ("operator", operator.to_string()),
+    ]))
+}
+
+/// Asserts the operator has a valid, non-expired approval to claim on behalf of the owner, and
+/// returns it.
+fn assert_claim_operator(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    operator: &Addr,
+) -> Result<ClaimOperatorApproval, ContractError> {
+    let approval = CLAIM_OPERATORS
+        .may_load(storage, (owner, operator))?
+        .ok_or(ContractError::Unauthorized)?;
+
+    ensure!(
+        !approval.expires.is_expired(&env.block),
+        ContractError::ClaimOperatorExpired
+    );
+
+    Ok(approval)
 }
 
 /// Calculates the rewards for a position
@@ -129,18 +706,19 @@ pub(crate) fn calculate_rewards(
     lp_denom: &str,
     receiver: &Addr,
     until_epoch_id: EpochId,
+    farm_identifiers: Option<&Vec<String>>,
     is_claim: bool,
 ) -> Result<RewardsResponse, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    // protocol farms, i.e. gauge and penalty redistribution farms, don't count towards the
+    // max_concurrent_farms limit, so fetch all of them
+    let mut farms = get_farms_by_lp_denom(deps.storage, lp_denom, None, Some(MAX_FARMS_LIMIT))?;
 
-    let farms = get_farms_by_lp_denom(
-        deps.storage,
-        lp_denom,
-        None,
-        Some(config.max_concurrent_farms),
-    )?;
+    if let Some(farm_identifiers) = farm_identifiers {
+        farms.retain(|farm| farm_identifiers.contains(&farm.identifier));
+    }
 
-    let last_claimed_epoch_for_user = LAST_CLAIMED_EPOCH.may_load(deps.storage, receiver)?;
+    let last_claimed_epoch_for_user =
+        LAST_CLAIMED_EPOCH.may_load(deps.storage, (receiver, lp_denom))?;
 
     // Check if the user ever claimed before
     if let Some(last_claimed_epoch) = last_claimed_epoch_for_user {
@@ -170,71 +748,16 @@ pub(crate) fn calculate_rewards(
     let mut modified_farms: HashMap<String, Uint128> = HashMap::new();
 
     for farm in farms {
-        // skip farms that have not started
-        if farm.start_epoch > until_epoch_id {
-            continue;
-        }
-
-        // compute where the user can start claiming rewards for the farm
-        let start_from_epoch = compute_start_from_epoch_for_address(
-            deps.storage,
-            &farm.lp_denom,
-            last_claimed_epoch_for_user,
+        let epoch_rewards = compute_farm_epoch_rewards(
+            deps,
+            env,
+            &farm,
             receiver,
+            last_claimed_epoch_for_user,
+            until_epoch_id,
         )?;
 
-        // compute the weights of the user for the epochs between start_from_epoch and current_epoch_id
-        let user_weights = compute_address_weights(
-            deps.storage,
-            receiver,
-            lp_denom,
-            &start_from_epoch,
-            &until_epoch_id,
-        )?;
-
-        // compute the weights of the contract for the epochs between start_from_epoch and current_epoch_id
-        let contract_weights = compute_contract_weights(
-            deps.storage,
-            &env.contract.address,
-            lp_denom,
-            &start_from_epoch,
-            &until_epoch_id,
-        )?;
-
-        // compute the farm emissions for the epochs between start_from_epoch and current_epoch_id
-        let (farm_emissions, until_epoch) =
-            compute_farm_emissions(&farm, &start_from_epoch, &until_epoch_id)?;
-
-        for epoch_id in start_from_epoch..=until_epoch {
-            if farm.start_epoch > epoch_id {
-                continue;
-            }
-
-            let user_weight = user_weights[&epoch_id];
-            let total_lp_weight = contract_weights
-                .get(&epoch_id)
-                .unwrap_or(&Uint128::zero())
-                .to_owned();
-
-            // skip epoch if the total lp weight is zero
-            if total_lp_weight.is_zero() {
-                continue;
-            }
-
-            let user_share = (user_weight, total_lp_weight);
-
-            let reward = farm_emissions
-                .get(&epoch_id)
-                .unwrap_or(&Uint128::zero())
-                .to_owned()
-                .checked_mul_floor(user_share)?;
-
-            // sanity check
-            ensure!(
-                reward.checked_add(farm.claimed_amount)? <= farm.farm_asset.amount,
-                ContractError::FarmExhausted
-            );
-
+        for EpochRewards { reward, .. } in epoch_rewards {
             if reward > Uint128::zero() {
                 rewards.push(Coin {
                     denom: farm.farm_asset.denom.clone(),
@@ -266,6 +789,133 @@ pub(crate) fn calculate_rewards(
     }
 }
 
+/// Computes the rewards the given address earns from a farm on each of the epochs it can claim, up
+/// to until_epoch_id. Epochs in which nobody had a weight in the farm's LP denom are skipped.
+pub(crate) fn compute_farm_epoch_rewards(
+    deps: Deps,
+    env: &Env,
+    farm: &Farm,
+    receiver: &Addr,
+    last_claimed_epoch_for_user: Option<EpochId>,
+    until_epoch_id: EpochId,
+) -> Result<Vec<EpochRewards>, ContractError> {
+    // skip farms that have not started
+    if farm.start_epoch > until_epoch_id {
+        return Ok(vec![]);
+    }
+
+    let lp_denom = farm.lp_denom.as_str();
+
+    // compute where the user can start claiming rewards for the farm
+    let start_from_epoch = compute_start_from_epoch_for_address(
+        deps.storage,
+        lp_denom,
+        last_claimed_epoch_for_user,
+        receiver,
+    )?;
+
+    // compute the weights of the user for the epochs between start_from_epoch and current_epoch_id
+    let user_weights = compute_address_weights(
+        deps.storage,
+        receiver,
+        lp_denom,
+        &start_from_epoch,
+        &until_epoch_id,
+    )?;
+
+    // compute the weights of the contract for the epochs between start_from_epoch and current_epoch_id
+    let contract_weights = compute_contract_weights(
+        deps.storage,
+        &env.contract.address,
+        lp_denom,
+        &start_from_epoch,
+        &until_epoch_id,
+    )?;
+
+    // compute the farm emissions for the epochs between start_from_epoch and current_epoch_id
+    let farm_pauses = get_farm_pauses(deps.storage, &farm.identifier)?;
+    let (farm_emissions, until_epoch) =
+        compute_farm_emissions(farm, &farm_pauses, &start_from_epoch, &until_epoch_id)?;
+
+    // the epochs the farm was individually claimed until, if any, have been claimed already
+    let last_claimed_farm_epoch =
+        LAST_CLAIMED_FARM_EPOCH.may_load(deps.storage, (receiver, lp_denom, &farm.identifier))?;
+
+    let mut epoch_rewards = vec![];
+
+    for epoch_id in start_from_epoch..=until_epoch {
+        if farm.start_epoch > epoch_id
+            || last_claimed_farm_epoch.is_some_and(|last_claimed| epoch_id <= last_claimed)
+        {
+            continue;
+        }
+
+        let user_weight = user_weights[&epoch_id];
+        let total_lp_weight = contract_weights
+            .get(&epoch_id)
+            .unwrap_or(&Uint128::zero())
+            .to_owned();
+
+        // skip epoch if the total lp weight is zero
+        if total_lp_weight.is_zero() {
+            continue;
+        }
+
+        let user_share = (user_weight, total_lp_weight);
+
+        let emission = farm_emissions
+            .get(&epoch_id)
+            .unwrap_or(&Uint128::zero())
+            .to_owned();
+        let reward = emission.checked_mul_floor(user_share)?;
+
+        // sanity check
+        ensure!(
+            reward.checked_add(farm.claimed_amount)? <= farm.farm_asset.amount,
+            ContractError::FarmExhausted
+        );
+
+        epoch_rewards.push(EpochRewards {
+            epoch_id,
+            emission,
+            lp_weight: user_weight,
+            total_lp_weight,
+            reward,
+        });
+    }
+
+    Ok(epoch_rewards)
+}
+
+/// Clears the epochs the farms of the given LP denom were individually claimed until by the address.
+/// If `until_epoch` is provided, only the entries up to that epoch are cleared.
+pub(crate) fn clear_last_claimed_farm_epochs(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    lp_denom: &str,
+    until_epoch: Option<EpochId>,
+) -> Result<(), ContractError> {
+    let farm_identifiers = LAST_CLAIMED_FARM_EPOCH
+        .prefix((address, lp_denom))
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((farm_identifier, last_claimed_epoch))
+                if until_epoch.is_none_or(|until_epoch| last_claimed_epoch <= until_epoch) =>
+            {
+                Some(Ok(farm_identifier))
+            }
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<String>>>()?;
+
+    for farm_identifier in farm_identifiers {
+        LAST_CLAIMED_FARM_EPOCH.remove(storage, (address, lp_denom, &farm_identifier));
+    }
+
+    Ok(())
+}
+
 /// Computes the epoch from which the user can start claiming rewards for a given farm
 pub(crate) fn compute_start_from_epoch_for_address(
     storage: &dyn Storage,
@@ -338,21 +988,34 @@ pub(crate) fn compute_contract_weights(
             (*start_from_epoch, weight)
         } else {
             // there's no weight recorded for start_from_epoch for the contract, which means nobody has
-            // opened or closed a position during the last epoch. Go fetch the last recoded
-            // lp weight and derive weights from there
-            let earliest_contract_lp_weight_result =
-                get_earliest_address_lp_weight(storage, contract, lp_asset_denom);
-
-            match earliest_contract_lp_weight_result {
-                Err(_) => {
-                    // it means the contract has not recorded a lp weight ever for this denom, which should
-                    // not happen if someone has ever created a position, and this wouldn't even reach this
-                    // point as the function to calculate farm rewards only loops on opened positions.
-                    return Err(ContractError::Unauthorized);
-                }
-                Ok((earliest_epoch_id, weight)) => {
-                    // some weight was recorded for the contract in the past, start from there
-                    (earliest_epoch_id, weight)
+            // opened or closed a position during the last epoch. Go fetch the last lp weight recorded
+            // before it and derive weights from there, so the iteration doesn't grow with the history
+            let previous_contract_lp_weight = LP_WEIGHT_HISTORY
+                .prefix((contract, lp_asset_denom))
+                .range(
+                    storage,
+                    None,
+                    Some(Bound::exclusive(*start_from_epoch)),
+                    Order::Descending,
+                )
+                .next()
+                .transpose()?;
+
+            match previous_contract_lp_weight {
+                Some((previous_epoch_id, weight)) => (previous_epoch_id, weight),
+                None => {
+                    match get_earliest_address_lp_weight(storage, contract, lp_asset_denom) {
+                        Err(_) => {
+                            // it means the contract has not recorded a lp weight ever for this denom, which should
+                            // not happen if someone has ever created a position, and this wouldn't even reach this
+                            // point as the function to calculate farm rewards only loops on opened positions.
+                            return Err(ContractError::Unauthorized);
+                        }
+                        Ok((earliest_epoch_id, weight)) => {
+                            // the contract recorded its first weight after start_from_epoch, start from there
+                            (earliest_epoch_id, weight)
+                        }
+                    }
                 }
             }
         };
@@ -378,13 +1041,13 @@ pub(crate) fn compute_contract_weights(
     Ok(contract_weights)
 }
 
-/// Computes the rewards emissions for a given farm. Let's assume for now that the farm
-/// is expanded by a multiple of the original emission rate.
+/// Computes the rewards emissions for a given farm, following the farm's emission curve.
 /// ### Returns
 /// A pair with the reward emissions for each epoch between start_from_epoch and the current_epoch_id in a hashmap
 /// and the last epoch for which the farm emissions were computed
 fn compute_farm_emissions(
     farm: &Farm,
+    farm_pauses: &[FarmPause],
     start_from_epoch: &EpochId,
     current_epoch_id: &EpochId,
 ) -> Result<(HashMap<EpochId, Uint128>, EpochId), ContractError> {
@@ -398,7 +1061,10 @@ fn compute_farm_emissions(
     };
 
     for epoch in *start_from_epoch..=until_epoch {
-        farm_emissions.insert(epoch, farm.emission_rate);
+        farm_emissions.insert(
+            epoch,
+            helpers::get_farm_emission_for_epoch(farm, farm_pauses, epoch)?,
+        );
     }
 
     Ok((farm_emissions, until_epoch))
@@ -433,3 +1099,176 @@ pub fn sync_address_lp_weight_history(
 
     Ok(())
 }
+
+/// Compacts the lp weight history of the address for the given lp denom, removing up to `limit` of
+/// the entries for the epochs every farm of the lp denom has been claimed for already. The weight in
+/// effect on the earliest of those claimed epochs is kept, as the weights of the following epochs
+/// are derived from it. Returns the number of entries removed.
+pub(crate) fn prune_address_lp_weight_history(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    lp_denom: &str,
+    limit: u32,
+) -> Result<u32, ContractError> {
+    let Some(last_claimed_epoch) = LAST_CLAIMED_EPOCH.may_load(storage, (address, lp_denom))?
+    else {
+        // nothing was claimed for the whole lp denom, the history is needed
+        return Ok(0);
+    };
+
+    // farms claimed individually may have been claimed beyond the last claimed epoch. Farms created
+    // later can't start in the past, so they don't need the history either
+    let mut claimed_until_epoch = None;
+    for farm in get_farms_by_lp_denom(storage, lp_denom, None, Some(MAX_FARMS_LIMIT))? {
+        let farm_claimed_until_epoch = LAST_CLAIMED_FARM_EPOCH
+            .may_load(storage, (address, lp_denom, &farm.identifier))?
+            .unwrap_or_default()
+            .max(last_claimed_epoch);
+
+        claimed_until_epoch = Some(
+            claimed_until_epoch
+                .unwrap_or(farm_claimed_until_epoch)
+                .min(farm_claimed_until_epoch),
+        );
+    }
+    let claimed_until_epoch = claimed_until_epoch.unwrap_or(last_claimed_epoch);
+
+    let stale_epochs = LP_WEIGHT_HISTORY
+        .prefix((address, lp_denom))
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(claimed_until_epoch)),
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<EpochId>>>()?;
+
+    if stale_epochs.is_empty() {
+        return Ok(0);
+    }
+
+    if !LP_WEIGHT_HISTORY.has(storage, (address, lp_denom, claimed_until_epoch)) {
+        let lp_weight =
+            get_address_lp_weight_at_epoch(storage, address, lp_denom, claimed_until_epoch)?;
+        LP_WEIGHT_HISTORY.save(
+            storage,
+            (address, lp_denom, claimed_until_epoch),
+            &lp_weight,
+        )?;
+    }
+
+    for epoch_id in &stale_epochs {
+        LP_WEIGHT_HISTORY.remove(storage, (address, lp_denom, *epoch_id));
+    }
+
+    Ok(stale_epochs.len() as u32)
+}
+
+/// Prunes the stale lp weight history of the given address, across all its LP denoms. Anyone can
+/// prune the history of any address, as only the entries of the epochs it has claimed already are
+/// removed.
+pub(crate) fn prune_history(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let mut limit = limit
+        .unwrap_or(LP_WEIGHT_HISTORY_PRUNE_LIMIT)
+        .min(MAX_LP_WEIGHT_HISTORY_LIMIT);
+
+    let mut pruned_entries = 0u32;
+
+    for lp_denom in get_address_lp_denoms(deps.storage, &address)? {
+        if limit == 0 {
+            break;
+        }
+
+        let pruned = prune_address_lp_weight_history(deps.storage, &address, &lp_denom, limit)?;
+        pruned_entries += pruned;
+        limit -= pruned;
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "prune_history".to_string()),
+        ("address", address.to_string()),
+        ("pruned_entries", pruned_entries.to_string()),
+    ]))
+}
+
+/// Adds the given asset to a step farm funded by the contract itself, emitting it on the given epoch.
+/// If the farm with the given identifier doesn't exist, a new one is created with the given prefix.
+/// These farms, i.e. the gauge and penalty farms, don't count towards the `max_concurrent_farms`
+/// limit, as there's at most one of each per LP denom and they can't be crowded out by user farms.
+/// Returns the identifier of the farm.
+pub(crate) fn upsert_step_farm(
+    storage: &mut dyn Storage,
+    env: &Env,
+    identifier: Option<String>,
+    identifier_prefix: &str,
+    lp_denom: &str,
+    epoch_id: EpochId,
+    farm_asset: Coin,
+) -> Result<String, ContractError> {
+    let existing_farm = match identifier {
+        Some(identifier) => FARMS.may_load(storage, &identifier)?,
+        None => None,
+    };
+
+    let mut farm = match existing_farm {
+        Some(mut farm) => {
+            if let Curve::Step { schedule } = &mut farm.curve {
+                match schedule.last_mut() {
+                    // the farm was already funded for this epoch, add to it
+                    Some((last_epoch_id, amount)) if *last_epoch_id == epoch_id => {
+                        *amount = amount.checked_add(farm_asset.amount)?;
+                    }
+                    _ => {
+                        // stop emitting on the epochs the farm wasn't funded for
+                        if epoch_id > farm.preliminary_end_epoch {
+                            schedule.push((farm.preliminary_end_epoch, Uint128::zero()));
+                        }
+
+                        schedule.push((epoch_id, farm_asset.amount));
+                    }
+                }
+            }
+
+            farm.farm_asset.amount = farm.farm_asset.amount.checked_add(farm_asset.amount)?;
+            farm.preliminary_end_epoch = epoch_id + 1u64;
+            farm
+        }
+        None => {
+            let farm_id =
+                FARM_COUNTER.update::<_, StdError>(storage, |current_id| Ok(current_id + 1u64))?;
+
+            Farm {
+                identifier: format!("{identifier_prefix}{farm_id}"),
+                start_epoch: epoch_id,
+                preliminary_end_epoch: epoch_id + 1u64,
+                curve: Curve::Step {
+                    schedule: vec![(epoch_id, farm_asset.amount)],
+                },
+                emission_rate: farm_asset.amount,
+                farm_asset,
+                lp_denom: lp_denom.to_string(),
+                owner: env.contract.address.clone(),
+                claimed_amount: Uint128::zero(),
+            }
+        }
+    };
+
+    // average emission per epoch, the actual emissions are derived from the schedule
+    farm.emission_rate = farm
+        .farm_asset
+        .amount
+        .checked_div_floor((farm.preliminary_end_epoch - farm.start_epoch, 1u64))?;
+
+    FARMS.save(storage, &farm.identifier, &farm)?;
+
+    Ok(farm.identifier)
+}
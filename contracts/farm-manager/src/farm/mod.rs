@@ -1,3 +1,5 @@
+use cosmwasm_std::Decimal;
+
 pub mod commands;
 #[cfg(test)]
 mod tests;
@@ -7,3 +9,17 @@ pub const EXPLICIT_FARM_ID_PREFIX: &str = "m-";
 
 /// The prefix used when creation a farm with an auto-generated ID
 pub const AUTO_FARM_ID_PREFIX: &str = "f-";
+
+/// The prefix used for the farms redistributing the emergency unlock penalties to the remaining LPs
+pub const PENALTY_FARM_ID_PREFIX: &str = "r-";
+
+/// The default slippage when compounding rewards, which is also the maximum slippage claim operators
+/// can compound with. It matches the default slippage of the pool manager
+pub const DEFAULT_COMPOUND_SLIPPAGE: Decimal = Decimal::percent(1);
+
+/// The maximum number of addresses, besides its owner, that can contribute to a farm
+pub const MAX_FARM_CONTRIBUTORS: u32 = 10;
+
+/// The maximum number of stale lp weight history entries removed at once, and the amount removed
+/// automatically when claiming or updating a position
+pub const LP_WEIGHT_HISTORY_PRUNE_LIMIT: u32 = 10;
@@ -1,6 +1,7 @@
 use crate::farm::commands::{
     compute_address_weights, compute_contract_weights, compute_start_from_epoch_for_address,
 };
+use crate::helpers::{get_farm_emission_for_epoch, validate_farm_curve};
 use crate::state::LP_WEIGHT_HISTORY;
 use cosmwasm_std::testing::mock_dependencies;
 use cosmwasm_std::{Addr, Coin, Uint128};
@@ -293,3 +294,151 @@ fn compute_contract_weights_successfully() {
     assert_eq!(weights.get(&9).unwrap(), &Uint128::new(9_000));
     assert_eq!(weights.get(&10).unwrap(), &Uint128::new(10_000));
 }
+
+#[test]
+fn compute_farm_emissions_for_curves_successfully() {
+    let mut farm = Farm {
+        identifier: "farm".to_string(),
+        owner: Addr::unchecked("owner"),
+        lp_denom: "lp".to_string(),
+        farm_asset: Coin {
+            denom: "farm".to_string(),
+            amount: Uint128::new(15_000),
+        },
+        claimed_amount: Default::default(),
+        emission_rate: Uint128::new(3_750),
+        curve: Curve::Linear,
+        start_epoch: 10,
+        preliminary_end_epoch: 14,
+    };
+
+    let emissions = |farm: &Farm| -> Vec<Uint128> {
+        (9u64..=14u64)
+            .map(|epoch_id| get_farm_emission_for_epoch(farm, &[], epoch_id).unwrap())
+            .collect()
+    };
+
+    // epochs outside the farm emit nothing, the preliminary_end_epoch is not inclusive
+    assert_eq!(
+        emissions(&farm),
+        vec![0u128, 3_750, 3_750, 3_750, 3_750, 0]
+            .into_iter()
+            .map(Uint128::new)
+            .collect::<Vec<_>>()
+    );
+
+    // the emission halves on every epoch
+    farm.curve = Curve::ExponentialDecay {
+        half_life_epochs: 1,
+    };
+    validate_farm_curve(&farm.curve, 10, 14, farm.farm_asset.amount).unwrap();
+    assert_eq!(
+        emissions(&farm),
+        vec![0u128, 8_000, 4_000, 2_000, 1_000, 0]
+            .into_iter()
+            .map(Uint128::new)
+            .collect::<Vec<_>>()
+    );
+
+    // the emission halves every two epochs
+    farm.curve = Curve::ExponentialDecay {
+        half_life_epochs: 2,
+    };
+    assert_eq!(
+        emissions(&farm),
+        vec![0u128, 5_000, 5_000, 2_500, 2_500, 0]
+            .into_iter()
+            .map(Uint128::new)
+            .collect::<Vec<_>>()
+    );
+
+    // each step emits its amount until the next one
+    farm.curve = Curve::Step {
+        schedule: vec![(10, Uint128::new(1_500)), (12, Uint128::new(6_000))],
+    };
+    validate_farm_curve(&farm.curve, 10, 14, farm.farm_asset.amount).unwrap();
+    assert_eq!(
+        emissions(&farm),
+        vec![0u128, 1_500, 1_500, 6_000, 6_000, 0]
+            .into_iter()
+            .map(Uint128::new)
+            .collect::<Vec<_>>()
+    );
+
+    // everything is released on the last epoch
+    farm.curve = Curve::Cliff;
+    assert_eq!(
+        emissions(&farm),
+        vec![0u128, 0, 0, 0, 15_000, 0]
+            .into_iter()
+            .map(Uint128::new)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn validate_farm_curves() {
+    let amount = Uint128::new(8_000);
+
+    validate_farm_curve(&Curve::Linear, 10, 14, amount).unwrap();
+    validate_farm_curve(&Curve::Cliff, 10, 14, amount).unwrap();
+    validate_farm_curve(
+        &Curve::ExponentialDecay {
+            half_life_epochs: 0,
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap_err();
+
+    // the schedule must start at the start epoch
+    validate_farm_curve(
+        &Curve::Step {
+            schedule: vec![(11, Uint128::new(2_000)), (12, Uint128::new(2_000))],
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap_err();
+    // the schedule epochs must be increasing
+    validate_farm_curve(
+        &Curve::Step {
+            schedule: vec![(10, Uint128::new(2_000)), (10, Uint128::new(2_000))],
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap_err();
+    // the schedule must be within the farm epochs
+    validate_farm_curve(
+        &Curve::Step {
+            schedule: vec![(10, Uint128::new(2_000)), (14, Uint128::new(2_000))],
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap_err();
+    // the schedule must emit the whole farm
+    validate_farm_curve(
+        &Curve::Step {
+            schedule: vec![(10, Uint128::new(1_000)), (12, Uint128::new(2_000))],
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap_err();
+    validate_farm_curve(
+        &Curve::Step {
+            schedule: vec![(10, Uint128::new(1_000)), (12, Uint128::new(3_000))],
+        },
+        10,
+        14,
+        amount,
+    )
+    .unwrap();
+}
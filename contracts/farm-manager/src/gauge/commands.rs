@@ -0,0 +1,372 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{
+    coin, ensure, Addr, Decimal, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+    Uint128,
+};
+
+use mantra_dex_std::farm_manager::{EpochId, GaugeBudget, GaugeVote};
+
+use crate::farm::commands::upsert_step_farm;
+use crate::gauge::GAUGE_FARM_ID_PREFIX;
+use crate::helpers::validate_lp_denom;
+use crate::state::{
+    get_address_lp_denoms, get_address_lp_weight_at_epoch, get_latest_address_lp_weight,
+    ADDRESS_GAUGE_VOTES, CONFIG, GAUGE_BUDGET, GAUGE_FARMS, GAUGE_VOTES,
+    LAST_GAUGE_DISTRIBUTION_EPOCH,
+};
+use crate::ContractError;
+
+/// Funds the gauge emission budget, setting the amount to be distributed every epoch. Only the
+/// owner of the contract can fund the gauges.
+pub(crate) fn fund_gauge(
+    deps: DepsMut,
+    info: MessageInfo,
+    emission_per_epoch: Uint128,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    ensure!(
+        emission_per_epoch > Uint128::zero(),
+        ContractError::InvalidGaugeEmission
+    );
+
+    let funds = cw_utils::one_coin(&info)?;
+
+    let budget = match GAUGE_BUDGET.may_load(deps.storage)? {
+        Some(mut budget) => {
+            // the gauge farms are denominated in the budget denom, it can't be changed
+            ensure!(budget.denom == funds.denom, ContractError::AssetMismatch);
+
+            budget.balance = budget.balance.checked_add(funds.amount)?;
+            budget.emission_per_epoch = emission_per_epoch;
+            budget
+        }
+        None => GaugeBudget {
+            denom: funds.denom,
+            balance: funds.amount,
+            emission_per_epoch,
+        },
+    };
+
+    GAUGE_BUDGET.save(deps.storage, &budget)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "fund_gauge".to_string()),
+        ("gauge_denom", budget.denom),
+        ("gauge_balance", budget.balance.to_string()),
+        ("emission_per_epoch", budget.emission_per_epoch.to_string()),
+    ]))
+}
+
+/// Casts the gauge votes of the sender for the current epoch, directing the emissions of the next
+/// epoch. The voting power is the weight the sender has on the current epoch, see
+/// [get_gauge_voting_power]. Voting again within the same epoch replaces the previous votes.
+pub(crate) fn gauge_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<GaugeVote>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    // distribute the emissions for the current epoch before the new votes are cast
+    let distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
+    let voting_power = get_gauge_voting_power(
+        deps.storage,
+        &env.contract.address,
+        &info.sender,
+        current_epoch.id,
+    )?;
+
+    ensure!(!voting_power.is_zero(), ContractError::NoVotingPower);
+
+    let mut lp_denoms = HashSet::new();
+    let mut total_vote_weight = Decimal::zero();
+
+    for vote in &votes {
+        validate_lp_denom(
+            deps.storage,
+            &vote.lp_denom,
+            config.pool_manager_addr.as_str(),
+        )?;
+
+        ensure!(
+            lp_denoms.insert(vote.lp_denom.as_str()),
+            ContractError::InvalidGaugeVotes {
+                reason: format!("duplicated vote for {}", vote.lp_denom)
+            }
+        );
+
+        ensure!(
+            !vote.weight.is_zero(),
+            ContractError::InvalidGaugeVotes {
+                reason: format!("zero weight for {}", vote.lp_denom)
+            }
+        );
+
+        total_vote_weight = total_vote_weight.checked_add(vote.weight)?;
+    }
+
+    ensure!(
+        total_vote_weight <= Decimal::one(),
+        ContractError::InvalidGaugeVotes {
+            reason: "the vote weights add up to more than 100%".to_string()
+        }
+    );
+
+    // remove the votes previously cast by the sender during this epoch
+    let previous_votes = ADDRESS_GAUGE_VOTES
+        .may_load(deps.storage, (current_epoch.id, &info.sender))?
+        .unwrap_or_default();
+
+    for (lp_denom, vote_weight) in previous_votes {
+        let tally = GAUGE_VOTES
+            .may_load(deps.storage, (current_epoch.id, &lp_denom))?
+            .unwrap_or_default()
+            .saturating_sub(vote_weight);
+
+        if tally.is_zero() {
+            GAUGE_VOTES.remove(deps.storage, (current_epoch.id, &lp_denom));
+        } else {
+            GAUGE_VOTES.save(deps.storage, (current_epoch.id, &lp_denom), &tally)?;
+        }
+    }
+
+    let mut cast_votes = vec![];
+
+    for vote in votes {
+        let vote_weight = voting_power.checked_mul_floor(vote.weight)?;
+
+        if vote_weight.is_zero() {
+            continue;
+        }
+
+        GAUGE_VOTES.update::<_, ContractError>(
+            deps.storage,
+            (current_epoch.id, &vote.lp_denom),
+            |tally| Ok(tally.unwrap_or_default().checked_add(vote_weight)?),
+        )?;
+
+        cast_votes.push((vote.lp_denom, vote_weight));
+    }
+
+    if cast_votes.is_empty() {
+        ADDRESS_GAUGE_VOTES.remove(deps.storage, (current_epoch.id, &info.sender));
+    } else {
+        ADDRESS_GAUGE_VOTES.save(deps.storage, (current_epoch.id, &info.sender), &cast_votes)?;
+    }
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "gauge_vote".to_string()),
+            ("voter", info.sender.to_string()),
+            ("epoch_id", current_epoch.id.to_string()),
+            ("voting_power", voting_power.to_string()),
+        ])
+        .add_attributes(distributions))
+}
+
+/// Gets the gauge voting power of an address on the given epoch, i.e. the sum of the voting power
+/// it gets from each LP denom, see [get_gauge_lp_voting_power].
+pub(crate) fn get_gauge_voting_power(
+    storage: &dyn Storage,
+    contract_address: &Addr,
+    address: &Addr,
+    epoch_id: EpochId,
+) -> Result<Uint128, ContractError> {
+    let mut voting_power = Uint128::zero();
+
+    for lp_denom in get_address_lp_denoms(storage, address)? {
+        voting_power = voting_power.checked_add(get_gauge_lp_voting_power(
+            storage,
+            contract_address,
+            address,
+            &lp_denom,
+            epoch_id,
+        )?)?;
+    }
+
+    Ok(voting_power)
+}
+
+/// Gets the gauge voting power an address gets from the given LP denom on the given epoch. It's the
+/// share of the total weight of the LP denom the address had at the start of the epoch, so positions
+/// opened during the epoch can't vote until the next one, and LP denoms with different unit values
+/// weigh the same. If the weight goes down during the epoch, the lower weight is used instead.
+/// The share is expressed with 18 decimals, i.e. the whole weight of an LP denom is 10^18.
+pub(crate) fn get_gauge_lp_voting_power(
+    storage: &dyn Storage,
+    contract_address: &Addr,
+    address: &Addr,
+    lp_denom: &str,
+    epoch_id: EpochId,
+) -> Result<Uint128, ContractError> {
+    let epoch_lp_weight = get_address_lp_weight_at_epoch(storage, address, lp_denom, epoch_id)?;
+    let (_, latest_lp_weight) =
+        get_latest_address_lp_weight(storage, address, lp_denom, &epoch_id)?;
+    let total_lp_weight =
+        get_address_lp_weight_at_epoch(storage, contract_address, lp_denom, epoch_id)?;
+
+    if total_lp_weight.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    Ok(Decimal::from_ratio(epoch_lp_weight.min(latest_lp_weight), total_lp_weight).atomics())
+}
+
+/// Rescales the gauge votes an address cast on the given epoch to its current voting power, given
+/// the voting power it had before its weight changed. Called whenever the weight of an address
+/// changes, so its votes stay backed by its positions.
+pub(crate) fn rescale_gauge_votes(
+    storage: &mut dyn Storage,
+    contract_address: &Addr,
+    address: &Addr,
+    epoch_id: EpochId,
+    previous_voting_power: Uint128,
+) -> Result<(), ContractError> {
+    let Some(votes) = ADDRESS_GAUGE_VOTES.may_load(storage, (epoch_id, address))? else {
+        return Ok(());
+    };
+
+    let voting_power = get_gauge_voting_power(storage, contract_address, address, epoch_id)?;
+    if voting_power == previous_voting_power || previous_voting_power.is_zero() {
+        return Ok(());
+    }
+
+    let mut rescaled_votes = vec![];
+
+    for (lp_denom, vote_weight) in votes {
+        let rescaled_vote_weight =
+            vote_weight.checked_mul_floor((voting_power, previous_voting_power))?;
+
+        let tally = GAUGE_VOTES
+            .may_load(storage, (epoch_id, &lp_denom))?
+            .unwrap_or_default()
+            .saturating_sub(vote_weight)
+            .checked_add(rescaled_vote_weight)?;
+
+        if tally.is_zero() {
+            GAUGE_VOTES.remove(storage, (epoch_id, &lp_denom));
+        } else {
+            GAUGE_VOTES.save(storage, (epoch_id, &lp_denom), &tally)?;
+        }
+
+        if !rescaled_vote_weight.is_zero() {
+            rescaled_votes.push((lp_denom, rescaled_vote_weight));
+        }
+    }
+
+    if rescaled_votes.is_empty() {
+        ADDRESS_GAUGE_VOTES.remove(storage, (epoch_id, address));
+    } else {
+        ADDRESS_GAUGE_VOTES.save(storage, (epoch_id, address), &rescaled_votes)?;
+    }
+
+    Ok(())
+}
+
+/// Distributes the gauge emissions for the current epoch. Permissionless, as the distribution is
+/// also triggered when voting or claiming.
+pub(crate) fn distribute_gauges(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    let distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "distribute_gauges".to_string()),
+            ("epoch_id", current_epoch.id.to_string()),
+        ])
+        .add_attributes(distributions))
+}
+
+/// Splits the gauge emission of the current epoch across the LP denoms in proportion to the votes
+/// cast during the previous epoch, creating or expanding the gauge farm of each LP denom. Emissions
+/// are distributed at most once per epoch; if no distribution takes place during an epoch, its
+/// emission stays in the budget.
+/// ### Returns
+/// The attributes describing the distribution, if any
+pub(crate) fn distribute_gauge_emissions(
+    storage: &mut dyn Storage,
+    env: &Env,
+    current_epoch_id: EpochId,
+) -> Result<Vec<(String, String)>, ContractError> {
+    let Some(mut budget) = GAUGE_BUDGET.may_load(storage)? else {
+        return Ok(vec![]);
+    };
+
+    let last_distribution_epoch = LAST_GAUGE_DISTRIBUTION_EPOCH.may_load(storage)?;
+    if current_epoch_id == 0u64 || last_distribution_epoch.is_some_and(|e| e >= current_epoch_id) {
+        return Ok(vec![]);
+    }
+
+    LAST_GAUGE_DISTRIBUTION_EPOCH.save(storage, &current_epoch_id)?;
+
+    let tallies = GAUGE_VOTES
+        .prefix(current_epoch_id - 1u64)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+
+    let total_votes = tallies
+        .iter()
+        .try_fold(Uint128::zero(), |acc, (_, tally)| acc.checked_add(*tally))?;
+
+    if total_votes.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let emission = budget.emission_per_epoch.min(budget.balance);
+    let mut distributed = Uint128::zero();
+    let mut attributes = vec![];
+
+    for (lp_denom, tally) in tallies {
+        let amount = emission.checked_mul_floor((tally, total_votes))?;
+
+        if amount.is_zero() {
+            continue;
+        }
+
+        let gauge_farm = GAUGE_FARMS.may_load(storage, &lp_denom)?;
+        let farm_identifier = upsert_step_farm(
+            storage,
+            env,
+            gauge_farm,
+            GAUGE_FARM_ID_PREFIX,
+            &lp_denom,
+            current_epoch_id,
+            coin(amount.u128(), &budget.denom),
+        )?;
+        GAUGE_FARMS.save(storage, &lp_denom, &farm_identifier)?;
+
+        distributed = distributed.checked_add(amount)?;
+        attributes.push((
+            "gauge_farm".to_string(),
+            format!("{farm_identifier}:{amount}"),
+        ));
+    }
+
+    budget.balance = budget.balance.checked_sub(distributed)?;
+    GAUGE_BUDGET.save(storage, &budget)?;
+
+    attributes.push(("gauge_emission".to_string(), distributed.to_string()));
+
+    Ok(attributes)
+}
@@ -0,0 +1,4 @@
+pub mod commands;
+
+/// The prefix used for the farms created by the gauges
+pub const GAUGE_FARM_ID_PREFIX: &str = "g-";
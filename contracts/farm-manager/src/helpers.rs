@@ -1,17 +1,18 @@
 use cosmwasm_std::{
-    ensure, BankMsg, Coin, CosmosMsg, Decimal, Deps, Env, MessageInfo, OverflowError,
-    OverflowOperation, Uint128,
+    ensure, BankMsg, Coin, CosmosMsg, Decimal, Deps, Env, MessageInfo, Order, OverflowError,
+    OverflowOperation, Storage, Uint128,
 };
 use std::cmp::Ordering;
-use std::collections::HashSet;
 
 use mantra_dex_std::coin::{get_factory_token_creator, is_factory_token};
-use mantra_dex_std::constants::MONTH_IN_SECONDS;
+use mantra_dex_std::constants::{LP_SYMBOL, MONTH_IN_SECONDS};
 use mantra_dex_std::epoch_manager::{Epoch, EpochResponse, QueryMsg};
 use mantra_dex_std::farm_manager::{
-    Config, EpochId, Farm, FarmParams, Position, DEFAULT_FARM_DURATION,
+    BoostConfig, Config, Curve, EpochId, Farm, FarmParams, PenaltySplit, DEFAULT_FARM_DURATION,
 };
 
+use crate::position::MAX_BOOST;
+use crate::state::{get_farm_paused_from, FarmPause, FARM_ASSETS, STAKEABLE_DENOMS};
 use crate::ContractError;
 
 /// Processes the farm creation fee and returns the appropriate messages to be sent
@@ -176,6 +177,181 @@ pub(crate) fn validate_farm_epochs(
     Ok((start_epoch, preliminary_end_epoch))
 }
 
+/// Validates the emission curve of a farm against its epochs and the amount to be distributed.
+pub(crate) fn validate_farm_curve(
+    curve: &Curve,
+    start_epoch: EpochId,
+    preliminary_end_epoch: EpochId,
+    farm_amount: Uint128,
+) -> Result<(), ContractError> {
+    match curve {
+        Curve::Linear | Curve::Cliff => {}
+        Curve::ExponentialDecay { half_life_epochs } => {
+            ensure!(
+                *half_life_epochs > 0u64,
+                ContractError::InvalidFarmCurve {
+                    reason: "the half life must be at least one epoch".to_string()
+                }
+            );
+        }
+        Curve::Step { schedule } => {
+            ensure!(
+                schedule.first().map(|(epoch, _)| *epoch) == Some(start_epoch),
+                ContractError::InvalidFarmCurve {
+                    reason: "the schedule must begin at the start epoch".to_string()
+                }
+            );
+
+            ensure!(
+                schedule.windows(2).all(|steps| steps[0].0 < steps[1].0),
+                ContractError::InvalidFarmCurve {
+                    reason: "the schedule epochs must be strictly increasing".to_string()
+                }
+            );
+
+            ensure!(
+                schedule
+                    .last()
+                    .is_some_and(|(epoch, _)| *epoch < preliminary_end_epoch),
+                ContractError::InvalidFarmCurve {
+                    reason: "the schedule must end before the preliminary end epoch".to_string()
+                }
+            );
+
+            // each step emits its amount on every epoch until the next step, or until the farm ends
+            let mut scheduled_amount = Uint128::zero();
+            for (i, (epoch, amount)) in schedule.iter().enumerate() {
+                let next_epoch = schedule
+                    .get(i + 1)
+                    .map(|(next_epoch, _)| *next_epoch)
+                    .unwrap_or(preliminary_end_epoch);
+
+                scheduled_amount = scheduled_amount
+                    .checked_add(amount.checked_mul(Uint128::from(next_epoch - epoch))?)?;
+            }
+
+            ensure!(
+                scheduled_amount == farm_amount,
+                ContractError::InvalidFarmCurve {
+                    reason: format!(
+                        "the schedule emits {scheduled_amount} but the farm holds {farm_amount}"
+                    )
+                }
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the emissions of a farm between the given epochs, until_epoch not inclusive, as
+/// (epoch_id, amount) steps where each amount is emitted on every epoch until the next step.
+pub(crate) fn get_farm_emission_schedule(
+    farm: &Farm,
+    pauses: &[FarmPause],
+    from_epoch: EpochId,
+    until_epoch: EpochId,
+) -> Result<Vec<(EpochId, Uint128)>, ContractError> {
+    let mut schedule: Vec<(EpochId, Uint128)> = vec![];
+
+    for epoch_id in from_epoch..until_epoch {
+        let emission = get_farm_emission_for_epoch(farm, pauses, epoch_id)?;
+
+        if !matches!(schedule.last(), Some((_, amount)) if *amount == emission) {
+            schedule.push((epoch_id, emission));
+        }
+    }
+
+    Ok(schedule)
+}
+
+/// Gets the amount a farm emits on the given epoch according to its curve. Epochs outside the
+/// farm's range emit nothing, keeping in mind the preliminary_end_epoch is not inclusive. Epochs the
+/// farm was paused for emit nothing either, and the curve is shifted by the number of paused epochs
+/// once the farm is resumed.
+pub(crate) fn get_farm_emission_for_epoch(
+    farm: &Farm,
+    pauses: &[FarmPause],
+    epoch_id: EpochId,
+) -> Result<Uint128, ContractError> {
+    if epoch_id < farm.start_epoch || epoch_id >= farm.preliminary_end_epoch {
+        return Ok(Uint128::zero());
+    }
+
+    // the preliminary_end_epoch is pushed back by the paused epochs when resuming the farm, so
+    // remove them to get the epochs the farm emits on as if it had never been paused
+    let mut paused_epochs_before = 0u64;
+    let mut paused_epochs = 0u64;
+    for pause in pauses {
+        match pause.resumed_from {
+            Some(resumed_from) => {
+                let pause_duration = resumed_from - pause.paused_from;
+                if epoch_id >= resumed_from {
+                    paused_epochs_before += pause_duration;
+                } else if epoch_id >= pause.paused_from {
+                    return Ok(Uint128::zero());
+                }
+                paused_epochs += pause_duration;
+            }
+            None if epoch_id >= pause.paused_from => return Ok(Uint128::zero()),
+            None => {}
+        }
+    }
+
+    let epoch_id = epoch_id - paused_epochs_before;
+    let preliminary_end_epoch = farm.preliminary_end_epoch - paused_epochs;
+
+    match &farm.curve {
+        Curve::Linear => Ok(farm.emission_rate),
+        Curve::Cliff => {
+            // the whole farm is released on its last epoch
+            if epoch_id == preliminary_end_epoch - 1u64 {
+                Ok(farm.farm_asset.amount)
+            } else {
+                Ok(Uint128::zero())
+            }
+        }
+        Curve::Step { schedule } => Ok(schedule
+            .iter()
+            .rev()
+            .find(|(epoch, _)| *epoch <= epoch_id)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()),
+        Curve::ExponentialDecay { half_life_epochs } => {
+            let half_life_epochs = *half_life_epochs;
+            let half = Decimal::percent(50);
+            let duration = preliminary_end_epoch - farm.start_epoch;
+
+            // the emission halves every half_life_epochs
+            let halvings = (epoch_id - farm.start_epoch) / half_life_epochs;
+            let weight = half.checked_pow(u32::try_from(halvings).unwrap_or(u32::MAX))?;
+
+            // add up the weights of all the epochs of the farm, using the same truncated values as
+            // above so the emissions never add up to more than the farm amount
+            let mut total_weight = Decimal::zero();
+            for period in 0..duration.div_ceil(half_life_epochs) {
+                let period_weight = half.checked_pow(u32::try_from(period).unwrap_or(u32::MAX))?;
+
+                // the weights are negligible from here on
+                if period_weight.is_zero() {
+                    break;
+                }
+
+                let period_epochs = half_life_epochs.min(duration - period * half_life_epochs);
+                total_weight = total_weight.checked_add(
+                    period_weight.checked_mul(Decimal::from_ratio(period_epochs, 1u64))?,
+                )?;
+            }
+
+            // multiply before dividing, so the share of the farm isn't truncated twice
+            Ok(farm
+                .farm_asset
+                .amount
+                .checked_mul_floor((weight.atomics(), total_weight.atomics()))?)
+        }
+    }
+}
+
 /// Validates the emergency unlock penalty is within the allowed range (0-100%). Returns value it's validating, i.e. the penalty.
 pub(crate) fn validate_emergency_unlock_penalty(
     emergency_unlock_penalty: Decimal,
@@ -188,11 +364,77 @@ pub(crate) fn validate_emergency_unlock_penalty(
     Ok(emergency_unlock_penalty)
 }
 
-/// Validates that the denom was created by the pool manager, i.e. it belongs to a valid pool.
+/// Validates the farm asset is allowed to be used to create farms, and that the farm is created with
+/// at least the minimum amount set for it. Any farm asset is allowed while the allowlist is empty.
+pub(crate) fn validate_farm_asset_allowed(
+    storage: &dyn Storage,
+    farm_asset: &Coin,
+) -> Result<(), ContractError> {
+    if FARM_ASSETS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    let min_amount = FARM_ASSETS
+        .may_load(storage, &farm_asset.denom)?
+        .ok_or_else(|| ContractError::FarmAssetNotAllowed {
+            denom: farm_asset.denom.clone(),
+        })?;
+
+    ensure!(
+        farm_asset.amount >= min_amount,
+        ContractError::InvalidFarmAmount {
+            min: min_amount.u128()
+        }
+    );
+
+    Ok(())
+}
+
+/// Validates the max boost is between 1x and [MAX_BOOST], and that some stake is required to get it.
+pub(crate) fn validate_boost_config(boost_config: &BoostConfig) -> Result<(), ContractError> {
+    ensure!(
+        boost_config.max_boost >= Decimal::one()
+            && boost_config.max_boost <= MAX_BOOST
+            && !boost_config.stake_per_weight.is_zero(),
+        ContractError::InvalidBoostConfig
+    );
+
+    Ok(())
+}
+
+/// Validates the shares of the emergency unlock penalty split add up to 100%. Returns value it's
+/// validating, i.e. the penalty split.
+pub(crate) fn validate_penalty_split(
+    penalty_split: PenaltySplit,
+) -> Result<PenaltySplit, ContractError> {
+    let total_share = penalty_split
+        .farm_owners
+        .checked_add(penalty_split.fee_collector)?
+        .checked_add(penalty_split.redistribution)?;
+
+    ensure!(
+        total_share == Decimal::one(),
+        ContractError::InvalidPenaltySplit
+    );
+
+    Ok(penalty_split)
+}
+
+/// Validates that the denom was created by the pool manager, i.e. it belongs to a valid pool, or
+/// that it's a non-LP denom the owner allowed to be staked.
 pub(crate) fn validate_lp_denom(
+    storage: &dyn Storage,
     lp_denom: &str,
     pool_manager_addr: &str,
 ) -> Result<(), ContractError> {
+    if STAKEABLE_DENOMS.has(storage, lp_denom) {
+        return Ok(());
+    }
+
     ensure!(
         is_factory_token(lp_denom) && get_factory_token_creator(lp_denom)? == pool_manager_addr,
         ContractError::AssetMismatch
@@ -201,6 +443,17 @@ pub(crate) fn validate_lp_denom(
     Ok(())
 }
 
+/// Gets the identifier of the pool an LP denom belongs to. LP denoms created by the pool manager have
+/// the format `factory/{pool_manager}/{pool_identifier}.{LP_SYMBOL}`.
+pub(crate) fn get_pool_identifier(lp_denom: &str) -> Result<String, ContractError> {
+    lp_denom
+        .splitn(3, '/')
+        .nth(2)
+        .and_then(|subdenom| subdenom.strip_suffix(&format!(".{LP_SYMBOL}")))
+        .map(|pool_identifier| pool_identifier.to_string())
+        .ok_or(ContractError::AssetMismatch)
+}
+
 /// Validates the unlocking duration range
 pub(crate) fn validate_unlocking_duration(
     min_unlocking_duration: u64,
@@ -231,33 +484,27 @@ pub(crate) fn validate_farm_expiration_time(
     Ok(())
 }
 
-/// Gets the unique LP asset denoms from a list of positions
-pub(crate) fn get_unique_lp_asset_denoms_from_positions(positions: Vec<Position>) -> Vec<String> {
-    positions
-        .iter()
-        .map(|position| position.lp_asset.denom.clone())
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect()
-}
-
 /// Checks if the farm is expired. A farm is considered to be expired if there's no more assets to claim
-/// or if there has passed the config.farm_expiration_time since the farm ended.
+/// or if there has passed the config.farm_expiration_time since the farm ended. A paused farm is
+/// considered to end on the epoch it was paused from, so it expires if it's not resumed in time.
 pub(crate) fn is_farm_expired(
     farm: &Farm,
     deps: Deps,
     env: &Env,
     config: &Config,
 ) -> Result<bool, ContractError> {
-    let epoch_response: EpochResponse = deps
-        .querier
-        // query preliminary_end_epoch + 1 because the farm is preliminary ending at that epoch, including it.
-        .query_wasm_smart(
-            config.epoch_manager_addr.to_string(),
-            &QueryMsg::Epoch {
-                id: farm.preliminary_end_epoch + 1u64,
-            },
-        )?;
+    // query preliminary_end_epoch + 1 because the farm is preliminary ending at that epoch, including it.
+    let ending_epoch_id = match get_farm_paused_from(deps.storage, &farm.identifier)? {
+        Some(paused_from) => paused_from.min(farm.preliminary_end_epoch + 1u64),
+        None => farm.preliminary_end_epoch + 1u64,
+    };
+
+    let epoch_response: EpochResponse = deps.querier.query_wasm_smart(
+        config.epoch_manager_addr.to_string(),
+        &QueryMsg::Epoch {
+            id: ending_epoch_id,
+        },
+    )?;
 
     let farm_ending_at = epoch_response.epoch.start_time;
 
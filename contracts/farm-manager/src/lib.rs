@@ -1,8 +1,10 @@
 pub mod contract;
 mod error;
 pub mod farm;
+mod gauge;
 pub mod helpers;
 mod manager;
+mod migrations;
 pub mod position;
 mod queries;
 pub mod state;
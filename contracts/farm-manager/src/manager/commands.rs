@@ -1,19 +1,34 @@
+use std::collections::HashSet;
+
 use cosmwasm_std::{
-    ensure, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, StdError,
-    Storage, SubMsg, Uint128, Uint64,
+    coin, ensure, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, Uint64,
 };
 
 use crate::contract::CLOSE_FARMS_ERR_REPLY_CODE;
+use mantra_dex_std::coin::{get_factory_token_creator, is_factory_token};
 use mantra_dex_std::farm_manager::MIN_FARM_AMOUNT;
-use mantra_dex_std::farm_manager::{Curve, Farm, FarmParams};
+use mantra_dex_std::farm_manager::{
+    BoostConfig, Config, Curve, EpochId, Farm, FarmParams, PenaltySplit,
+};
 
-use crate::farm::{AUTO_FARM_ID_PREFIX, EXPLICIT_FARM_ID_PREFIX};
+use crate::farm::{
+    AUTO_FARM_ID_PREFIX, EXPLICIT_FARM_ID_PREFIX, MAX_FARM_CONTRIBUTORS, PENALTY_FARM_ID_PREFIX,
+};
+use crate::gauge::GAUGE_FARM_ID_PREFIX;
 use crate::helpers::{
-    assert_farm_asset, is_farm_expired, process_farm_creation_fee,
-    validate_emergency_unlock_penalty, validate_farm_epochs, validate_farm_expiration_time,
-    validate_identifier, validate_lp_denom, validate_unlocking_duration,
+    assert_farm_asset, get_farm_emission_for_epoch, get_farm_emission_schedule, is_farm_expired,
+    process_farm_creation_fee, validate_boost_config, validate_emergency_unlock_penalty,
+    validate_farm_asset_allowed, validate_farm_curve, validate_farm_epochs,
+    validate_farm_expiration_time, validate_identifier, validate_lp_denom, validate_penalty_split,
+    validate_unlocking_duration,
+};
+use crate::state::{
+    get_farm_by_identifier, get_farm_paused_from, get_farm_pauses, get_farms_by_lp_denom,
+    FarmPause, BOOST_CONFIG, CONFIG, EVICTED_FARMS, FARMS, FARM_ASSETS, FARM_CONTRIBUTIONS,
+    FARM_COUNTER, FARM_PAUSES, GAUGE_BUDGET, GAUGE_FARMS, MAX_FARMS_LIMIT, PENALTY_FARMS,
+    STAKEABLE_DENOMS,
 };
-use crate::state::{get_farm_by_identifier, get_farms_by_lp_denom, CONFIG, FARMS, FARM_COUNTER};
 use crate::ContractError;
 
 /// Creates a farm with the given params
@@ -26,16 +41,15 @@ pub(crate) fn create_farm(
     let config = CONFIG.load(deps.storage)?;
 
     // ensure the lp denom is valid and was created by the pool manager
-    validate_lp_denom(&params.lp_denom, config.pool_manager_addr.as_str())?;
-
-    // check if there are any expired farms for this LP asset
-    let farms = get_farms_by_lp_denom(
+    validate_lp_denom(
         deps.storage,
         &params.lp_denom,
-        None,
-        Some(config.max_concurrent_farms),
+        config.pool_manager_addr.as_str(),
     )?;
 
+    // check if there are any expired farms for this LP asset
+    let farms = get_farms_by_lp_denom(deps.storage, &params.lp_denom, None, Some(MAX_FARMS_LIMIT))?;
+
     let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
         deps.as_ref(),
         config.epoch_manager_addr.clone().into_string(),
@@ -53,9 +67,19 @@ pub(crate) fn create_farm(
         submessages.append(&mut close_farms(deps.storage, expired_farms)?);
     }
 
-    // check if more farms can be created for this particular LP asset
+    // check if more farms can be created for this particular LP asset. Protocol farms, i.e. gauge
+    // and penalty redistribution farms, and evicted farms don't count towards the limit
+    let user_farms = farms
+        .iter()
+        .filter(|farm| {
+            !farm.identifier.starts_with(GAUGE_FARM_ID_PREFIX)
+                && !farm.identifier.starts_with(PENALTY_FARM_ID_PREFIX)
+                && !EVICTED_FARMS.has(deps.storage, &farm.identifier)
+        })
+        .count();
+
     ensure!(
-        farms.len() < config.max_concurrent_farms as usize,
+        user_farms < config.max_concurrent_farms as usize,
         ContractError::TooManyFarms {
             max: config.max_concurrent_farms,
         }
@@ -69,6 +93,9 @@ pub(crate) fn create_farm(
         }
     );
 
+    // check the farm asset is allowlisted, so farm slots can't be filled with spam farms
+    validate_farm_asset_allowed(deps.storage, &params.farm_asset)?;
+
     let farm_creation_fee = config.clone().create_farm_fee;
 
     if farm_creation_fee.amount != Uint128::zero() {
@@ -106,8 +133,17 @@ pub(crate) fn create_farm(
     );
     // the farm does not exist, all good, continue
 
+    let curve = params.curve.unwrap_or(Curve::Linear);
+    validate_farm_curve(
+        &curve,
+        start_epoch,
+        preliminary_end_epoch,
+        params.farm_asset.amount,
+    )?;
+
     // calculates the emission rate. The way it's calculated, it makes the last epoch to be
-    // non-inclusive, i.e. the last epoch is not counted in the emission
+    // non-inclusive, i.e. the last epoch is not counted in the emission. For non-linear curves
+    // this is the average emission per epoch, the actual emissions are derived from the curve
     let emission_rate = params
         .farm_asset
         .amount
@@ -118,7 +154,7 @@ pub(crate) fn create_farm(
         identifier: farm_identifier,
         start_epoch,
         preliminary_end_epoch,
-        curve: params.curve.unwrap_or(Curve::Linear),
+        curve,
         farm_asset: params.farm_asset,
         lp_denom: params.lp_denom,
         owner: info.sender,
@@ -167,8 +203,107 @@ pub(crate) fn expand_farm(
 
     let config = CONFIG.load(deps.storage)?;
 
+    let current_epoch_id = assert_farm_can_be_topped_up(deps.as_ref(), &env, &config, &farm)?;
+
+    // ensure the lp denom is valid and was created by the pool manager
+    validate_lp_denom(
+        deps.storage,
+        &params.lp_denom,
+        config.pool_manager_addr.as_str(),
+    )?;
+
+    // ensure the farm asset, i.e. the additional reward, was sent
+    let reward = cw_utils::one_coin(&info)?;
+
+    ensure!(reward == params.farm_asset, ContractError::AssetMismatch);
+
+    top_up_farm(&mut farm, &reward, current_epoch_id)?;
+
+    FARMS.save(deps.storage, &farm.identifier, &farm)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "expand_farm".to_string()),
+        ("farm_identifier", farm.identifier),
+        ("expanded_by", params.farm_asset.to_string()),
+        ("total_farm", farm.farm_asset.to_string()),
+    ]))
+}
+
+/// Contributes to an existing farm, topping it up the same way expanding it does. Anyone can
+/// contribute to a farm, and contributors get their share of the refunds when the farm is closed
+/// or cancelled.
+pub(crate) fn contribute_to_farm(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    farm_identifier: String,
+    asset: Coin,
+) -> Result<Response, ContractError> {
+    let mut farm = get_farm_by_identifier(deps.storage, &farm_identifier)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let current_epoch_id = assert_farm_can_be_topped_up(deps.as_ref(), &env, &config, &farm)?;
+
+    // ensure the farm asset, i.e. the contribution, was sent
+    let contribution = cw_utils::one_coin(&info)?;
+
+    ensure!(contribution == asset, ContractError::AssetMismatch);
+
+    // contributions have to be as big as a farm, so the contributor slots can't be taken with dust
+    ensure!(
+        contribution.amount >= MIN_FARM_AMOUNT,
+        ContractError::InvalidFarmAmount {
+            min: MIN_FARM_AMOUNT.u128()
+        }
+    );
+
+    top_up_farm(&mut farm, &contribution, current_epoch_id)?;
+
+    // the owner's share is whatever the other contributors didn't fund
+    if info.sender != farm.owner {
+        let key = (farm.identifier.as_str(), &info.sender);
+
+        ensure!(
+            FARM_CONTRIBUTIONS.has(deps.storage, key)
+                || FARM_CONTRIBUTIONS
+                    .prefix(&farm.identifier)
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .count()
+                    < MAX_FARM_CONTRIBUTORS as usize,
+            ContractError::TooManyFarmContributors {
+                max: MAX_FARM_CONTRIBUTORS,
+            }
+        );
+
+        FARM_CONTRIBUTIONS.update::<_, ContractError>(deps.storage, key, |contributed| {
+            Ok(contributed
+                .unwrap_or_default()
+                .checked_add(contribution.amount)?)
+        })?;
+    }
+
+    FARMS.save(deps.storage, &farm.identifier, &farm)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "contribute_to_farm".to_string()),
+        ("farm_identifier", farm.identifier),
+        ("contributor", info.sender.to_string()),
+        ("contribution", contribution.to_string()),
+        ("total_farm", farm.farm_asset.to_string()),
+    ]))
+}
+
+/// Ensures the farm hasn't ended nor expired, so it can still be topped up. Returns the current
+/// epoch id.
+fn assert_farm_can_be_topped_up(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    farm: &Farm,
+) -> Result<EpochId, ContractError> {
     let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
-        deps.as_ref(),
+        deps,
         config.epoch_manager_addr.clone().into_string(),
     )?;
 
@@ -181,53 +316,85 @@ pub(crate) fn expand_farm(
 
     // check if the farm has already expired, can't be expanded
     ensure!(
-        !is_farm_expired(&farm, deps.as_ref(), &env, &config)?,
+        !is_farm_expired(farm, deps, env, config)?,
         ContractError::FarmAlreadyExpired
     );
 
-    // ensure the lp denom is valid and was created by the pool manager
-    validate_lp_denom(&params.lp_denom, config.pool_manager_addr.as_str())?;
+    ensure!(
+        get_farm_paused_from(deps.storage, &farm.identifier)?.is_none(),
+        ContractError::FarmPaused
+    );
 
-    // ensure the farm asset, i.e. the additional reward, was sent
-    let reward = cw_utils::one_coin(&info)?;
+    ensure!(
+        !EVICTED_FARMS.has(deps.storage, &farm.identifier),
+        ContractError::FarmEvicted
+    );
 
-    ensure!(reward == params.farm_asset, ContractError::AssetMismatch);
+    Ok(current_epoch.id)
+}
 
+/// Tops up the farm with the given reward, according to its emission curve.
+fn top_up_farm(
+    farm: &mut Farm,
+    reward: &Coin,
+    current_epoch_id: EpochId,
+) -> Result<(), ContractError> {
     // check that the asset sent matches the asset expected
     ensure!(
-        farm.farm_asset.denom == params.farm_asset.denom,
+        farm.farm_asset.denom == reward.denom,
         ContractError::AssetMismatch
     );
 
-    // make sure the expansion is a multiple of the emission rate
-    ensure!(
-        reward.amount % farm.emission_rate == Uint128::zero(),
-        ContractError::InvalidExpansionAmount {
-            emission_rate: farm.emission_rate
-        }
-    );
+    match farm.curve {
+        Curve::Linear => {
+            // make sure the expansion is a multiple of the emission rate
+            ensure!(
+                reward.amount % farm.emission_rate == Uint128::zero(),
+                ContractError::InvalidExpansionAmount {
+                    emission_rate: farm.emission_rate
+                }
+            );
 
-    // increase the total amount of the farm
-    farm.farm_asset.amount = farm.farm_asset.amount.checked_add(reward.amount)?;
+            // increase the total amount of the farm
+            farm.farm_asset.amount = farm.farm_asset.amount.checked_add(reward.amount)?;
 
-    let additional_epochs = params.farm_asset.amount.checked_div(farm.emission_rate)?;
+            let additional_epochs = reward.amount.checked_div(farm.emission_rate)?;
 
-    // adjust the preliminary end_epoch
-    farm.preliminary_end_epoch = farm
-        .preliminary_end_epoch
-        .checked_add(Uint64::try_from(additional_epochs)?.u64())
-        .ok_or(ContractError::InvalidEpoch {
-            which: "end".to_string(),
-        })?;
+            // adjust the preliminary end_epoch
+            farm.preliminary_end_epoch = farm
+                .preliminary_end_epoch
+                .checked_add(Uint64::try_from(additional_epochs)?.u64())
+                .ok_or(ContractError::InvalidEpoch {
+                    which: "end".to_string(),
+                })?;
+        }
+        Curve::Cliff => {
+            // nothing has been emitted until the cliff is reached, so the farm can be topped up
+            // as long as its last epoch hasn't started
+            ensure!(
+                current_epoch_id < farm.preliminary_end_epoch - 1u64,
+                ContractError::FarmNotExpandable {
+                    curve: farm.curve.to_string()
+                }
+            );
 
-    FARMS.save(deps.storage, &farm.identifier, &farm)?;
+            // increase the total amount of the farm
+            farm.farm_asset.amount = farm.farm_asset.amount.checked_add(reward.amount)?;
+            farm.emission_rate = farm
+                .farm_asset
+                .amount
+                .checked_div_floor((farm.preliminary_end_epoch - farm.start_epoch, 1u64))?;
+        }
+        // the emissions of past epochs depend on the total amount or on the schedule, so
+        // expanding these farms would alter rewards that might have already been claimed
+        Curve::ExponentialDecay { .. } | Curve::Step { .. } => {
+            return Err(ContractError::FarmNotExpandable {
+                curve: farm.curve.to_string(),
+            });
+        }
+    }
 
-    Ok(Response::default().add_attributes(vec![
-        ("action", "expand_farm".to_string()),
-        ("farm_identifier", farm.identifier),
-        ("expanded_by", params.farm_asset.to_string()),
-        ("total_farm", farm.farm_asset.to_string()),
-    ]))
+    Ok(())
 }
 
 /// Closes a farm. Only the farm creator or the owner of the contract can close a farm, except if
@@ -256,6 +423,222 @@ pub(crate) fn close_farm(
         ]))
 }
 
+/// Pauses a farm, which stops emitting from the next epoch until it's resumed. Only the farm creator
+/// can pause a farm.
+pub(crate) fn pause_farm(
+    deps: DepsMut,
+    info: MessageInfo,
+    farm_identifier: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let farm = get_farm_by_identifier(deps.storage, &farm_identifier)?;
+
+    ensure!(farm.owner == info.sender, ContractError::Unauthorized);
+
+    let mut pauses = get_farm_pauses(deps.storage, &farm.identifier)?;
+    ensure!(
+        pauses
+            .last()
+            .is_none_or(|pause| pause.resumed_from.is_some()),
+        ContractError::FarmPaused
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    // the rewards of the current epoch can be claimed already, so the farm stops emitting on the
+    // next one
+    let paused_from = current_epoch.id + 1u64;
+
+    ensure!(
+        farm.start_epoch <= current_epoch.id && paused_from < farm.preliminary_end_epoch,
+        ContractError::FarmNotEmitting
+    );
+
+    pauses.push(FarmPause {
+        paused_from,
+        resumed_from: None,
+    });
+    FARM_PAUSES.save(deps.storage, &farm.identifier, &pauses)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "pause_farm".to_string()),
+        ("farm_identifier", farm_identifier),
+        ("paused_from", paused_from.to_string()),
+    ]))
+}
+
+/// Resumes a paused farm from the next epoch. The end of the farm is pushed back by the number of
+/// epochs it was paused for, keeping its curve.
+pub(crate) fn resume_farm(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    farm_identifier: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let mut farm = get_farm_by_identifier(deps.storage, &farm_identifier)?;
+
+    ensure!(farm.owner == info.sender, ContractError::Unauthorized);
+
+    let mut pauses = get_farm_pauses(deps.storage, &farm.identifier)?;
+    let paused_from = get_farm_paused_from(deps.storage, &farm.identifier)?
+        .ok_or(ContractError::FarmNotPaused)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // a farm that stayed paused for too long expires, it can only be closed then
+    ensure!(
+        !is_farm_expired(&farm, deps.as_ref(), &env, &config)?,
+        ContractError::FarmAlreadyExpired
+    );
+
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    let resumed_from = current_epoch.id + 1u64;
+    let paused_epochs = resumed_from.saturating_sub(paused_from);
+
+    // the farm didn't skip any epoch if resumed on the same epoch it was paused
+    pauses.pop();
+    if paused_epochs > 0u64 {
+        pauses.push(FarmPause {
+            paused_from,
+            resumed_from: Some(resumed_from),
+        });
+        farm.preliminary_end_epoch += paused_epochs;
+    }
+
+    if pauses.is_empty() {
+        FARM_PAUSES.remove(deps.storage, &farm.identifier);
+    } else {
+        FARM_PAUSES.save(deps.storage, &farm.identifier, &pauses)?;
+    }
+    FARMS.save(deps.storage, &farm.identifier, &farm)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "resume_farm".to_string()),
+        ("farm_identifier", farm_identifier),
+        ("resumed_from", resumed_from.to_string()),
+        (
+            "preliminary_end_epoch",
+            farm.preliminary_end_epoch.to_string(),
+        ),
+    ]))
+}
+
+/// Cancels a farm, refunding the emissions of the upcoming epochs to the farm creator and its
+/// contributors. Unlike closing it, the rewards already accrued by the LPs remain claimable. Only
+/// the farm creator can cancel a farm.
+pub(crate) fn cancel_farm(
+    deps: DepsMut,
+    info: MessageInfo,
+    farm_identifier: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let mut farm = get_farm_by_identifier(deps.storage, &farm_identifier)?;
+
+    ensure!(farm.owner == info.sender, ContractError::Unauthorized);
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    if farm.start_epoch > current_epoch.id {
+        // nothing has been emitted yet, close the farm altogether
+        return Ok(Response::default()
+            .add_submessages(close_farms(deps.storage, vec![farm])?)
+            .add_attributes(vec![
+                ("action", "cancel_farm".to_string()),
+                ("farm_identifier", farm_identifier),
+            ]));
+    }
+
+    // the rewards of the current epoch can be claimed already, so the farm stops emitting on the
+    // next one, or on the epoch it was paused from if it's paused
+    let cancelled_from =
+        get_farm_paused_from(deps.storage, &farm.identifier)?.unwrap_or(current_epoch.id + 1u64);
+
+    ensure!(
+        cancelled_from < farm.preliminary_end_epoch,
+        ContractError::FarmNotEmitting
+    );
+
+    let funded_amount = farm.farm_asset.amount;
+    let refund = stop_farm_emissions(deps.storage, &mut farm, cancelled_from)?;
+
+    FARMS.save(deps.storage, &farm.identifier, &farm)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    for (recipient, recipient_refund) in
+        split_farm_refund(deps.storage, &farm, funded_amount, refund, false)?
+    {
+        if recipient_refund > Uint128::zero() {
+            messages.push(
+                BankMsg::Send {
+                    to_address: recipient.into_string(),
+                    amount: vec![coin(recipient_refund.u128(), &farm.farm_asset.denom)],
+                }
+                .into(),
+            );
+        }
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "cancel_farm".to_string()),
+            ("farm_identifier", farm_identifier),
+            (
+                "refund",
+                coin(refund.u128(), &farm.farm_asset.denom).to_string(),
+            ),
+        ]))
+}
+
+/// Stops the farm from emitting from the given epoch onwards, turning it into a step farm that
+/// keeps the emissions of the previous epochs, including the epochs it was paused for. Returns the
+/// amount the farm had left from the given epoch.
+fn stop_farm_emissions(
+    storage: &mut dyn Storage,
+    farm: &mut Farm,
+    epoch_id: EpochId,
+) -> Result<Uint128, ContractError> {
+    let pauses = get_farm_pauses(storage, &farm.identifier)?;
+
+    let mut emitted = Uint128::zero();
+    for past_epoch_id in farm.start_epoch..epoch_id {
+        emitted =
+            emitted.checked_add(get_farm_emission_for_epoch(farm, &pauses, past_epoch_id)?)?;
+    }
+
+    let past_schedule = get_farm_emission_schedule(farm, &pauses, farm.start_epoch, epoch_id)?;
+    let remaining_amount = farm.farm_asset.amount.checked_sub(emitted)?;
+
+    // the schedule already accounts for the pauses
+    FARM_PAUSES.remove(storage, &farm.identifier);
+
+    farm.curve = Curve::Step {
+        schedule: past_schedule,
+    };
+    farm.farm_asset.amount = emitted;
+    farm.preliminary_end_epoch = epoch_id;
+    farm.emission_rate = emitted.checked_div_floor((epoch_id - farm.start_epoch, 1u64))?;
+
+    Ok(remaining_amount)
+}
+
 /// Closes a list of farms. Does not validate the sender, do so before calling this function.
 /// Execute the BankMsg using a SubMsg with reply on error, in case a malicious TF token tries
 /// to block token transfers via hooks.
@@ -266,23 +649,98 @@ fn close_farms(storage: &mut dyn Storage, farms: Vec<Farm>) -> Result<Vec<SubMsg
         // remove the farm from the storage
         FARMS.remove(storage, &farm.identifier)?;
 
+        FARM_PAUSES.remove(storage, &farm.identifier);
+        EVICTED_FARMS.remove(storage, &farm.identifier);
+        let funded_amount = farm.farm_asset.amount;
+
         // return the available asset, i.e. the amount that hasn't been claimed
-        farm.farm_asset.amount = farm.farm_asset.amount.saturating_sub(farm.claimed_amount);
-
-        if farm.farm_asset.amount > Uint128::zero() {
-            messages.push(SubMsg::reply_on_error(
-                CosmosMsg::Bank(BankMsg::Send {
-                    to_address: farm.owner.into_string(),
-                    amount: vec![farm.farm_asset],
-                }),
-                CLOSE_FARMS_ERR_REPLY_CODE,
-            ));
+        farm.farm_asset.amount = funded_amount.saturating_sub(farm.claimed_amount);
+
+        // gauge farms are funded by the gauge budget, return the available asset there
+        if farm.identifier.starts_with(GAUGE_FARM_ID_PREFIX) {
+            GAUGE_FARMS.remove(storage, &farm.lp_denom);
+            GAUGE_BUDGET.update::<_, ContractError>(storage, |mut budget| {
+                budget.balance = budget.balance.checked_add(farm.farm_asset.amount)?;
+                Ok(budget)
+            })?;
+
+            continue;
+        }
+
+        // penalty redistribution farms are funded by the emergency unlock penalties, return the
+        // available asset to the fee collector
+        if farm.identifier.starts_with(PENALTY_FARM_ID_PREFIX) {
+            PENALTY_FARMS.remove(storage, &farm.lp_denom);
+            farm.owner = CONFIG.load(storage)?.fee_collector_addr;
+        }
+
+        let refunds =
+            split_farm_refund(storage, &farm, funded_amount, farm.farm_asset.amount, true)?;
+
+        for (recipient, refund) in refunds {
+            if refund > Uint128::zero() {
+                messages.push(SubMsg::reply_on_error(
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: recipient.into_string(),
+                        amount: vec![coin(refund.u128(), &farm.farm_asset.denom)],
+                    }),
+                    CLOSE_FARMS_ERR_REPLY_CODE,
+                ));
+            }
         }
     }
 
     Ok(messages)
 }
 
+/// Splits a refund of the farm among its contributors, pro-rata to their contributions out of the
+/// amount the farm was funded with. The owner of the farm gets the rest. When the farm is closed,
+/// the contributions are removed, otherwise they are reduced by the refunded amounts.
+fn split_farm_refund(
+    storage: &mut dyn Storage,
+    farm: &Farm,
+    funded_amount: Uint128,
+    refund: Uint128,
+    closed: bool,
+) -> Result<Vec<(Addr, Uint128)>, ContractError> {
+    let contributions = FARM_CONTRIBUTIONS
+        .prefix(&farm.identifier)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut refunds = vec![];
+    let mut owner_refund = refund;
+
+    for (contributor, contribution) in contributions {
+        let contributor_refund = if refund.is_zero() {
+            Uint128::zero()
+        } else {
+            // never refund more than what's left, in case of rounding errors
+            refund
+                .checked_mul_floor((contribution, funded_amount))?
+                .min(owner_refund)
+        };
+
+        owner_refund = owner_refund.checked_sub(contributor_refund)?;
+
+        if closed {
+            FARM_CONTRIBUTIONS.remove(storage, (&farm.identifier, &contributor));
+        } else {
+            FARM_CONTRIBUTIONS.save(
+                storage,
+                (&farm.identifier, &contributor),
+                &contribution.saturating_sub(contributor_refund),
+            )?;
+        }
+
+        refunds.push((contributor, contributor_refund));
+    }
+
+    refunds.push((farm.owner.clone(), owner_refund));
+
+    Ok(refunds)
+}
+
 #[allow(clippy::too_many_arguments)]
 /// Updates the configuration of the contract
 pub(crate) fn update_config(
@@ -298,6 +756,7 @@ pub(crate) fn update_config(
     max_unlocking_duration: Option<u64>,
     farm_expiration_time: Option<u64>,
     emergency_unlock_penalty: Option<Decimal>,
+    penalty_split: Option<PenaltySplit>,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
@@ -352,6 +811,10 @@ pub(crate) fn update_config(
             validate_emergency_unlock_penalty(emergency_unlock_penalty)?;
     }
 
+    if let Some(penalty_split) = penalty_split {
+        config.penalty_split = validate_penalty_split(penalty_split)?;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attributes(vec![
@@ -384,5 +847,202 @@ pub(crate) fn update_config(
             "emergency_unlock_penalty",
             config.emergency_unlock_penalty.to_string(),
         ),
+        (
+            "penalty_split",
+            format!(
+                "farm_owners:{},fee_collector:{},redistribution:{}",
+                config.penalty_split.farm_owners,
+                config.penalty_split.fee_collector,
+                config.penalty_split.redistribution
+            ),
+        ),
+    ]))
+}
+
+/// Adds and removes non-LP denoms from the ones allowed to be staked. Positions of removed denoms
+/// can still be closed and withdrawn, but no new positions or farms can be created for them.
+pub(crate) fn update_stakeable_denoms(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    for denom in &add {
+        // LP denoms created by the pool manager are stakeable already
+        let is_lp_denom = is_factory_token(denom)
+            && get_factory_token_creator(denom)? == config.pool_manager_addr.as_str();
+
+        ensure!(
+            !denom.is_empty() && !is_lp_denom,
+            ContractError::AssetMismatch
+        );
+
+        STAKEABLE_DENOMS.save(deps.storage, denom, &())?;
+    }
+
+    for denom in &remove {
+        STAKEABLE_DENOMS.remove(deps.storage, denom);
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_stakeable_denoms".to_string()),
+        ("added", add.join(",")),
+        ("removed", remove.join(",")),
+    ]))
+}
+
+/// Updates the denoms allowed to be used as farm assets, with the minimum amount a farm has to be
+/// created with. Removing a denom doesn't affect the existing farms, which can be evicted by the
+/// owner if needed.
+pub(crate) fn update_farm_assets(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<Coin>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    for asset in &add {
+        ensure!(!asset.denom.is_empty(), ContractError::AssetMismatch);
+
+        FARM_ASSETS.save(deps.storage, &asset.denom, &asset.amount)?;
+    }
+
+    for denom in &remove {
+        FARM_ASSETS.remove(deps.storage, denom);
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_farm_assets".to_string()),
+        (
+            "added",
+            add.iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        ("removed", remove.join(",")),
     ]))
 }
+
+/// Evicts the given farms, e.g. spam farms taking up the slots of an LP denom. The farms stop
+/// emitting from the next epoch and the emissions of the upcoming epochs are refunded to the farm
+/// creators and contributors, while the rewards already accrued by the LPs remain claimable until
+/// the farms expire. Farms that haven't started are closed altogether. Only the owner of the
+/// contract can evict farms.
+pub(crate) fn evict_farms(
+    deps: DepsMut,
+    info: MessageInfo,
+    farm_identifiers: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    let mut evicted_farm_identifiers = HashSet::new();
+    let mut farms_to_close = vec![];
+    let mut submessages = vec![];
+
+    for farm_identifier in &farm_identifiers {
+        // skip the duplicates, so the same farm is not refunded twice
+        if !evicted_farm_identifiers.insert(farm_identifier) {
+            continue;
+        }
+
+        let mut farm = get_farm_by_identifier(deps.storage, farm_identifier)?;
+
+        // protocol farms don't take up the slots of the LP denom
+        ensure!(
+            !farm.identifier.starts_with(GAUGE_FARM_ID_PREFIX)
+                && !farm.identifier.starts_with(PENALTY_FARM_ID_PREFIX),
+            ContractError::Unauthorized
+        );
+
+        // nothing has been emitted yet, close the farm altogether
+        if farm.start_epoch > current_epoch.id {
+            farms_to_close.push(farm);
+            continue;
+        }
+
+        // the rewards of the current epoch can be claimed already, so the farm stops emitting on
+        // the next one, or on the epoch it was paused from if it's paused
+        let evicted_from = get_farm_paused_from(deps.storage, &farm.identifier)?
+            .unwrap_or(current_epoch.id + 1u64);
+
+        if evicted_from < farm.preliminary_end_epoch {
+            let funded_amount = farm.farm_asset.amount;
+            let refund = stop_farm_emissions(deps.storage, &mut farm, evicted_from)?;
+
+            FARMS.save(deps.storage, &farm.identifier, &farm)?;
+
+            // the refunds are sent with a reply on error, in case a malicious TF token tries to
+            // block token transfers via hooks
+            for (recipient, recipient_refund) in
+                split_farm_refund(deps.storage, &farm, funded_amount, refund, false)?
+            {
+                if recipient_refund > Uint128::zero() {
+                    submessages.push(SubMsg::reply_on_error(
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: recipient.into_string(),
+                            amount: vec![coin(recipient_refund.u128(), &farm.farm_asset.denom)],
+                        }),
+                        CLOSE_FARMS_ERR_REPLY_CODE,
+                    ));
+                }
+            }
+        }
+
+        EVICTED_FARMS.save(deps.storage, &farm.identifier, &current_epoch.id)?;
+    }
+
+    submessages.append(&mut close_farms(deps.storage, farms_to_close)?);
+
+    Ok(Response::default()
+        .add_submessages(submessages)
+        .add_attributes(vec![
+            ("action", "evict_farms".to_string()),
+            ("farm_identifiers", farm_identifiers.join(",")),
+        ]))
+}
+
+/// Updates the boost applied to the weights of the addresses staking the native token. Passing None
+/// disables the boost. The weights already recorded keep their boost until they are updated.
+pub(crate) fn update_boost_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    boost_config: Option<BoostConfig>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let attributes = match &boost_config {
+        Some(boost_config) => {
+            validate_boost_config(boost_config)?;
+            BOOST_CONFIG.save(deps.storage, boost_config)?;
+
+            vec![
+                ("max_boost", boost_config.max_boost.to_string()),
+                (
+                    "stake_per_weight",
+                    boost_config.stake_per_weight.to_string(),
+                ),
+            ]
+        }
+        None => {
+            BOOST_CONFIG.remove(deps.storage);
+
+            vec![("max_boost", Decimal::one().to_string())]
+        }
+    };
+
+    Ok(Response::default()
+        .add_attribute("action", "update_boost_config")
+        .add_attributes(attributes))
+}
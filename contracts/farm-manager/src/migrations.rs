@@ -0,0 +1,113 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Decimal, DepsMut, Order, StdError};
+use cw_storage_plus::{Bound, Item, Map};
+
+use mantra_dex_std::farm_manager::{Config, EpochId};
+
+use crate::position::DEFAULT_PENALTY_SPLIT;
+use crate::state::{
+    get_address_lp_denoms, ADDRESS_OPEN_POSITIONS, CONFIG, LAST_CLAIMED_EPOCH, POSITIONS,
+};
+
+/// Migrates to v2.1.0, which keeps track of the number of open positions each address has per LP
+/// denom, lifting the limit of open positions per address, tracks the last claimed epoch per
+/// LP denom instead of per address, and adds the emergency unlock penalty split to the Config struct.
+pub fn migrate_to_v210(deps: DepsMut) -> Result<(), StdError> {
+    // recreate the old structure
+    #[cw_serde]
+    struct OldConfig {
+        pub fee_collector_addr: Addr,
+        pub epoch_manager_addr: Addr,
+        pub pool_manager_addr: Addr,
+        pub create_farm_fee: Coin,
+        pub max_concurrent_farms: u32,
+        pub max_farm_epoch_buffer: u32,
+        pub min_unlocking_duration: u64,
+        pub max_unlocking_duration: u64,
+        pub farm_expiration_time: u64,
+        pub emergency_unlock_penalty: Decimal,
+    }
+
+    const OLD_CONFIG: Item<OldConfig> = Item::new("config");
+
+    let old_config = OLD_CONFIG.load(deps.storage)?;
+
+    // keep splitting the penalty as before, between the farm owners and the fee collector
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            fee_collector_addr: old_config.fee_collector_addr,
+            epoch_manager_addr: old_config.epoch_manager_addr,
+            pool_manager_addr: old_config.pool_manager_addr,
+            create_farm_fee: old_config.create_farm_fee,
+            max_concurrent_farms: old_config.max_concurrent_farms,
+            max_farm_epoch_buffer: old_config.max_farm_epoch_buffer,
+            min_unlocking_duration: old_config.min_unlocking_duration,
+            max_unlocking_duration: old_config.max_unlocking_duration,
+            farm_expiration_time: old_config.farm_expiration_time,
+            emergency_unlock_penalty: old_config.emergency_unlock_penalty,
+            penalty_split: DEFAULT_PENALTY_SPLIT,
+        },
+    )?;
+
+    // the positions and the old last claimed epochs are migrated in batches, so they are never
+    // loaded in memory all at once
+    const BATCH_SIZE: usize = 100;
+
+    let mut start_after: Option<String> = None;
+    loop {
+        let positions = POSITIONS
+            .range(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(BATCH_SIZE)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let batch_len = positions.len();
+
+        for (identifier, position) in positions {
+            if position.open {
+                ADDRESS_OPEN_POSITIONS.update(
+                    deps.storage,
+                    (&position.receiver, &position.lp_asset.denom),
+                    |count| -> Result<_, StdError> { Ok(count.unwrap_or_default() + 1u64) },
+                )?;
+            }
+
+            start_after = Some(identifier);
+        }
+
+        if batch_len < BATCH_SIZE {
+            break;
+        }
+    }
+
+    const OLD_LAST_CLAIMED_EPOCH: Map<&Addr, EpochId> = Map::new("last_claimed_epoch");
+
+    // the migrated entries are removed, so each batch starts from the beginning of the map
+    loop {
+        let old_values = OLD_LAST_CLAIMED_EPOCH
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(BATCH_SIZE)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if old_values.is_empty() {
+            return Ok(());
+        }
+
+        for (address, last_claimed_epoch) in old_values {
+            for lp_denom in get_address_lp_denoms(deps.storage, &address)? {
+                LAST_CLAIMED_EPOCH.save(
+                    deps.storage,
+                    (&address, &lp_denom),
+                    &last_claimed_epoch,
+                )?;
+            }
+
+            OLD_LAST_CLAIMED_EPOCH.remove(deps.storage, &address);
+        }
+    }
+}
@@ -6,18 +6,24 @@ use std::collections::HashSet;
 
 use mantra_dex_std::farm_manager::Position;
 
-use crate::helpers::{is_farm_expired, validate_identifier, validate_lp_denom};
+use crate::farm::commands::{prune_address_lp_weight_history, upsert_step_farm};
+use crate::farm::{LP_WEIGHT_HISTORY_PRUNE_LIMIT, PENALTY_FARM_ID_PREFIX};
+use crate::gauge::commands::{
+    distribute_gauge_emissions, get_gauge_lp_voting_power, get_gauge_voting_power,
+    rescale_gauge_votes,
+};
+use crate::helpers::{is_farm_expired, validate_lp_denom};
 use crate::position::helpers::{
-    calculate_emergency_penalty, calculate_weight, create_penalty_share_msg,
-    get_latest_address_weight, reconcile_user_state, validate_no_pending_rewards,
-    AUTO_POSITION_ID_PREFIX, EXPLICIT_POSITION_ID_PREFIX, PENALTY_FEE_SHARE,
+    calculate_boosted_weight, calculate_emergency_penalty, calculate_weight,
+    create_penalty_share_msg, get_base_address_weight, get_latest_address_weight,
+    reconcile_user_state, validate_no_pending_rewards, AUTO_POSITION_ID_PREFIX,
 };
 use crate::position::helpers::{
-    validate_positions_limit, validate_unlocking_duration_for_position,
+    compute_position_identifier, update_open_positions, validate_unlocking_duration_for_position,
 };
 use crate::state::{
-    get_farms_by_lp_denom, get_position, CONFIG, LP_WEIGHT_HISTORY, MAX_FARMS_LIMIT, POSITIONS,
-    POSITION_ID_COUNTER,
+    get_address_lp_denoms, get_farms_by_lp_denom, get_position, BASE_LP_WEIGHTS, CONFIG,
+    LP_WEIGHT_HISTORY, MAX_FARMS_LIMIT, PENALTY_FARMS, POSITIONS, POSITION_ID_COUNTER,
 };
 use crate::ContractError;
 
@@ -34,7 +40,11 @@ pub(crate) fn create_position(
     let lp_asset = cw_utils::one_coin(&info)?;
 
     // ensure the lp denom is valid and was created by the pool manager
-    validate_lp_denom(&lp_asset.denom, config.pool_manager_addr.as_str())?;
+    validate_lp_denom(
+        deps.storage,
+        &lp_asset.denom,
+        config.pool_manager_addr.as_str(),
+    )?;
 
     // validate unlocking duration
     validate_unlocking_duration_for_position(&config, unlocking_duration)?;
@@ -54,38 +64,7 @@ pub(crate) fn create_position(
         info.sender.clone()
     };
 
-    // computes the position identifier
-    let position_id_counter = POSITION_ID_COUNTER
-        .may_load(deps.storage)?
-        .unwrap_or_default()
-        + 1u64;
-
-    // compute the identifier for this position
-    let identifier = if let Some(identifier) = identifier {
-        // prepend EXPLICIT_POSITION_ID_PREFIX to identifier
-        format!("{EXPLICIT_POSITION_ID_PREFIX}{identifier}")
-    } else {
-        POSITION_ID_COUNTER.save(deps.storage, &position_id_counter)?;
-        // prepend AUTO_POSITION_ID_PREFIX to the position_id_counter
-        format!("{AUTO_POSITION_ID_PREFIX}{position_id_counter}")
-    };
-
-    validate_identifier(&identifier)?;
-
-    // check if there's an existing position with the computed identifier
-    let position = get_position(deps.storage, Some(identifier.clone()))?;
-
-    ensure!(
-        position.is_none(),
-        ContractError::PositionAlreadyExists {
-            identifier: identifier.clone(),
-        }
-    );
-
-    // No position found, create a new one
-
-    // ensure the user doesn't have more than the maximum allowed close positions
-    validate_positions_limit(deps.as_ref(), &receiver, true)?;
+    let identifier = compute_position_identifier(deps.storage, identifier)?;
 
     let position = Position {
         identifier: identifier.clone(),
@@ -97,6 +76,7 @@ pub(crate) fn create_position(
     };
 
     POSITIONS.save(deps.storage, &identifier, &position)?;
+    update_open_positions(deps.storage, &receiver, &lp_asset.denom, true)?;
 
     // Update weights for the LP and the user
     update_weights(deps, env, &receiver, &lp_asset, unlocking_duration, true)?;
@@ -124,7 +104,11 @@ pub(crate) fn expand_position(
 
     // ensure the lp denom is valid and was created by the pool manager
     let config = CONFIG.load(deps.storage)?;
-    validate_lp_denom(&lp_asset.denom, config.pool_manager_addr.as_str())?;
+    validate_lp_denom(
+        deps.storage,
+        &lp_asset.denom,
+        config.pool_manager_addr.as_str(),
+    )?;
 
     // make sure the lp asset sent matches the lp asset of the position
     ensure!(
@@ -179,8 +163,16 @@ pub(crate) fn close_position(
 ) -> Result<Response, ContractError> {
     cw_utils::nonpayable(&info)?;
 
+    // distribute the gauge emissions for the current epoch, so they are accounted as pending rewards
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.to_string(),
+    )?;
+    let gauge_distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
     // check if the user has pending rewards. Can't close a position without claiming pending rewards first
-    validate_no_pending_rewards(deps.as_ref(), &env, &info)?;
+    validate_no_pending_rewards(deps.as_ref(), &env, &info.sender)?;
 
     let mut position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
         ContractError::NoPositionFound {
@@ -210,9 +202,6 @@ pub(crate) fn close_position(
         .plus_seconds(position.unlocking_duration)
         .seconds();
 
-    // ensure the user doesn't have more than the maximum allowed close positions
-    validate_positions_limit(deps.as_ref(), &info.sender, false)?;
-
     // check if it's going to be closed in full or partially
     let lp_amount_to_close = if let Some(lp_asset) = lp_asset {
         // make sure the lp_asset requested to close matches the lp_asset of the position
@@ -263,6 +252,10 @@ pub(crate) fn close_position(
     let close_in_full = !position.open;
     attributes.push(("close_in_full", close_in_full.to_string()));
 
+    if close_in_full {
+        update_open_positions(deps.storage, &info.sender, &position.lp_asset.denom, false)?;
+    }
+
     update_weights(
         deps.branch(),
         &env,
@@ -276,7 +269,9 @@ pub(crate) fn close_position(
 
     reconcile_user_state(deps, &info.sender, &position)?;
 
-    Ok(Response::default().add_attributes(attributes))
+    Ok(Response::default()
+        .add_attributes(attributes)
+        .add_attributes(gauge_distributions))
 }
 
 /// Modifies the position to be closed in full, returning the total amount of lp for this position.
@@ -299,6 +294,14 @@ pub(crate) fn withdraw_position(
 ) -> Result<Response, ContractError> {
     cw_utils::nonpayable(&info)?;
 
+    // distribute the gauge emissions for the current epoch, so they are accounted as pending rewards
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.to_string(),
+    )?;
+    let gauge_distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
     let mut position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
         ContractError::NoPositionFound {
             identifier: identifier.clone(),
@@ -319,7 +322,6 @@ pub(crate) fn withdraw_position(
     // emergency_unlock is requested
     if emergency_unlock.is_some() && emergency_unlock.unwrap() && !position.is_expired(current_time)
     {
-        let config = CONFIG.load(deps.storage)?;
         let base_emergency_penalty = config.emergency_unlock_penalty;
 
         let emergency_penalty =
@@ -335,48 +337,62 @@ pub(crate) fn withdraw_position(
             ContractError::InvalidEmergencyUnlockPenalty
         );
 
-        // calculate the penalty fee that goes to the owner of the farm
+        // if the position is open, update the weights when doing the emergency withdrawal
+        // otherwise not, as the weights have already being updated when the position was closed.
+        // This is done before splitting the penalty so the withdrawn weight is left out of the
+        // redistribution.
+        if position.open {
+            update_weights(
+                deps.branch(),
+                &env,
+                &info.sender,
+                &position.lp_asset,
+                position.unlocking_duration,
+                false,
+            )?;
+        }
+
+        let lp_denom = position.lp_asset.denom.clone();
+
+        // calculate the penalty fee that goes to the owners of the farms, and the one that is
+        // redistributed among the remaining LPs. The fee collector gets the rest.
         let owner_penalty_fee_comission = Decimal::from_ratio(total_penalty_fee, Uint128::one())
-            .checked_mul(PENALTY_FEE_SHARE)?
+            .checked_mul(config.penalty_split.farm_owners)?
+            .to_uint_floor();
+        let redistribution_penalty_fee = Decimal::from_ratio(total_penalty_fee, Uint128::one())
+            .checked_mul(config.penalty_split.redistribution)?
             .to_uint_floor();
 
-        let mut penalty_fee_fee_collector =
-            total_penalty_fee.saturating_sub(owner_penalty_fee_comission);
+        let mut penalty_fee_fee_collector = total_penalty_fee
+            .saturating_sub(owner_penalty_fee_comission)
+            .saturating_sub(redistribution_penalty_fee);
 
         let fee_collector_addr = &config.fee_collector_addr;
 
-        let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
-            deps.as_ref(),
-            config.epoch_manager_addr.to_string(),
-        )?;
-
-        let farms = get_farms_by_lp_denom(
-            deps.storage,
-            &position.lp_asset.denom,
-            None,
-            Some(MAX_FARMS_LIMIT),
-        )?
-        .into_iter()
-        // filter out farms that are not active, i.e. have not started yet or have expired
-        .filter(|farm| {
-            farm.start_epoch <= current_epoch.id
-                && !is_farm_expired(farm, deps.as_ref(), &env, &config).unwrap_or(false)
-        })
-        .collect::<Vec<_>>();
-
-        // get unique farm owners for this lp denom
+        let farms = get_farms_by_lp_denom(deps.storage, &lp_denom, None, Some(MAX_FARMS_LIMIT))?
+            .into_iter()
+            // filter out farms that are not active, i.e. have not started yet or have expired
+            .filter(|farm| {
+                farm.start_epoch <= current_epoch.id
+                    && !is_farm_expired(farm, deps.as_ref(), &env, &config).unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+
+        // get unique farm owners for this lp denom, leaving out the farms owned by the contract
+        // itself, i.e. the gauge and penalty redistribution farms
         let unique_farm_owners: Vec<Addr> = farms
             .iter()
             .map(|farm| farm.owner.clone())
+            .filter(|owner| owner != env.contract.address)
             .collect::<HashSet<_>>()
             .into_iter()
             .collect();
 
         // if there are no farms for this lp denom there's no need to send any penalty to the farm
-        // owners, as there are none. Send it all to the fee collector
+        // owners, as there are none. Send their share to the fee collector
         if unique_farm_owners.is_empty() {
-            // send the whole penalty fee to the fee collector
-            penalty_fee_fee_collector = total_penalty_fee;
+            penalty_fee_fee_collector =
+                penalty_fee_fee_collector.checked_add(owner_penalty_fee_comission)?;
         } else {
             // send penalty to farm owners
             let penalty_fee_share_per_farm_owner = Decimal::from_ratio(
@@ -386,44 +402,54 @@ pub(crate) fn withdraw_position(
             .to_uint_floor();
 
             // if the farm owner penalty fee is greater than zero, send it to the farm owners,
-            // otherwise send the whole penalty fee to the fee collector
+            // otherwise send their share to the fee collector
             if penalty_fee_share_per_farm_owner > Uint128::zero() {
                 for farm_owner in unique_farm_owners {
                     messages.push(create_penalty_share_msg(
-                        position.lp_asset.denom.to_string(),
+                        lp_denom.to_string(),
                         penalty_fee_share_per_farm_owner,
                         &farm_owner,
                     ));
                 }
             } else {
-                // if the penalty fee share per farm owner is zero, then the whole penalty fee goes
-                // to the fee collector, if any
-                penalty_fee_fee_collector = total_penalty_fee;
+                penalty_fee_fee_collector =
+                    penalty_fee_fee_collector.checked_add(owner_penalty_fee_comission)?;
+            }
+        }
+
+        // redistribute the penalty among the LPs that remain in the pool, via a farm emitting the
+        // LP tokens on the next epoch. If no one else is left, the share goes to the fee collector
+        if redistribution_penalty_fee > Uint128::zero() {
+            let (_, remaining_lp_weight) =
+                get_latest_address_weight(deps.storage, &env.contract.address, &lp_denom)?;
+
+            if remaining_lp_weight.is_zero() {
+                penalty_fee_fee_collector =
+                    penalty_fee_fee_collector.checked_add(redistribution_penalty_fee)?;
+            } else {
+                let penalty_farm = PENALTY_FARMS.may_load(deps.storage, &lp_denom)?;
+                let farm_identifier = upsert_step_farm(
+                    deps.storage,
+                    &env,
+                    penalty_farm,
+                    PENALTY_FARM_ID_PREFIX,
+                    &lp_denom,
+                    current_epoch.id + 1,
+                    coin(redistribution_penalty_fee.u128(), &lp_denom),
+                )?;
+                PENALTY_FARMS.save(deps.storage, &lp_denom, &farm_identifier)?;
             }
         }
 
         // send penalty to the fee collector
         if penalty_fee_fee_collector > Uint128::zero() {
             messages.push(create_penalty_share_msg(
-                position.lp_asset.denom.to_string(),
+                lp_denom.to_string(),
                 penalty_fee_fee_collector,
                 fee_collector_addr,
             ));
         }
 
-        // if the position is open, update the weights when doing the emergency withdrawal
-        // otherwise not, as the weights have already being updated when the position was closed
-        if position.open {
-            update_weights(
-                deps.branch(),
-                &env,
-                &info.sender,
-                &position.lp_asset,
-                position.unlocking_duration,
-                false,
-            )?;
-        }
-
         // subtract the penalty from the original position
         position.lp_asset.amount = position.lp_asset.amount.saturating_sub(total_penalty_fee);
     } else {
@@ -453,6 +479,7 @@ pub(crate) fn withdraw_position(
     // if the position to remove was open, i.e. withdrawn via the emergency unlock feature, then
     // we need to reconcile the user state
     if position.open {
+        update_open_positions(deps.storage, &info.sender, &position.lp_asset.denom, false)?;
         reconcile_user_state(deps, &info.sender, &position)?;
     }
 
@@ -462,9 +489,419 @@ pub(crate) fn withdraw_position(
             ("receiver", info.sender.to_string()),
             ("identifier", identifier),
         ])
+        .add_attributes(gauge_distributions)
         .add_messages(messages))
 }
 
+/// Extends the unlocking duration of an open position, increasing its weight starting on the next
+/// epoch.
+pub(crate) fn extend_position_lock(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    identifier: String,
+    new_unlocking_duration: u64,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let mut position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
+        ContractError::NoPositionFound {
+            identifier: identifier.clone(),
+        },
+    )?;
+
+    ensure!(
+        position.receiver == info.sender,
+        ContractError::Unauthorized
+    );
+
+    ensure!(
+        position.open,
+        ContractError::PositionAlreadyClosed { identifier }
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the unlocking duration can only be extended
+    ensure!(
+        new_unlocking_duration > position.unlocking_duration,
+        ContractError::InvalidUnlockingDuration {
+            min: position.unlocking_duration + 1u64,
+            max: config.max_unlocking_duration,
+            specified: new_unlocking_duration,
+        }
+    );
+    validate_unlocking_duration_for_position(&config, new_unlocking_duration)?;
+
+    update_weights(
+        deps.branch(),
+        &env,
+        &info.sender,
+        &position.lp_asset,
+        position.unlocking_duration,
+        false,
+    )?;
+    update_weights(
+        deps.branch(),
+        &env,
+        &info.sender,
+        &position.lp_asset,
+        new_unlocking_duration,
+        true,
+    )?;
+
+    let old_unlocking_duration = position.unlocking_duration;
+    position.unlocking_duration = new_unlocking_duration;
+    POSITIONS.save(deps.storage, &position.identifier, &position)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "extend_position_lock".to_string()),
+        ("receiver", info.sender.to_string()),
+        ("identifier", position.identifier),
+        ("old_unlocking_duration", old_unlocking_duration.to_string()),
+        ("new_unlocking_duration", new_unlocking_duration.to_string()),
+    ]))
+}
+
+/// Transfers a position to a new receiver. If the position is open, its weight is moved from the
+/// current receiver to the new one starting on the next epoch.
+pub(crate) fn transfer_position(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    identifier: String,
+    new_receiver: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    // distribute the gauge emissions for the current epoch, so they are accounted as pending rewards
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps.as_ref(),
+        config.epoch_manager_addr.to_string(),
+    )?;
+    let gauge_distributions = distribute_gauge_emissions(deps.storage, &env, current_epoch.id)?;
+
+    // check if the user has pending rewards. Can't transfer a position without claiming pending rewards first
+    validate_no_pending_rewards(deps.as_ref(), &env, &info.sender)?;
+
+    let mut position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
+        ContractError::NoPositionFound {
+            identifier: identifier.clone(),
+        },
+    )?;
+
+    ensure!(
+        position.receiver == info.sender,
+        ContractError::Unauthorized
+    );
+
+    let new_receiver = deps.api.addr_validate(&new_receiver)?;
+    ensure!(new_receiver != info.sender, ContractError::Unauthorized);
+
+    // the new receiver's weight changes too, so it can't have pending rewards either
+    validate_no_pending_rewards(deps.as_ref(), &env, &new_receiver)?;
+
+    position.receiver = new_receiver.clone();
+    POSITIONS.save(deps.storage, &identifier, &position)?;
+
+    // closed positions don't have weight, only the right to withdraw the LP is transferred
+    if position.open {
+        update_open_positions(deps.storage, &info.sender, &position.lp_asset.denom, false)?;
+        update_open_positions(deps.storage, &new_receiver, &position.lp_asset.denom, true)?;
+
+        update_weights(
+            deps.branch(),
+            &env,
+            &info.sender,
+            &position.lp_asset,
+            position.unlocking_duration,
+            false,
+        )?;
+        update_weights(
+            deps.branch(),
+            &env,
+            &new_receiver,
+            &position.lp_asset,
+            position.unlocking_duration,
+            true,
+        )?;
+
+        reconcile_user_state(deps, &info.sender, &position)?;
+    }
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "transfer_position".to_string()),
+            ("receiver", info.sender.to_string()),
+            ("new_receiver", new_receiver.to_string()),
+            ("identifier", identifier),
+        ])
+        .add_attributes(gauge_distributions))
+}
+
+/// Splits the given amount of LP off an open position into a new position with the same unlocking
+/// duration.
+pub(crate) fn split_position(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    identifier: String,
+    amount: Uint128,
+    new_identifier: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let mut position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
+        ContractError::NoPositionFound {
+            identifier: identifier.clone(),
+        },
+    )?;
+
+    ensure!(
+        position.receiver == info.sender,
+        ContractError::Unauthorized
+    );
+
+    ensure!(
+        position.open,
+        ContractError::PositionAlreadyClosed { identifier }
+    );
+
+    // both positions must keep some LP after the split
+    ensure!(
+        !amount.is_zero() && amount < position.lp_asset.amount,
+        ContractError::InvalidLpAmount {
+            expected: position.lp_asset.amount,
+            actual: amount,
+        }
+    );
+
+    let new_identifier = compute_position_identifier(deps.storage, new_identifier)?;
+
+    // remove the weight of the whole position and add the weight of both resulting positions, so
+    // rounding is consistent with positions created from scratch
+    update_weights(
+        deps.branch(),
+        &env,
+        &info.sender,
+        &position.lp_asset,
+        position.unlocking_duration,
+        false,
+    )?;
+
+    position.lp_asset.amount = position.lp_asset.amount.checked_sub(amount)?;
+
+    let new_position = Position {
+        identifier: new_identifier.clone(),
+        lp_asset: coin(amount.u128(), &position.lp_asset.denom),
+        unlocking_duration: position.unlocking_duration,
+        open: true,
+        expiring_at: None,
+        receiver: position.receiver.clone(),
+    };
+
+    for position in [&position, &new_position] {
+        POSITIONS.save(deps.storage, &position.identifier, position)?;
+
+        update_weights(
+            deps.branch(),
+            &env,
+            &info.sender,
+            &position.lp_asset,
+            position.unlocking_duration,
+            true,
+        )?;
+    }
+
+    update_open_positions(deps.storage, &info.sender, &position.lp_asset.denom, true)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "split_position".to_string()),
+        ("receiver", info.sender.to_string()),
+        ("position", position.to_string()),
+        ("new_position", new_position.to_string()),
+    ]))
+}
+
+/// Merges open positions of the same LP denom into the first of them. The merged position takes the
+/// longest unlocking duration of the positions, unless a longer one is given.
+pub(crate) fn merge_positions(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    identifiers: Vec<String>,
+    unlocking_duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let unique_identifiers: HashSet<&String> = identifiers.iter().collect();
+    ensure!(
+        identifiers.len() >= 2 && unique_identifiers.len() == identifiers.len(),
+        ContractError::InvalidPositionsToMerge
+    );
+
+    let positions = identifiers
+        .iter()
+        .map(|identifier| {
+            let position = get_position(deps.storage, Some(identifier.clone()))?.ok_or(
+                ContractError::NoPositionFound {
+                    identifier: identifier.clone(),
+                },
+            )?;
+
+            ensure!(
+                position.receiver == info.sender,
+                ContractError::Unauthorized
+            );
+
+            ensure!(
+                position.open,
+                ContractError::PositionAlreadyClosed {
+                    identifier: identifier.clone(),
+                }
+            );
+
+            Ok(position)
+        })
+        .collect::<Result<Vec<Position>, ContractError>>()?;
+
+    let lp_denom = positions[0].lp_asset.denom.clone();
+    ensure!(
+        positions
+            .iter()
+            .all(|position| position.lp_asset.denom == lp_denom),
+        ContractError::AssetMismatch
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let longest_unlocking_duration = positions
+        .iter()
+        .map(|position| position.unlocking_duration)
+        .max()
+        .unwrap_or_default();
+
+    // the lock of a position can't be shortened by merging it
+    let unlocking_duration = if let Some(unlocking_duration) = unlocking_duration {
+        ensure!(
+            unlocking_duration >= longest_unlocking_duration,
+            ContractError::InvalidUnlockingDuration {
+                min: longest_unlocking_duration,
+                max: config.max_unlocking_duration,
+                specified: unlocking_duration,
+            }
+        );
+        validate_unlocking_duration_for_position(&config, unlocking_duration)?;
+
+        unlocking_duration
+    } else {
+        longest_unlocking_duration
+    };
+
+    let mut merged_position = positions[0].clone();
+    merged_position.lp_asset.amount = Uint128::zero();
+    merged_position.unlocking_duration = unlocking_duration;
+
+    for position in positions.iter() {
+        update_weights(
+            deps.branch(),
+            &env,
+            &info.sender,
+            &position.lp_asset,
+            position.unlocking_duration,
+            false,
+        )?;
+
+        merged_position.lp_asset.amount = merged_position
+            .lp_asset
+            .amount
+            .checked_add(position.lp_asset.amount)?;
+    }
+
+    // the rest of the positions are absorbed by the first one
+    for position in positions.iter().skip(1) {
+        POSITIONS.remove(deps.storage, &position.identifier)?;
+        update_open_positions(deps.storage, &info.sender, &lp_denom, false)?;
+    }
+
+    POSITIONS.save(deps.storage, &merged_position.identifier, &merged_position)?;
+
+    update_weights(
+        deps.branch(),
+        &env,
+        &info.sender,
+        &merged_position.lp_asset,
+        merged_position.unlocking_duration,
+        true,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "merge_positions".to_string()),
+        ("receiver", info.sender.to_string()),
+        ("merged_identifiers", identifiers.join(",")),
+        ("position", merged_position.to_string()),
+    ]))
+}
+
+/// Recomputes the boost of the given address for the given LP denom, e.g. after its stake of the
+/// native token changed. The new weight applies from the next epoch. Anyone can refresh the boost of
+/// any address, so a boost can't be kept after unstaking.
+pub(crate) fn refresh_boost(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    lp_denom: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let base_lp_weight = get_base_address_weight(deps.storage, &address, &lp_denom)?;
+
+    ensure!(!base_lp_weight.is_zero(), ContractError::NoOpenPositions);
+
+    save_address_weight(deps.branch(), &env, &address, &lp_denom, base_lp_weight)?;
+
+    let (_, lp_weight) = get_latest_address_weight(deps.storage, &address, &lp_denom)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "refresh_boost".to_string()),
+        ("address", address.to_string()),
+        ("lp_denom", lp_denom),
+        ("base_lp_weight", base_lp_weight.to_string()),
+        ("lp_weight", lp_weight.to_string()),
+    ]))
+}
+
+/// Re-evaluates the boost of the given address for the given LP denoms when claiming, so a boost
+/// doesn't outlive the stake it was computed for. The weights whose boost changed are saved for the
+/// next epoch.
+pub(crate) fn refresh_boosts(
+    mut deps: DepsMut,
+    env: &Env,
+    address: &Addr,
+    lp_denoms: &[String],
+) -> Result<(), ContractError> {
+    for lp_denom in lp_denoms {
+        let base_lp_weight = get_base_address_weight(deps.storage, address, lp_denom)?;
+
+        if base_lp_weight.is_zero() {
+            continue;
+        }
+
+        let (_, lp_weight) = get_latest_address_weight(deps.storage, address, lp_denom)?;
+        let boosted_lp_weight =
+            calculate_boosted_weight(deps.as_ref(), address, lp_denom, base_lp_weight)?;
+
+        if boosted_lp_weight != lp_weight {
+            save_address_weight(deps.branch(), env, address, lp_denom, base_lp_weight)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Updates the weights when managing a position. Computes what the weight is gonna be in the next epoch.
 fn update_weights(
     deps: DepsMut,
@@ -473,6 +910,32 @@ fn update_weights(
     lp_asset: &Coin,
     unlocking_duration: u64,
     fill: bool,
+) -> Result<(), ContractError> {
+    let weight = calculate_weight(lp_asset, unlocking_duration)?;
+
+    // update the user's weight for this LP, before applying the boost
+    let mut base_lp_weight = get_base_address_weight(deps.storage, receiver, &lp_asset.denom)?;
+
+    if fill {
+        // filling position
+        base_lp_weight = base_lp_weight.checked_add(weight)?;
+    } else {
+        // closing position
+        base_lp_weight = base_lp_weight.saturating_sub(weight);
+    }
+
+    save_address_weight(deps, env, receiver, &lp_asset.denom, base_lp_weight)
+}
+
+/// Saves the weight the user is gonna have in the next epoch for the given LP, boosting its base
+/// weight, and updates the LP weight of the contract accordingly. The gauge votes the user cast on
+/// the current epoch are rescaled to its new voting power.
+fn save_address_weight(
+    deps: DepsMut,
+    env: &Env,
+    receiver: &Addr,
+    lp_denom: &str,
+    base_lp_weight: Uint128,
 ) -> Result<(), ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -481,46 +944,68 @@ fn update_weights(
         config.epoch_manager_addr.to_string(),
     )?;
 
-    let weight = calculate_weight(lp_asset, unlocking_duration)?;
-
-    let (_, mut lp_weight) =
-        get_latest_address_weight(deps.storage, &env.contract.address, &lp_asset.denom)?;
+    let (_, previous_address_lp_weight) =
+        get_latest_address_weight(deps.storage, receiver, lp_denom)?;
+    let address_lp_weight =
+        calculate_boosted_weight(deps.as_ref(), receiver, lp_denom, base_lp_weight)?;
+    let mut previous_voting_power = get_gauge_voting_power(
+        deps.storage,
+        &env.contract.address,
+        receiver,
+        current_epoch.id,
+    )?;
 
-    if fill {
-        // filling position
-        lp_weight = lp_weight.checked_add(weight)?;
-    } else {
-        // closing position
-        lp_weight = lp_weight.saturating_sub(weight);
+    // the open positions are updated before the weight, so the LP denom is no longer counted when
+    // the last position for it was just closed or transferred
+    if !get_address_lp_denoms(deps.storage, receiver)?.contains(&lp_denom.to_string()) {
+        previous_voting_power = previous_voting_power.checked_add(get_gauge_lp_voting_power(
+            deps.storage,
+            &env.contract.address,
+            receiver,
+            lp_denom,
+            current_epoch.id,
+        )?)?;
     }
 
-    // update the LP weight for the contract
+    // update the LP weight for the contract, replacing the user's previous weight
+    let (_, lp_weight) = get_latest_address_weight(deps.storage, &env.contract.address, lp_denom)?;
+    let lp_weight = lp_weight
+        .saturating_sub(previous_address_lp_weight)
+        .checked_add(address_lp_weight)?;
+
     LP_WEIGHT_HISTORY.save(
         deps.storage,
-        (
-            &env.contract.address,
-            &lp_asset.denom,
-            current_epoch.id + 1u64,
-        ),
+        (&env.contract.address, lp_denom, current_epoch.id + 1u64),
         &lp_weight,
     )?;
 
-    // update the user's weight for this LP
-    let (_, mut address_lp_weight) =
-        get_latest_address_weight(deps.storage, receiver, &lp_asset.denom)?;
+    LP_WEIGHT_HISTORY.save(
+        deps.storage,
+        (receiver, lp_denom, current_epoch.id + 1u64),
+        &address_lp_weight,
+    )?;
+
+    rescale_gauge_votes(
+        deps.storage,
+        &env.contract.address,
+        receiver,
+        current_epoch.id,
+        previous_voting_power,
+    )?;
 
-    if fill {
-        // filling position
-        address_lp_weight = address_lp_weight.checked_add(weight)?;
+    // keep track of the base weight only while it's boosted
+    if address_lp_weight == base_lp_weight {
+        BASE_LP_WEIGHTS.remove(deps.storage, (receiver, lp_denom));
     } else {
-        // closing position
-        address_lp_weight = address_lp_weight.saturating_sub(weight);
+        BASE_LP_WEIGHTS.save(deps.storage, (receiver, lp_denom), &base_lp_weight)?;
     }
 
-    LP_WEIGHT_HISTORY.save(
+    // compact the entries of the epochs the user claimed already
+    prune_address_lp_weight_history(
         deps.storage,
-        (receiver, &lp_asset.denom, current_epoch.id + 1u64),
-        &address_lp_weight,
+        receiver,
+        lp_denom,
+        LP_WEIGHT_HISTORY_PRUNE_LIMIT,
     )?;
 
     Ok(())
@@ -1,20 +1,22 @@
 use cosmwasm_std::{
-    ensure, Addr, BankMsg, Coin, CosmosMsg, Decimal, Decimal256, Deps, DepsMut, Env, MessageInfo,
-    Order, StdError, Storage, Uint128,
+    ensure, Addr, BankMsg, Coin, CosmosMsg, Decimal, Decimal256, Deps, DepsMut, Env, Order,
+    StdError, Storage, Uint128,
 };
 
 use mantra_dex_std::farm_manager::{Config, EpochId, Position, RewardsResponse};
 
-use crate::farm::commands::sync_address_lp_weight_history;
+use crate::farm::commands::{clear_last_claimed_farm_epochs, sync_address_lp_weight_history};
+use crate::helpers::validate_identifier;
+use crate::position::SECONDS_IN_YEAR;
 use crate::queries::query_rewards;
 use crate::state::{
-    get_positions_by_receiver, has_any_lp_weight, CONFIG, LAST_CLAIMED_EPOCH, LP_WEIGHT_HISTORY,
-    MAX_POSITIONS_LIMIT,
+    get_address_lp_denoms, get_position, has_any_lp_weight, ADDRESS_OPEN_POSITIONS,
+    BASE_LP_WEIGHTS, BOOST_CONFIG, CONFIG, LAST_CLAIMED_EPOCH, LP_WEIGHT_HISTORY,
+    POSITION_ID_COUNTER,
 };
 use crate::ContractError;
 
 const SECONDS_IN_DAY: u64 = 86400;
-const SECONDS_IN_YEAR: u64 = 31556926;
 
 /// The prefix used when creation a position with an auto-generated ID
 pub const AUTO_POSITION_ID_PREFIX: &str = "p-";
@@ -22,9 +24,6 @@ pub const AUTO_POSITION_ID_PREFIX: &str = "p-";
 /// The prefix used when creation a position with an explicitly provided ID
 pub const EXPLICIT_POSITION_ID_PREFIX: &str = "u-";
 
-/// The penalty fee share that is given to the owner of the farm if someone does an emergency withdraw
-pub const PENALTY_FEE_SHARE: Decimal = Decimal::percent(50);
-
 /// Calculates the weight size for a user filling a position
 pub fn calculate_weight(
     lp_asset: &Coin,
@@ -90,6 +89,77 @@ pub fn get_latest_address_weight(
     return_latest_weight(result)
 }
 
+/// Gets the weight of the positions an address has for the given lp denom, before applying its boost.
+pub(crate) fn get_base_address_weight(
+    storage: &dyn Storage,
+    address: &Addr,
+    lp_denom: &str,
+) -> Result<Uint128, ContractError> {
+    match BASE_LP_WEIGHTS.may_load(storage, (address, lp_denom))? {
+        Some(base_weight) => Ok(base_weight),
+        // the weight is not boosted
+        None => Ok(get_latest_address_weight(storage, address, lp_denom)?.1),
+    }
+}
+
+/// Boosts the given weight according to the amount of the native token the address has staked with
+/// the chain's staking module. The stake is split across the LP denoms the address has positions
+/// for, pro rata to their weight, so the same stake can't boost each of them fully. The boost grows
+/// linearly with the stake, up to the max boost, which is reached when staking `stake_per_weight`
+/// per unit of the total weight of the address.
+pub(crate) fn calculate_boosted_weight(
+    deps: Deps,
+    address: &Addr,
+    lp_denom: &str,
+    weight: Uint128,
+) -> Result<Uint128, ContractError> {
+    let Some(boost_config) = BOOST_CONFIG.may_load(deps.storage)? else {
+        return Ok(weight);
+    };
+
+    if weight.is_zero() {
+        return Ok(weight);
+    }
+
+    // only the stake in the native token counts towards the boost
+    let bonded_denom = deps.querier.query_bonded_denom()?;
+    let staked = deps
+        .querier
+        .query_all_delegations(address)?
+        .iter()
+        .filter(|delegation| delegation.amount.denom == bonded_denom)
+        .try_fold(Uint128::zero(), |acc, delegation| {
+            acc.checked_add(delegation.amount.amount)
+        })?;
+
+    // the weight of the address across all its LP denoms, before applying the boost
+    let mut total_weight = weight;
+    for address_lp_denom in get_address_lp_denoms(deps.storage, address)? {
+        if address_lp_denom != lp_denom {
+            total_weight = total_weight.checked_add(get_base_address_weight(
+                deps.storage,
+                address,
+                &address_lp_denom,
+            )?)?;
+        }
+    }
+
+    // the stake needed to get the max boost
+    let max_boost_stake = total_weight.checked_mul_ceil(boost_config.stake_per_weight)?;
+    let boost_share = Decimal::checked_from_ratio(staked, max_boost_stake)
+        .unwrap_or(Decimal::one())
+        .min(Decimal::one());
+
+    let boost = Decimal::one().checked_add(
+        boost_config
+            .max_boost
+            .checked_sub(Decimal::one())?
+            .checked_mul(boost_share)?,
+    )?;
+
+    Ok(weight.checked_mul_floor(boost)?)
+}
+
 /// Helper function to return the weight from the result. If the result is None, i.e. the weight
 /// was not found in the map, it returns (0, 0).
 fn return_latest_weight(
@@ -102,6 +172,36 @@ fn return_latest_weight(
     }
 }
 
+/// Computes the identifier for a new position. Explicit identifiers are prefixed with
+/// [EXPLICIT_POSITION_ID_PREFIX], otherwise one is generated with the position counter and prefixed
+/// with [AUTO_POSITION_ID_PREFIX]. Fails if a position with the resulting identifier already exists.
+pub(crate) fn compute_position_identifier(
+    storage: &mut dyn Storage,
+    identifier: Option<String>,
+) -> Result<String, ContractError> {
+    let identifier = if let Some(identifier) = identifier {
+        // prepend EXPLICIT_POSITION_ID_PREFIX to identifier
+        format!("{EXPLICIT_POSITION_ID_PREFIX}{identifier}")
+    } else {
+        let position_id_counter = POSITION_ID_COUNTER.may_load(storage)?.unwrap_or_default() + 1u64;
+        POSITION_ID_COUNTER.save(storage, &position_id_counter)?;
+        // prepend AUTO_POSITION_ID_PREFIX to the position_id_counter
+        format!("{AUTO_POSITION_ID_PREFIX}{position_id_counter}")
+    };
+
+    validate_identifier(&identifier)?;
+
+    // check if there's an existing position with the computed identifier
+    ensure!(
+        get_position(storage, Some(identifier.clone()))?.is_none(),
+        ContractError::PositionAlreadyExists {
+            identifier: identifier.clone(),
+        }
+    );
+
+    Ok(identifier)
+}
+
 /// Validates the `unlocking_duration` specified in the position params is within the range specified
 /// in the config.
 pub(crate) fn validate_unlocking_duration_for_position(
@@ -121,26 +221,29 @@ pub(crate) fn validate_unlocking_duration_for_position(
     Ok(())
 }
 
-/// Validates the amount of positions a user can have either open or closed at a given time.
-pub(crate) fn validate_positions_limit(
-    deps: Deps,
+/// Keeps track of the number of open positions the receiver has for the given LP denom, increasing
+/// it when a position is opened and decreasing it when a position stops being open.
+pub(crate) fn update_open_positions(
+    storage: &mut dyn Storage,
     receiver: &Addr,
-    open_state: bool,
+    lp_denom: &str,
+    open: bool,
 ) -> Result<(), ContractError> {
-    let existing_user_positions = get_positions_by_receiver(
-        deps.storage,
-        receiver.as_str(),
-        Some(open_state),
-        None,
-        Some(MAX_POSITIONS_LIMIT),
-    )?;
+    let open_positions = ADDRESS_OPEN_POSITIONS
+        .may_load(storage, (receiver, lp_denom))?
+        .unwrap_or_default();
+
+    let open_positions = if open {
+        open_positions + 1u64
+    } else {
+        open_positions.saturating_sub(1u64)
+    };
 
-    ensure!(
-        existing_user_positions.len() < MAX_POSITIONS_LIMIT as usize,
-        ContractError::MaxPositionsPerUserExceeded {
-            max: MAX_POSITIONS_LIMIT
-        }
-    );
+    if open_positions == 0u64 {
+        ADDRESS_OPEN_POSITIONS.remove(storage, (receiver, lp_denom));
+    } else {
+        ADDRESS_OPEN_POSITIONS.save(storage, (receiver, lp_denom), &open_positions)?;
+    }
 
     Ok(())
 }
@@ -163,13 +266,13 @@ pub(crate) fn create_penalty_share_msg(
     .into()
 }
 
-/// Validates that the user has no pending rewards before performing an operation.
+/// Validates that the given address has no pending rewards before performing an operation.
 pub fn validate_no_pending_rewards(
     deps: Deps,
     env: &Env,
-    info: &MessageInfo,
+    address: &Addr,
 ) -> Result<(), ContractError> {
-    let rewards_response = query_rewards(deps, env, info.sender.clone().into_string(), None)?;
+    let rewards_response = query_rewards(deps, env, address.to_string(), None, None, None)?;
 
     match rewards_response {
         RewardsResponse::RewardsResponse { total_rewards, .. } => {
@@ -183,9 +286,8 @@ pub fn validate_no_pending_rewards(
 
 /// Reconciles a user's state by updating or removing stale data based on their current open positions.
 ///
-/// This function checks for two primary conditions:
-/// 1. If the user has no more open positions, it clears the LAST_CLAIMED_EPOCH state item.
-/// 2. If the user has no more open positions for a specific LP denom, it wipes the LP weight history for that denom.
+/// If the user has no more open positions for a specific LP denom, it clears the LAST_CLAIMED_EPOCH and
+/// LAST_CLAIMED_FARM_EPOCH entries and wipes the LP weight history for that denom.
 ///
 /// Why do we need to do this?
 /// If the lp history and the LAST_CLAIMED_EPOCH for the user is not cleared when fully existing the farm,
@@ -202,27 +304,14 @@ pub fn reconcile_user_state(
     receiver: &Addr,
     position: &Position,
 ) -> Result<(), ContractError> {
-    let receiver_open_positions = get_positions_by_receiver(
-        deps.storage,
-        receiver.as_ref(),
-        Some(true),
-        None,
-        Some(MAX_POSITIONS_LIMIT),
-    )?;
+    let receiver_lp_denoms = get_address_lp_denoms(deps.storage, receiver)?;
 
-    // if the user has no more open positions, clear the last claimed epoch
-    if receiver_open_positions.is_empty() {
-        LAST_CLAIMED_EPOCH.remove(deps.storage, receiver);
-    }
+    // if the user has no more open positions for the position's LP denom, clear the last claimed
+    // epochs and wipe the LP weight history for that denom
+    if !receiver_lp_denoms.contains(&position.lp_asset.denom) {
+        LAST_CLAIMED_EPOCH.remove(deps.storage, (receiver, &position.lp_asset.denom));
+        clear_last_claimed_farm_epochs(deps.storage, receiver, &position.lp_asset.denom, None)?;
 
-    // if the user has no more open positions for the position's LP denom, wipe the LP weight
-    // history for that denom
-    if receiver_open_positions
-        .iter()
-        .filter(|p| p.lp_asset.denom == position.lp_asset.denom)
-        .collect::<Vec<_>>()
-        .is_empty()
-    {
         // if it doesn't have any it means it was already cleared up when closing the position,
         // but it is different if the user emergency exits an open position.
         // if withdrawing a position after closing it, this won't be triggered as it was already
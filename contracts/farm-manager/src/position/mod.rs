@@ -1,3 +1,20 @@
 pub mod commands;
 mod helpers;
 mod tests;
+
+use cosmwasm_std::Decimal;
+use mantra_dex_std::farm_manager::PenaltySplit;
+
+/// The number of seconds in a year, used to compute lock weights and annualized yields
+pub(crate) const SECONDS_IN_YEAR: u64 = 31556926;
+
+/// The default split of the emergency unlock penalty, half goes to the owners of the farms and half
+/// to the fee collector
+pub const DEFAULT_PENALTY_SPLIT: PenaltySplit = PenaltySplit {
+    farm_owners: Decimal::percent(50),
+    fee_collector: Decimal::percent(50),
+    redistribution: Decimal::zero(),
+};
+
+/// The maximum boost that can be configured for the weights of the stakers of the native token
+pub const MAX_BOOST: Decimal = Decimal::percent(1_000);
@@ -1,17 +1,26 @@
-use cosmwasm_std::{Coin, Deps, Env};
+use cosmwasm_std::{
+    coin, ensure, Coin, Decimal, Decimal256, Deps, Env, Order, StdResult, Uint128, Uint256,
+};
 
 use mantra_dex_std::coin::aggregate_coins;
 use mantra_dex_std::farm_manager::{
-    Config, EpochId, FarmsBy, FarmsResponse, LpWeightResponse, PositionsBy, PositionsResponse,
-    RewardsResponse,
+    BoostConfigResponse, ClaimHistoryResponse, ClaimOperatorsResponse, Config,
+    DetailedRewardsResponse, EpochId, FarmApr, FarmAprResponse, FarmAssetsResponse,
+    FarmEmissionsResponse, FarmRewards, FarmsBy, FarmsResponse, GaugeResponse, GaugeVotesResponse,
+    LpWeightHistoryResponse, LpWeightResponse, PositionsBy, PositionsResponse, RewardsResponse,
+    StakeableDenomsResponse,
 };
+use mantra_dex_std::pool_manager::PoolsResponse;
 
-use crate::farm::commands::calculate_rewards;
-use crate::helpers::get_unique_lp_asset_denoms_from_positions;
+use crate::farm::commands::{calculate_rewards, compute_farm_epoch_rewards};
+use crate::position::SECONDS_IN_YEAR;
 use crate::state::{
-    get_farm_by_identifier, get_farms, get_farms_by_farm_asset, get_farms_by_lp_denom,
-    get_position, get_positions, get_positions_by_receiver, CONFIG, LP_WEIGHT_HISTORY,
-    MAX_POSITIONS_LIMIT,
+    get_address_lp_denoms, get_address_lp_weight_at_epoch, get_claim_operators,
+    get_claimed_farm_rewards, get_farm_assets, get_farm_by_identifier, get_farm_pauses, get_farms,
+    get_farms_by_farm_asset, get_farms_by_lp_denom, get_lp_weight_history, get_position,
+    get_positions, get_positions_by_receiver, get_stakeable_denoms, ADDRESS_GAUGE_VOTES,
+    BOOST_CONFIG, CLAIMED_REWARDS, CONFIG, GAUGE_BUDGET, GAUGE_VOTES, LAST_CLAIMED_EPOCH,
+    LP_WEIGHT_HISTORY, MAX_FARMS_LIMIT, MAX_FARM_EMISSIONS_LIMIT, STAKEABLE_DENOMS,
 };
 use crate::{helpers, ContractError};
 
@@ -73,24 +82,25 @@ pub(crate) fn query_positions(
     Ok(PositionsResponse { positions })
 }
 
-/// Queries the rewards for a given address.
+/// Queries the rewards for a given address. The rewards can be restricted to the given LP denom
+/// and/or farm.
 pub(crate) fn query_rewards(
     deps: Deps,
     env: &Env,
     address: String,
     until_epoch: Option<EpochId>,
+    lp_denom: Option<String>,
+    farm_identifier: Option<String>,
 ) -> Result<RewardsResponse, ContractError> {
     let receiver = deps.api.addr_validate(&address)?;
     // check if the user has any open LP positions
-    let open_positions = get_positions_by_receiver(
-        deps.storage,
-        receiver.as_str(),
-        Some(true),
-        None,
-        Some(MAX_POSITIONS_LIMIT),
-    )?;
+    let mut lp_denoms = get_address_lp_denoms(deps.storage, &receiver)?;
+
+    if let Some(lp_denom) = &lp_denom {
+        lp_denoms.retain(|denom| denom == lp_denom);
+    }
 
-    if open_positions.is_empty() {
+    if lp_denoms.is_empty() {
         // if the user has no open LP positions, return an empty rewards list
         return Ok(RewardsResponse::RewardsResponse {
             total_rewards: vec![],
@@ -98,6 +108,8 @@ pub(crate) fn query_rewards(
         });
     }
 
+    let farm_identifiers = farm_identifier.map(|farm_identifier| vec![farm_identifier]);
+
     let config = CONFIG.load(deps.storage)?;
     let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
         deps,
@@ -109,12 +121,17 @@ pub(crate) fn query_rewards(
     let mut total_rewards = vec![];
     let mut rewards_per_lp: Vec<(String, Vec<Coin>)> = vec![];
 
-    let lp_denoms = get_unique_lp_asset_denoms_from_positions(open_positions);
-
     for lp_denom in &lp_denoms {
         // calculate the rewards for the lp denom
-        let rewards_response =
-            calculate_rewards(deps, env, lp_denom, &receiver, until_epoch, false)?;
+        let rewards_response = calculate_rewards(
+            deps,
+            env,
+            lp_denom,
+            &receiver,
+            until_epoch,
+            farm_identifiers.as_ref(),
+            false,
+        )?;
         match rewards_response {
             RewardsResponse::QueryRewardsResponse { rewards } => {
                 total_rewards.append(&mut rewards.clone());
@@ -134,6 +151,91 @@ pub(crate) fn query_rewards(
     })
 }
 
+/// Queries the pending rewards for a given address broken down by farm. For every epoch the rewards
+/// are computed for, it details the farm's emission, the weight of the address and the total weight,
+/// the reward being the address' share of the emission. The rewards can be restricted to the given
+/// LP denom and/or farm.
+pub(crate) fn query_detailed_rewards(
+    deps: Deps,
+    env: &Env,
+    address: String,
+    until_epoch: Option<EpochId>,
+    lp_denom: Option<String>,
+    farm_identifier: Option<String>,
+) -> Result<DetailedRewardsResponse, ContractError> {
+    let receiver = deps.api.addr_validate(&address)?;
+    let mut lp_denoms = get_address_lp_denoms(deps.storage, &receiver)?;
+
+    if let Some(lp_denom) = &lp_denom {
+        lp_denoms.retain(|denom| denom == lp_denom);
+    }
+
+    if lp_denoms.is_empty() {
+        return Ok(DetailedRewardsResponse { rewards: vec![] });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps,
+        config.epoch_manager_addr.into_string(),
+    )?;
+
+    let until_epoch = helpers::until_epoch_or_current(until_epoch, &current_epoch)?;
+
+    let mut rewards = vec![];
+
+    for lp_denom in &lp_denoms {
+        let last_claimed_epoch =
+            LAST_CLAIMED_EPOCH.may_load(deps.storage, (&receiver, lp_denom))?;
+
+        if let Some(last_claimed_epoch) = last_claimed_epoch {
+            ensure!(
+                until_epoch >= last_claimed_epoch,
+                ContractError::InvalidUntilEpoch { until_epoch }
+            );
+
+            // the rewards were claimed already
+            if until_epoch == last_claimed_epoch {
+                continue;
+            }
+        }
+
+        let mut farms = get_farms_by_lp_denom(deps.storage, lp_denom, None, Some(MAX_FARMS_LIMIT))?;
+
+        if let Some(farm_identifier) = &farm_identifier {
+            farms.retain(|farm| &farm.identifier == farm_identifier);
+        }
+
+        for farm in farms {
+            let epochs = compute_farm_epoch_rewards(
+                deps,
+                env,
+                &farm,
+                &receiver,
+                last_claimed_epoch,
+                until_epoch,
+            )?;
+
+            if epochs.is_empty() {
+                continue;
+            }
+
+            let reward = epochs
+                .iter()
+                .try_fold(Uint128::zero(), |acc, epoch| acc.checked_add(epoch.reward))?;
+
+            rewards.push(FarmRewards {
+                farm_identifier: farm.identifier,
+                lp_denom: farm.lp_denom,
+                reward: coin(reward.u128(), farm.farm_asset.denom),
+                epochs,
+            });
+        }
+    }
+
+    Ok(DetailedRewardsResponse { rewards })
+}
+
 /// Queries the total lp weight for the given denom on the given epoch, i.e. the lp weight snapshot.
 pub(crate) fn query_lp_weight(
     deps: Deps,
@@ -153,3 +255,283 @@ pub(crate) fn query_lp_weight(
         epoch_id,
     })
 }
+
+/// Queries the total lp weight for the given denom on the given epoch, i.e. the sum of the weights of
+/// all the positions. The contract only takes a snapshot of the total weight when it changes, so
+/// this returns the latest snapshot taken on or before the given epoch.
+pub(crate) fn query_total_lp_weight(
+    deps: Deps,
+    env: &Env,
+    denom: String,
+    epoch_id: EpochId,
+) -> Result<LpWeightResponse, ContractError> {
+    let lp_weight =
+        get_address_lp_weight_at_epoch(deps.storage, &env.contract.address, &denom, epoch_id)?;
+
+    Ok(LpWeightResponse {
+        lp_weight,
+        epoch_id,
+    })
+}
+
+/// Queries the lp weight snapshots of the given address for the given denom. Querying the contract
+/// address returns the snapshots of the total lp weight.
+pub(crate) fn query_lp_weight_history(
+    deps: Deps,
+    address: String,
+    denom: String,
+    start_after: Option<EpochId>,
+    limit: Option<u32>,
+) -> Result<LpWeightHistoryResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let weights = get_lp_weight_history(deps.storage, &address, &denom, start_after, limit)?;
+
+    Ok(LpWeightHistoryResponse { weights })
+}
+
+/// Queries the gauge emission budget.
+pub(crate) fn query_gauge(deps: Deps) -> Result<GaugeResponse, ContractError> {
+    Ok(GaugeResponse {
+        budget: GAUGE_BUDGET.may_load(deps.storage)?,
+    })
+}
+
+/// Queries the gauge votes cast on the given epoch. If a voter is provided, it returns the votes cast
+/// by that address, otherwise the total votes each LP denom received.
+pub(crate) fn query_gauge_votes(
+    deps: Deps,
+    epoch_id: EpochId,
+    voter: Option<String>,
+) -> Result<GaugeVotesResponse, ContractError> {
+    let votes = if let Some(voter) = voter {
+        ADDRESS_GAUGE_VOTES
+            .may_load(deps.storage, (epoch_id, &deps.api.addr_validate(&voter)?))?
+            .unwrap_or_default()
+    } else {
+        GAUGE_VOTES
+            .prefix(epoch_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(String, Uint128)>>>()?
+    };
+
+    Ok(GaugeVotesResponse { epoch_id, votes })
+}
+
+/// Queries the operators approved by the given address to claim rewards on its behalf.
+pub(crate) fn query_claim_operators(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ClaimOperatorsResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let start_after = start_after
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+
+    let operators = get_claim_operators(deps.storage, &address, start_after, limit)?;
+
+    Ok(ClaimOperatorsResponse { operators })
+}
+
+/// Queries the lifetime rewards the given address claimed, in total and from each farm.
+pub(crate) fn query_claim_history(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<ClaimHistoryResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+
+    let total_claimed = CLAIMED_REWARDS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, amount) = item?;
+            Ok(Coin { denom, amount })
+        })
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    let farms = get_claimed_farm_rewards(deps.storage, &address, start_after, limit)?;
+
+    Ok(ClaimHistoryResponse {
+        total_claimed,
+        farms,
+    })
+}
+
+/// Queries the denoms allowed to be used as farm assets, with the minimum amount a farm has to be
+/// created with.
+pub(crate) fn query_farm_assets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<FarmAssetsResponse, ContractError> {
+    let assets = get_farm_assets(deps.storage, start_after, limit)?;
+
+    Ok(FarmAssetsResponse { assets })
+}
+
+/// Queries the boost applied to the weights of the addresses staking the native token.
+pub(crate) fn query_boost_config(deps: Deps) -> Result<BoostConfigResponse, ContractError> {
+    Ok(BoostConfigResponse {
+        boost_config: BOOST_CONFIG.may_load(deps.storage)?,
+    })
+}
+
+/// Queries the non-LP denoms allowed to be staked.
+pub(crate) fn query_stakeable_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<StakeableDenomsResponse, ContractError> {
+    let denoms = get_stakeable_denoms(deps.storage, start_after, limit)?;
+
+    Ok(StakeableDenomsResponse { denoms })
+}
+
+/// Queries the amount the given farm emits on each epoch within the given range, both ends
+/// inclusive. Defaults to the whole farm, truncated to [MAX_FARM_EMISSIONS_LIMIT] epochs.
+pub(crate) fn query_farm_emissions(
+    deps: Deps,
+    farm_identifier: String,
+    from_epoch: Option<EpochId>,
+    to_epoch: Option<EpochId>,
+) -> Result<FarmEmissionsResponse, ContractError> {
+    let farm = get_farm_by_identifier(deps.storage, &farm_identifier)?;
+    let farm_pauses = get_farm_pauses(deps.storage, &farm.identifier)?;
+
+    let from_epoch = from_epoch.unwrap_or(farm.start_epoch);
+    // the preliminary_end_epoch is not inclusive
+    let to_epoch = to_epoch
+        .unwrap_or(farm.preliminary_end_epoch.saturating_sub(1u64))
+        .min(from_epoch.saturating_add(MAX_FARM_EMISSIONS_LIMIT - 1u64));
+
+    ensure!(
+        from_epoch <= to_epoch,
+        ContractError::InvalidEpoch {
+            which: "from".to_string()
+        }
+    );
+
+    let emissions = (from_epoch..=to_epoch)
+        .map(|epoch_id| {
+            let amount = helpers::get_farm_emission_for_epoch(&farm, &farm_pauses, epoch_id)?;
+            Ok((epoch_id, coin(amount.u128(), &farm.farm_asset.denom)))
+        })
+        .collect::<Result<Vec<(EpochId, Coin)>, ContractError>>()?;
+
+    Ok(FarmEmissionsResponse {
+        farm_identifier: farm.identifier,
+        emissions,
+    })
+}
+
+/// Queries the yield the farms for the given denom pay on the current epoch. The APR is the base
+/// rate earned by a position with a weight multiplier of one, i.e. locked for the minimum unlocking
+/// duration.
+///
+/// For LP denoms, the LP is valued in terms of the reward asset using the pool reserves, assuming
+/// all the assets in the pool are equally valued. The APR is not computed for farms rewarding an
+/// asset that is not in the pool, nor for non-LP denoms rewarding a different denom, as there's no
+/// way to value one in terms of the other.
+pub(crate) fn query_farm_apr(
+    deps: Deps,
+    env: &Env,
+    lp_denom: String,
+) -> Result<FarmAprResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = mantra_dex_std::epoch_manager::get_current_epoch(
+        deps,
+        config.epoch_manager_addr.to_string(),
+    )?;
+    let epoch_manager_config: mantra_dex_std::epoch_manager::ConfigResponse =
+        deps.querier.query_wasm_smart(
+            config.epoch_manager_addr.to_string(),
+            &mantra_dex_std::epoch_manager::QueryMsg::Config {},
+        )?;
+    let epoch_duration = epoch_manager_config.epoch_config.duration.u64();
+
+    let total_lp_weight = get_address_lp_weight_at_epoch(
+        deps.storage,
+        &env.contract.address,
+        &lp_denom,
+        current_epoch.id,
+    )?;
+
+    // the pool reserves, and the total shares of the pool, to value the LP with
+    let pool = if STAKEABLE_DENOMS.has(deps.storage, &lp_denom) {
+        None
+    } else {
+        let pools_response: PoolsResponse = deps.querier.query_wasm_smart(
+            config.pool_manager_addr.to_string(),
+            &mantra_dex_std::pool_manager::QueryMsg::Pools {
+                pool_identifier: Some(helpers::get_pool_identifier(&lp_denom)?),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+
+        pools_response
+            .pools
+            .into_iter()
+            .next()
+            .map(|pool| (pool.pool_info.assets, pool.total_share.amount))
+    };
+
+    let farms = get_farms_by_lp_denom(deps.storage, &lp_denom, None, Some(MAX_FARMS_LIMIT))?
+        .into_iter()
+        .map(|farm| {
+            let farm_pauses = get_farm_pauses(deps.storage, &farm.identifier)?;
+            let emission =
+                helpers::get_farm_emission_for_epoch(&farm, &farm_pauses, current_epoch.id)?;
+
+            // the value of one LP, expressed in the reward asset. Farms paying in the LP denom
+            // itself, e.g. the penalty farms, are worth one LP per LP
+            let lp_value = match &pool {
+                _ if farm.farm_asset.denom == lp_denom => Some(Decimal256::one()),
+                Some((assets, total_share)) => assets
+                    .iter()
+                    .find(|asset| asset.denom == farm.farm_asset.denom)
+                    .and_then(|reward_reserve| {
+                        Decimal256::checked_from_ratio(
+                            Uint256::from(reward_reserve.amount)
+                                .checked_mul(Uint256::from(assets.len() as u64))
+                                .ok()?,
+                            *total_share,
+                        )
+                        .ok()
+                    }),
+                None => None,
+            };
+
+            // an empty pool, or an APR too large to be represented, is not meaningful
+            let apr = match lp_value {
+                Some(lp_value) if !total_lp_weight.is_zero() && !lp_value.is_zero() => {
+                    Decimal256::checked_from_ratio(
+                        Uint256::from(emission).checked_mul(Uint256::from(SECONDS_IN_YEAR))?,
+                        Uint256::from(epoch_duration)
+                            .checked_mul(Uint256::from(total_lp_weight))?,
+                    )?
+                    .checked_div(lp_value)
+                    .ok()
+                    .and_then(|apr| Decimal::try_from(apr).ok())
+                }
+                _ => None,
+            };
+
+            Ok(FarmApr {
+                farm_identifier: farm.identifier,
+                emission: coin(emission.u128(), farm.farm_asset.denom),
+                apr,
+            })
+        })
+        .collect::<Result<Vec<FarmApr>, ContractError>>()?;
+
+    Ok(FarmAprResponse {
+        lp_denom,
+        epoch_id: current_epoch.id,
+        total_lp_weight,
+        farms,
+    })
+}
@@ -1,10 +1,14 @@
 use std::clone::Clone;
 use std::string::ToString;
 
-use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Decimal, Order, StdResult, Storage, Uint128};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
 
-use mantra_dex_std::farm_manager::{Config, EpochId, Farm, Position};
+use mantra_dex_std::farm_manager::{
+    BoostConfig, ClaimOperator, Config, EpochId, Farm, GaugeBudget, Position,
+};
 
 use crate::ContractError;
 
@@ -44,8 +48,24 @@ impl IndexList<Position> for PositionIndexes<'_> {
     }
 }
 
-/// The last epoch an address claimed rewards
-pub const LAST_CLAIMED_EPOCH: Map<&Addr, EpochId> = Map::new("last_claimed_epoch");
+/// The number of open positions an address has for each LP denom. It allows computing rewards
+/// across all the LP denoms of an address, no matter how many positions it has.
+/// Key is a tuple of (address, lp_denom), value is the number of open positions.
+pub const ADDRESS_OPEN_POSITIONS: Map<(&Addr, &str), u64> = Map::new("address_open_positions");
+
+/// The last epoch an address claimed rewards for all the farms of a given LP denom.
+/// Key is a tuple of (address, lp_denom), value is the epoch id.
+pub const LAST_CLAIMED_EPOCH: Map<(&Addr, &str), EpochId> =
+    Map::new("last_claimed_epoch_by_lp_denom");
+
+/// The last epoch an address claimed rewards for a particular farm, when claiming a subset of the
+/// farms of an LP denom. Key is a tuple of (address, lp_denom, farm_identifier), value is the epoch id.
+pub const LAST_CLAIMED_FARM_EPOCH: Map<(&Addr, &str, &str), EpochId> =
+    Map::new("last_claimed_farm_epoch");
+
+/// The operators approved to claim rewards on behalf of an address. Key is a tuple of
+/// (owner, operator), value is the approval.
+pub const CLAIM_OPERATORS: Map<(&Addr, &Addr), ClaimOperatorApproval> = Map::new("claim_operators");
 
 /// The lp weight history for addresses, including the contract. i.e. how much lp weight an address
 /// or contract has at a given epoch.
@@ -80,13 +100,124 @@ impl IndexList<Farm> for FarmIndexes<'_> {
     }
 }
 
+/// The protocol emission budget that is split across LP denoms according to the gauge votes.
+pub const GAUGE_BUDGET: Item<GaugeBudget> = Item::new("gauge_budget");
+
+/// The total vote weight each LP denom received on a given epoch.
+/// Key is a tuple of (epoch_id, lp_denom), value is the vote weight.
+pub const GAUGE_VOTES: Map<(EpochId, &str), Uint128> = Map::new("gauge_votes");
+
+/// The gauge votes an address cast on a given epoch, as (lp_denom, vote weight) pairs.
+/// Key is a tuple of (epoch_id, address).
+pub const ADDRESS_GAUGE_VOTES: Map<(EpochId, &Addr), Vec<(String, Uint128)>> =
+    Map::new("address_gauge_votes");
+
+/// The farm receiving the gauge emissions of each LP denom. Key is the lp_denom, value is the farm identifier.
+pub const GAUGE_FARMS: Map<&str, String> = Map::new("gauge_farms");
+
+/// The farm redistributing the emergency unlock penalties of each LP denom to the remaining LPs. Key
+/// is the lp_denom, value is the farm identifier.
+pub const PENALTY_FARMS: Map<&str, String> = Map::new("penalty_farms");
+
+/// The non-LP denoms the owner allowed to be staked in positions and incentivized with farms, as LP
+/// denoms are. Key is the denom.
+pub const STAKEABLE_DENOMS: Map<&str, ()> = Map::new("stakeable_denoms");
+
+/// The denoms the owner allowed to be used as farm assets, with the minimum amount a farm has to be
+/// created with. Any denom can be used while the allowlist is empty. Key is the denom, value is the
+/// minimum amount.
+pub const FARM_ASSETS: Map<&str, Uint128> = Map::new("farm_assets");
+
+/// A period a farm doesn't emit on, after which its emissions are shifted by the number of epochs
+/// it was paused for.
+#[cw_serde]
+pub struct FarmPause {
+    /// The first epoch the farm doesn't emit on.
+    pub paused_from: EpochId,
+    /// The epoch the farm emits again from, or None if the farm is still paused.
+    pub resumed_from: Option<EpochId>,
+}
+
+/// The approval of a claim operator.
+#[cw_serde]
+pub struct ClaimOperatorApproval {
+    /// When the approval expires.
+    pub expires: Expiration,
+    /// The address, other than the owner, the operator is allowed to send the claimed rewards to.
+    pub receiver: Option<Addr>,
+}
+
+/// Holds the information needed to compound the rewards that are swapped into the pool assets when
+/// compounding a position, once the swaps are done.
+#[cw_serde]
+pub struct CompoundSwapBuffer {
+    /// The identifier of the position the rewards are compounded into.
+    pub position_identifier: String,
+    /// The receiver of the position.
+    pub receiver: Addr,
+    /// The unlocking duration of the position.
+    pub unlocking_duration: u64,
+    /// The identifier of the pool of the position.
+    pub pool_identifier: String,
+    /// The maximum slippage allowed when providing the swapped rewards as liquidity.
+    pub max_slippage: Decimal,
+    /// The pending swaps, as (ask_asset_denom, contract balance of the ask asset before the swap),
+    /// in the order they are executed.
+    pub swaps: Vec<(String, Uint128)>,
+}
+
+pub const COMPOUND_SWAP_BUFFER: Item<CompoundSwapBuffer> = Item::new("compound_swap_buffer");
+
+/// The amounts third parties contributed to farms. The owner of a farm funded whatever the
+/// contributors didn't. Key is a tuple of (farm_identifier, contributor), value is the amount.
+pub const FARM_CONTRIBUTIONS: Map<(&str, &Addr), Uint128> = Map::new("farm_contributions");
+
+/// The boost applied to the weights of the addresses staking the native token. If not set, the
+/// weights are not boosted.
+pub const BOOST_CONFIG: Item<BoostConfig> = Item::new("boost_config");
+
+/// The weight of the positions an address has for a given lp denom before applying its boost. It is
+/// only stored while the weight recorded in the lp weight history is boosted. Key is a tuple of
+/// (address, lp_denom).
+pub const BASE_LP_WEIGHTS: Map<(&Addr, &str), Uint128> = Map::new("base_lp_weights");
+
+/// The lifetime rewards an address claimed from each farm. Key is a tuple of (address,
+/// farm_identifier), value is the claimed coins, as the identifier of a closed farm can be reused.
+pub const CLAIMED_FARM_REWARDS: Map<(&Addr, &str), Vec<Coin>> = Map::new("claimed_farm_rewards");
+
+/// The lifetime rewards an address claimed, across all farms. Key is a tuple of (address, denom),
+/// value is the claimed amount.
+pub const CLAIMED_REWARDS: Map<(&Addr, &str), Uint128> = Map::new("claimed_rewards");
+
+/// The periods the farms were paused for, in chronological order. Key is the farm identifier.
+pub const FARM_PAUSES: Map<&str, Vec<FarmPause>> = Map::new("farm_pauses");
+
+/// The farms evicted by the owner, which stay around until they expire so the rewards they accrued
+/// can be claimed, but don't take up a slot of their LP denom. Key is the farm identifier, value is
+/// the epoch the farm was evicted on.
+pub const EVICTED_FARMS: Map<&str, EpochId> = Map::new("evicted_farms");
+
+/// The last epoch the gauge emissions were distributed.
+pub const LAST_GAUGE_DISTRIBUTION_EPOCH: Item<EpochId> = Item::new("last_gauge_distribution_epoch");
+
 // settings for pagination
 
-// The maximum number of positions that a user can have open or closed at a given time, i.e. there
-// can be at most MAX_POSITIONS_LIMIT open positions and MAX_POSITIONS_LIMIT closed positions.
+// The maximum number of positions that can be queried at a given time.
 pub const MAX_POSITIONS_LIMIT: u32 = 10;
 // The maximum number of farms that can be queried at a given time.
 pub const MAX_FARMS_LIMIT: u32 = 100;
+// The maximum number of claim operators that can be queried at a given time.
+pub const MAX_CLAIM_OPERATORS_LIMIT: u32 = 30;
+// The maximum number of stakeable denoms that can be queried at a given time.
+pub const MAX_STAKEABLE_DENOMS_LIMIT: u32 = 30;
+// The maximum number of farm assets that can be queried at a given time.
+pub const MAX_FARM_ASSETS_LIMIT: u32 = 30;
+// The maximum number of epochs the emissions of a farm can be queried for at a given time.
+pub const MAX_FARM_EMISSIONS_LIMIT: u64 = 100;
+// The maximum number of lp weight history entries that can be queried at a given time.
+pub const MAX_LP_WEIGHT_HISTORY_LIMIT: u32 = 100;
+// The maximum number of farms the claim history can be queried for at a given time.
+pub const MAX_CLAIM_HISTORY_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
 
 /// Gets the farms in the contract
@@ -167,6 +298,24 @@ pub fn get_farm_by_identifier(
         .ok_or(ContractError::NonExistentFarm)
 }
 
+/// Gets the periods the given farm was paused for.
+pub fn get_farm_pauses(storage: &dyn Storage, farm_identifier: &str) -> StdResult<Vec<FarmPause>> {
+    Ok(FARM_PAUSES
+        .may_load(storage, farm_identifier)?
+        .unwrap_or_default())
+}
+
+/// Gets the epoch the given farm is paused from, if it's currently paused.
+pub fn get_farm_paused_from(
+    storage: &dyn Storage,
+    farm_identifier: &str,
+) -> StdResult<Option<EpochId>> {
+    Ok(get_farm_pauses(storage, farm_identifier)?
+        .last()
+        .filter(|pause| pause.resumed_from.is_none())
+        .map(|pause| pause.paused_from))
+}
+
 /// Gets positions
 pub(crate) fn get_positions(
     storage: &dyn Storage,
@@ -225,9 +374,6 @@ pub fn get_positions_by_receiver(
 
     let positions_by_receiver = index
         .range(storage, start, None, Order::Ascending)
-        // take only the first `limit` positions. If filtering by open state, it means the user
-        // at most have MAX_POSITION_LIMIT open and MAX_POSITION_LIMIT close positions, as they
-        // are validated when creating/closing a position.
         .take(limit)
         .map(|item| {
             let (_, position) = item?;
@@ -238,6 +384,119 @@ pub fn get_positions_by_receiver(
     Ok(positions_by_receiver)
 }
 
+/// Gets the LP denoms the given address has open positions for.
+pub fn get_address_lp_denoms(storage: &dyn Storage, address: &Addr) -> StdResult<Vec<String>> {
+    ADDRESS_OPEN_POSITIONS
+        .prefix(address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Gets the claim operators approved by the given address, including the expired ones.
+pub fn get_claim_operators(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClaimOperator>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_CLAIM_OPERATORS_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    CLAIM_OPERATORS
+        .prefix(owner)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (operator, approval) = item?;
+
+            Ok(ClaimOperator {
+                operator: operator.to_string(),
+                expires: approval.expires,
+                receiver: approval.receiver.map(|receiver| receiver.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Gets the non-LP denoms allowed to be staked.
+pub fn get_stakeable_denoms(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_STAKEABLE_DENOMS_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    STAKEABLE_DENOMS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+/// Gets the denoms allowed to be used as farm assets, with the minimum amount a farm has to be
+/// created with.
+pub fn get_farm_assets(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Coin>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_FARM_ASSETS_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    FARM_ASSETS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (denom, min_amount) = item?;
+            Ok(Coin {
+                denom,
+                amount: min_amount,
+            })
+        })
+        .collect()
+}
+
+/// Gets the lp weight history of an address for the given lp denom, in ascending epoch order.
+pub fn get_lp_weight_history(
+    storage: &dyn Storage,
+    address: &Addr,
+    lp_denom: &str,
+    start_after: Option<EpochId>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(EpochId, Uint128)>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_LP_WEIGHT_HISTORY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    LP_WEIGHT_HISTORY
+        .prefix((address, lp_denom))
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+/// Gets the lifetime rewards an address claimed from each farm, by farm identifier.
+pub fn get_claimed_farm_rewards(
+    storage: &dyn Storage,
+    address: &Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Vec<Coin>)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_CLAIM_HISTORY_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    CLAIMED_FARM_REWARDS
+        .prefix(address)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
 /// Gets the earliest entry of an address in the address lp weight history.
 /// If the address has no open positions, it returns an error.
 pub fn get_earliest_address_lp_weight(
@@ -297,3 +556,25 @@ pub fn get_latest_address_lp_weight(
         Err(std_err) => Err(std_err.into()),
     }
 }
+
+/// Gets the lp weight an address has for the given lp denom on the given epoch, i.e. the latest
+/// snapshot taken on or before that epoch. Snapshots for future epochs are ignored.
+pub fn get_address_lp_weight_at_epoch(
+    storage: &dyn Storage,
+    address: &Addr,
+    lp_denom: &str,
+    epoch_id: EpochId,
+) -> Result<Uint128, ContractError> {
+    Ok(LP_WEIGHT_HISTORY
+        .prefix((address, lp_denom))
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(epoch_id)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, weight)| weight)
+        .unwrap_or_default())
+}
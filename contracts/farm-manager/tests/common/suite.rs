@@ -1,11 +1,13 @@
-use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::testing::{mock_env, MockStorage};
 use cosmwasm_std::{
-    coin, Addr, BankMsg, Coin, Decimal, Empty, StdResult, Timestamp, Uint128, Uint64,
+    coin, Addr, BankMsg, Coin, CosmosMsg, Decimal, Empty, StakingMsg, StdResult, Timestamp,
+    Uint128, Uint64, Validator,
 };
 use cw_multi_test::{
     App, AppBuilder, AppResponse, BankKeeper, DistributionKeeper, Executor, FailingModule,
-    GovFailingModule, IbcFailingModule, MockApiBech32, StakeKeeper, WasmKeeper,
+    GovFailingModule, IbcFailingModule, MockApiBech32, StakeKeeper, StakingInfo, WasmKeeper,
 };
+use cw_utils::Expiration;
 use mantra_dex_std::constants::MONTH_IN_SECONDS;
 
 use crate::common::suite_contracts::{
@@ -15,8 +17,11 @@ use crate::common::MOCK_CONTRACT_ADDR_1;
 use mantra_common_testing::multi_test::stargate_mock::StargateMock;
 use mantra_dex_std::epoch_manager::{EpochConfig, EpochResponse};
 use mantra_dex_std::farm_manager::{
-    Config, FarmAction, FarmsBy, FarmsResponse, InstantiateMsg, LpWeightResponse, PositionAction,
-    PositionsResponse, RewardsResponse,
+    BoostConfig, BoostConfigResponse, ClaimHistoryResponse, ClaimOperatorsResponse, Config,
+    DetailedRewardsResponse, EpochId, FarmAction, FarmAprResponse, FarmAssetsResponse,
+    FarmEmissionsResponse, FarmsBy, FarmsResponse, GaugeResponse, GaugeVote, GaugeVotesResponse,
+    InstantiateMsg, LpWeightHistoryResponse, LpWeightResponse, PenaltySplit, PositionAction,
+    PositionsResponse, RewardsResponse, StakeableDenomsResponse,
 };
 
 type OsmosisTokenFactoryApp = App<
@@ -123,10 +128,37 @@ impl TestingSuite {
             .with_wasm(WasmKeeper::default())
             .with_bank(bank)
             .with_stargate(StargateMock::new(vec![coin(8888u128, "uom".to_string())]))
-            .build(|router, _api, storage| {
+            .build(|router, api, storage| {
                 balances.into_iter().for_each(|(account, amount)| {
                     router.bank.init_balance(storage, &account, amount).unwrap()
                 });
+
+                router
+                    .staking
+                    .setup(
+                        storage,
+                        StakingInfo {
+                            bonded_denom: "uom".to_string(),
+                            unbonding_time: 1_814_400,
+                            apr: Decimal::percent(10),
+                        },
+                    )
+                    .unwrap();
+
+                router
+                    .staking
+                    .add_validator(
+                        api,
+                        storage,
+                        &mock_env().block,
+                        Validator::create(
+                            api.addr_make("validator").to_string(),
+                            Decimal::zero(),
+                            Decimal::one(),
+                            Decimal::one(),
+                        ),
+                    )
+                    .unwrap();
             });
 
         Self {
@@ -389,6 +421,7 @@ impl TestingSuite {
         max_unlocking_duration: Option<u64>,
         farm_expiration_time: Option<u64>,
         emergency_unlock_penalty: Option<Decimal>,
+        penalty_split: Option<PenaltySplit>,
         funds: Vec<Coin>,
         result: impl Fn(Result<AppResponse, anyhow::Error>),
     ) -> &mut Self {
@@ -403,6 +436,7 @@ impl TestingSuite {
             max_unlocking_duration,
             farm_expiration_time,
             emergency_unlock_penalty,
+            penalty_split,
         };
 
         result(self.app.execute_contract(
@@ -463,7 +497,292 @@ impl TestingSuite {
         until_epoch: Option<u64>,
         result: impl Fn(Result<AppResponse, anyhow::Error>),
     ) -> &mut Self {
-        let msg = mantra_dex_std::farm_manager::ExecuteMsg::Claim { until_epoch };
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::Claim {
+            until_epoch,
+            lp_denoms: None,
+            farm_identifiers: None,
+            owner: None,
+            receiver: None,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &funds,
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn claim_partially(
+        &mut self,
+        sender: &Addr,
+        until_epoch: Option<u64>,
+        lp_denoms: Option<Vec<String>>,
+        farm_identifiers: Option<Vec<String>>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::Claim {
+            until_epoch,
+            lp_denoms,
+            farm_identifiers,
+            owner: None,
+            receiver: None,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn claim_on_behalf(
+        &mut self,
+        sender: &Addr,
+        owner: &Addr,
+        receiver: Option<&Addr>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::Claim {
+            until_epoch: None,
+            lp_denoms: None,
+            farm_identifiers: None,
+            owner: Some(owner.to_string()),
+            receiver: receiver.map(|receiver| receiver.to_string()),
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn approve_claim_operator(
+        &mut self,
+        sender: &Addr,
+        operator: &Addr,
+        expires: Expiration,
+        receiver: Option<&Addr>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::ApproveClaimOperator {
+            operator: operator.to_string(),
+            expires,
+            receiver: receiver.map(|receiver| receiver.to_string()),
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn prune_history(
+        &mut self,
+        sender: &Addr,
+        address: &Addr,
+        limit: Option<u32>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::PruneHistory {
+            address: address.to_string(),
+            limit,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn revoke_claim_operator(
+        &mut self,
+        sender: &Addr,
+        operator: &Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::RevokeClaimOperator {
+            operator: operator.to_string(),
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn update_stakeable_denoms(
+        &mut self,
+        sender: &Addr,
+        add: Vec<String>,
+        remove: Vec<String>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::UpdateStakeableDenoms { add, remove };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn update_farm_assets(
+        &mut self,
+        sender: &Addr,
+        add: Vec<Coin>,
+        remove: Vec<String>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::UpdateFarmAssets { add, remove };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn evict_farms(
+        &mut self,
+        sender: &Addr,
+        farm_identifiers: Vec<String>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::EvictFarms { farm_identifiers };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn update_boost_config(
+        &mut self,
+        sender: &Addr,
+        boost_config: Option<BoostConfig>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::UpdateBoostConfig { boost_config };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn refresh_boost(
+        &mut self,
+        sender: &Addr,
+        address: &Addr,
+        lp_denom: &str,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::RefreshBoost {
+            address: address.to_string(),
+            lp_denom: lp_denom.to_string(),
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    /// Delegates the given amount to the mock validator.
+    #[track_caller]
+    pub(crate) fn delegate(
+        &mut self,
+        sender: &Addr,
+        amount: Coin,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: self.app.api().addr_make("validator").to_string(),
+            amount,
+        });
+
+        result(self.app.execute(sender.clone(), msg));
+
+        self
+    }
+
+    /// Undelegates the given amount from the mock validator.
+    #[track_caller]
+    pub(crate) fn undelegate(
+        &mut self,
+        sender: &Addr,
+        amount: Coin,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = CosmosMsg::Staking(StakingMsg::Undelegate {
+            validator: self.app.api().addr_make("validator").to_string(),
+            amount,
+        });
+
+        result(self.app.execute(sender.clone(), msg));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn fund_gauge(
+        &mut self,
+        sender: &Addr,
+        emission_per_epoch: Uint128,
+        funds: Vec<Coin>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::FundGauge { emission_per_epoch };
 
         result(self.app.execute_contract(
             sender.clone(),
@@ -474,6 +793,43 @@ impl TestingSuite {
 
         self
     }
+
+    #[track_caller]
+    pub(crate) fn gauge_vote(
+        &mut self,
+        sender: &Addr,
+        votes: Vec<GaugeVote>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::GaugeVote { votes };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn distribute_gauges(
+        &mut self,
+        sender: &Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::farm_manager::ExecuteMsg::DistributeGauges {};
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
 }
 
 /// queries
@@ -562,6 +918,8 @@ impl TestingSuite {
             &mantra_dex_std::farm_manager::QueryMsg::Rewards {
                 address: address.to_string(),
                 until_epoch,
+                lp_denom: None,
+                farm_identifier: None,
             },
         );
 
@@ -570,6 +928,53 @@ impl TestingSuite {
         self
     }
 
+    #[track_caller]
+    pub(crate) fn query_filtered_rewards(
+        &mut self,
+        address: &Addr,
+        lp_denom: Option<&str>,
+        farm_identifier: Option<&str>,
+        result: impl Fn(StdResult<RewardsResponse>),
+    ) -> &mut Self {
+        let rewards_response: StdResult<RewardsResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::Rewards {
+                address: address.to_string(),
+                until_epoch: None,
+                lp_denom: lp_denom.map(str::to_string),
+                farm_identifier: farm_identifier.map(str::to_string),
+            },
+        );
+
+        result(rewards_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_detailed_rewards(
+        &mut self,
+        address: &Addr,
+        lp_denom: Option<&str>,
+        farm_identifier: Option<&str>,
+        result: impl Fn(StdResult<DetailedRewardsResponse>),
+    ) -> &mut Self {
+        let detailed_rewards_response: StdResult<DetailedRewardsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::DetailedRewards {
+                    address: address.to_string(),
+                    until_epoch: None,
+                    lp_denom: lp_denom.map(str::to_string),
+                    farm_identifier: farm_identifier.map(str::to_string),
+                },
+            );
+
+        result(detailed_rewards_response);
+
+        self
+    }
+
     #[track_caller]
     pub(crate) fn query_lp_weight(
         &mut self,
@@ -592,6 +997,222 @@ impl TestingSuite {
         self
     }
 
+    #[track_caller]
+    pub(crate) fn query_total_lp_weight(
+        &mut self,
+        denom: &str,
+        epoch_id: u64,
+        result: impl Fn(StdResult<LpWeightResponse>),
+    ) -> &mut Self {
+        let total_lp_weight_response: StdResult<LpWeightResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::TotalLpWeight {
+                    denom: denom.to_string(),
+                    epoch_id,
+                },
+            );
+
+        result(total_lp_weight_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_lp_weight_history(
+        &mut self,
+        address: &Addr,
+        denom: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<LpWeightHistoryResponse>),
+    ) -> &mut Self {
+        let lp_weight_history_response: StdResult<LpWeightHistoryResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::LpWeightHistory {
+                    address: address.to_string(),
+                    denom: denom.to_string(),
+                    start_after,
+                    limit,
+                },
+            );
+
+        result(lp_weight_history_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_gauge(&mut self, result: impl Fn(StdResult<GaugeResponse>)) -> &mut Self {
+        let gauge_response: StdResult<GaugeResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::Gauge {},
+        );
+
+        result(gauge_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_gauge_votes(
+        &mut self,
+        epoch_id: u64,
+        voter: Option<&Addr>,
+        result: impl Fn(StdResult<GaugeVotesResponse>),
+    ) -> &mut Self {
+        let gauge_votes_response: StdResult<GaugeVotesResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::GaugeVotes {
+                epoch_id,
+                voter: voter.map(|voter| voter.to_string()),
+            },
+        );
+
+        result(gauge_votes_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_claim_operators(
+        &mut self,
+        address: &Addr,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<ClaimOperatorsResponse>),
+    ) -> &mut Self {
+        let claim_operators_response: StdResult<ClaimOperatorsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::ClaimOperators {
+                    address: address.to_string(),
+                    start_after: start_after.map(|operator| operator.to_string()),
+                    limit,
+                },
+            );
+
+        result(claim_operators_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_claim_history(
+        &mut self,
+        address: &Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<ClaimHistoryResponse>),
+    ) -> &mut Self {
+        let claim_history_response: StdResult<ClaimHistoryResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::ClaimHistory {
+                    address: address.to_string(),
+                    start_after,
+                    limit,
+                },
+            );
+
+        result(claim_history_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_farm_assets(
+        &mut self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<FarmAssetsResponse>),
+    ) -> &mut Self {
+        let farm_assets_response: StdResult<FarmAssetsResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::FarmAssets { start_after, limit },
+        );
+
+        result(farm_assets_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_boost_config(
+        &mut self,
+        result: impl Fn(StdResult<BoostConfigResponse>),
+    ) -> &mut Self {
+        let boost_config_response: StdResult<BoostConfigResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::BoostConfig {},
+            );
+
+        result(boost_config_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_stakeable_denoms(
+        &mut self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<StakeableDenomsResponse>),
+    ) -> &mut Self {
+        let stakeable_denoms_response: StdResult<StakeableDenomsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::StakeableDenoms { start_after, limit },
+            );
+
+        result(stakeable_denoms_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_farm_emissions(
+        &mut self,
+        farm_identifier: &str,
+        from_epoch: Option<EpochId>,
+        to_epoch: Option<EpochId>,
+        result: impl Fn(StdResult<FarmEmissionsResponse>),
+    ) -> &mut Self {
+        let farm_emissions_response: StdResult<FarmEmissionsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.farm_manager_addr,
+                &mantra_dex_std::farm_manager::QueryMsg::FarmEmissions {
+                    farm_identifier: farm_identifier.to_string(),
+                    from_epoch,
+                    to_epoch,
+                },
+            );
+
+        result(farm_emissions_response);
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn query_farm_apr(
+        &mut self,
+        lp_denom: &str,
+        result: impl Fn(StdResult<FarmAprResponse>),
+    ) -> &mut Self {
+        let farm_apr_response: StdResult<FarmAprResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::FarmApr {
+                lp_denom: lp_denom.to_string(),
+            },
+        );
+
+        result(farm_apr_response);
+
+        self
+    }
+
     #[track_caller]
     pub(crate) fn query_balance(
         &mut self,
@@ -1,7 +1,8 @@
-use cosmwasm_std::{coin, Coin, Uint128};
+use cosmwasm_std::{coin, Coin, Decimal, Uint128};
+use farm_manager::ContractError;
 use mantra_dex_std::constants::LP_SYMBOL;
 use mantra_dex_std::farm_manager::{
-    FarmAction, FarmParams, Position, PositionAction, PositionsBy, RewardsResponse,
+    FarmAction, FarmParams, PenaltySplit, Position, PositionAction, PositionsBy, RewardsResponse,
 };
 use test_utils::common_constants::{
     DEFAULT_UNLOCKING_DURATION_SECONDS, DENOM_UOM, DENOM_UOSMO, DENOM_UUSDY, INITIAL_BALANCE,
@@ -1620,3 +1621,138 @@ fn test_managing_positions_close_and_emergency_withdraw() {
             }
         });
 }
+
+#[test]
+fn emergency_withdrawal_penalty_is_redistributed_among_remaining_lps() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM.to_string()),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    let fee_collector = suite.fee_collector_addr.clone();
+
+    // the split must add up to 100%
+    suite
+        .update_config(
+            &alice,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(PenaltySplit {
+                farm_owners: Decimal::zero(),
+                fee_collector: Decimal::percent(50),
+                redistribution: Decimal::percent(60),
+            }),
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidPenaltySplit => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidPenaltySplit")
+                    }
+                }
+            },
+        )
+        .update_config(
+            &alice,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(PenaltySplit {
+                farm_owners: Decimal::zero(),
+                fee_collector: Decimal::percent(50),
+                redistribution: Decimal::percent(50),
+            }),
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("alice_position".to_string()),
+                unlocking_duration: DEFAULT_UNLOCKING_DURATION_SECONDS,
+                receiver: None,
+            },
+            vec![coin(ONE_THOUSAND, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &bob,
+            PositionAction::Create {
+                identifier: Some("bob_position".to_string()),
+                unlocking_duration: DEFAULT_UNLOCKING_DURATION_SECONDS,
+                receiver: None,
+            },
+            vec![coin(ONE_THOUSAND, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    suite.add_one_epoch();
+
+    // the penalty is 10% of the position, half goes to the fee collector and the other half is
+    // redistributed among the remaining LPs, i.e. bob
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Withdraw {
+                identifier: "u-alice_position".to_string(),
+                emergency_unlock: Some(true),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(lp_denom.clone(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE - 100));
+        })
+        .query_balance(lp_denom.clone(), &fee_collector, |balance| {
+            assert_eq!(balance, Uint128::new(50));
+        })
+        .query_farms(None, None, None, |result| {
+            let farms_response = result.unwrap();
+            assert_eq!(farms_response.farms.len(), 1);
+            assert!(farms_response.farms[0].identifier.starts_with("r-"));
+            assert_eq!(farms_response.farms[0].farm_asset, coin(50, &lp_denom));
+        });
+
+    suite.add_one_epoch();
+
+    suite
+        .claim(&bob, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(lp_denom.clone(), &bob, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE - ONE_THOUSAND + 50));
+        });
+}
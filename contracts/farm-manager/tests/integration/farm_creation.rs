@@ -3,7 +3,7 @@ extern crate core;
 use cosmwasm_std::{coin, Coin, Uint128};
 use farm_manager::ContractError;
 use mantra_dex_std::constants::LP_SYMBOL;
-use mantra_dex_std::farm_manager::{Curve, Farm, FarmAction, FarmParams, FarmsBy};
+use mantra_dex_std::farm_manager::{Curve, Farm, FarmAction, FarmParams, FarmsBy, PositionAction};
 
 use crate::common::suite::TestingSuite;
 use crate::common::{MOCK_CONTRACT_ADDR_1, MOCK_CONTRACT_ADDR_2};
@@ -870,3 +870,298 @@ fn test_create_farm_resolves_dos_issue() {
         assert_eq!(m_raj_farm.owner, user2);
     });
 }
+
+#[test]
+fn farm_assets_allowlist_and_eviction() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM.to_string()),
+        coin(INITIAL_BALANCE, DENOM_UUSDY.to_string()),
+        coin(INITIAL_BALANCE, DENOM_UOSMO.to_string()),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    let farm_params = |denom: &str, amount: u128| FarmParams {
+        lp_denom: lp_denom.clone(),
+        start_epoch: Some(START_EPOCH_12),
+        preliminary_end_epoch: Some(END_EPOCH_16),
+        curve: None,
+        farm_asset: coin(amount, denom),
+        farm_identifier: None,
+    };
+
+    // any farm asset can be used while the allowlist is empty
+    suite
+        .query_farm_assets(None, None, |result| {
+            assert!(result.unwrap().assets.is_empty());
+        })
+        .manage_farm(
+            &other,
+            FarmAction::Create {
+                params: farm_params(DENOM_UOSMO, FARM_ASSET_AMOUNT_4K),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UOSMO),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .update_farm_assets(
+            &other,
+            vec![coin(FARM_ASSET_AMOUNT_8K, DENOM_UUSDY)],
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .update_farm_assets(
+            &creator,
+            vec![coin(FARM_ASSET_AMOUNT_8K, DENOM_UUSDY)],
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_farm_assets(None, None, |result| {
+            assert_eq!(
+                result.unwrap().assets,
+                vec![coin(FARM_ASSET_AMOUNT_8K, DENOM_UUSDY)]
+            );
+        })
+        .manage_farm(
+            &other,
+            FarmAction::Create {
+                params: farm_params(DENOM_UOSMO, FARM_ASSET_AMOUNT_4K),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UOSMO),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmAssetNotAllowed { denom } => {
+                        assert_eq!(denom, DENOM_UOSMO);
+                    }
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::FarmAssetNotAllowed")
+                    }
+                }
+            },
+        )
+        .manage_farm(
+            &other,
+            FarmAction::Create {
+                params: farm_params(DENOM_UUSDY, FARM_ASSET_AMOUNT_4K),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidFarmAmount { min } => {
+                        assert_eq!(min, FARM_ASSET_AMOUNT_8K);
+                    }
+                    _ => panic!("Wrong error type, should return ContractError::InvalidFarmAmount"),
+                }
+            },
+        )
+        .manage_farm(
+            &other,
+            FarmAction::Create {
+                params: farm_params(DENOM_UUSDY, FARM_ASSET_AMOUNT_10K),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_10K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the owner evicts the farm created before the allowlist, refunding its creator
+    suite
+        .evict_farms(&other, vec!["f-1".to_string()], |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::OwnershipError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+            }
+        })
+        .evict_farms(&creator, vec!["f-1".to_string()], |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UOSMO.to_string(), &other, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE));
+        })
+        .query_farms(None, None, None, |result| {
+            let farms_response = result.unwrap();
+            assert_eq!(farms_response.farms.len(), 1);
+            assert_eq!(farms_response.farms[0].identifier, "f-2");
+        });
+
+    // removing the denom from the allowlist opens farm creation up again
+    suite
+        .update_farm_assets(&creator, vec![], vec![DENOM_UUSDY.to_string()], |result| {
+            result.unwrap();
+        })
+        .query_farm_assets(None, None, |result| {
+            assert!(result.unwrap().assets.is_empty());
+        });
+}
+
+#[test]
+fn evicted_farms_keep_the_accrued_rewards_claimable() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM.to_string()),
+        coin(INITIAL_BALANCE, DENOM_UUSDY.to_string()),
+        coin(INITIAL_BALANCE, DENOM_UOSMO.to_string()),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+    let alice = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    let farm_params = |denom: &str, start_epoch: u64, identifier: &str| FarmParams {
+        lp_denom: lp_denom.clone(),
+        start_epoch: Some(start_epoch),
+        preliminary_end_epoch: Some(END_EPOCH_16),
+        curve: None,
+        farm_asset: coin(FARM_ASSET_AMOUNT_4K, denom),
+        farm_identifier: Some(identifier.to_string()),
+    };
+
+    suite
+        .manage_farm(
+            &other,
+            FarmAction::Create {
+                params: farm_params(DENOM_UOSMO, START_EPOCH_12, "spam"),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UOSMO),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: farm_params(DENOM_UUSDY, START_EPOCH_12, "legit"),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the spam farm emits on epoch 12, then it's evicted
+    suite
+        .add_epochs(2)
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: farm_params(DENOM_UUSDY, 13, "new"),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::TooManyFarms { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::TooManyFarms"),
+                }
+            },
+        )
+        // the duplicated identifier is only evicted once
+        .evict_farms(
+            &creator,
+            vec!["m-spam".to_string(), "m-spam".to_string()],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(DENOM_UOSMO.to_string(), &other, |balance| {
+            assert_eq!(
+                balance,
+                Uint128::new(INITIAL_BALANCE - FARM_ASSET_AMOUNT_4K + 3_000)
+            );
+        })
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-spam".to_string(),
+                asset: coin(ONE_THOUSAND, DENOM_UOSMO),
+            },
+            vec![coin(ONE_THOUSAND, DENOM_UOSMO)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmEvicted => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmEvicted"),
+                }
+            },
+        )
+        // the evicted farm doesn't take up a slot anymore
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: farm_params(DENOM_UUSDY, 13, "new"),
+            },
+            vec![
+                coin(FARM_ASSET_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the rewards accrued before the eviction can still be claimed
+    suite
+        .add_one_epoch()
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UOSMO.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE + ONE_THOUSAND));
+        });
+}
@@ -4,7 +4,9 @@ use cosmwasm_std::{coin, coins, Coin, Uint128};
 use cw_utils::PaymentError;
 use farm_manager::ContractError;
 use mantra_dex_std::constants::LP_SYMBOL;
-use mantra_dex_std::farm_manager::{FarmAction, FarmParams, FarmsBy, PositionAction};
+use mantra_dex_std::farm_manager::{
+    Curve, FarmAction, FarmParams, FarmsBy, PositionAction, MIN_FARM_AMOUNT,
+};
 
 use crate::common::suite::TestingSuite;
 use crate::common::{MOCK_CONTRACT_ADDR_1, MOCK_CONTRACT_ADDR_2};
@@ -2005,3 +2007,542 @@ fn test_create_vs_expand_actions() {
         },
     );
 }
+
+#[test]
+fn pause_resume_and_cancel_farms() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, DENOM_UOSMO),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: START_EPOCH_12,
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                    farm_identifier: Some("paused".to_string()),
+                },
+            },
+            vec![
+                coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: START_EPOCH_12,
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(FARM_AMOUNT_4K, DENOM_UOSMO),
+                    farm_identifier: Some("cancelled".to_string()),
+                },
+            },
+            vec![
+                coin(FARM_AMOUNT_4K, DENOM_UOSMO),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the farm emits on epoch 12 and is paused from epoch 13
+    suite
+        .add_epochs(2)
+        .manage_farm(
+            &alice,
+            FarmAction::Pause {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Resume {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmNotPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmNotPaused"),
+                }
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Pause {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Pause {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmPaused"),
+                }
+            },
+        );
+
+    // the other farm emits on epochs 12 and 13, the emissions of epochs 14 and 15 are refunded
+    suite
+        .add_one_epoch()
+        .manage_farm(
+            &creator,
+            FarmAction::Cancel {
+                farm_identifier: "m-cancelled".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(DENOM_UOSMO.to_string(), &creator, |balance| {
+            assert_eq!(
+                balance,
+                Uint128::new(INITIAL_BALANCE - FARM_AMOUNT_4K + FARM_AMOUNT_2K)
+            );
+        })
+        .manage_farm(
+            &creator,
+            FarmAction::Cancel {
+                farm_identifier: "m-cancelled".to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmNotEmitting => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmNotEmitting"),
+                }
+            },
+        );
+
+    // the farm resumes on epoch 15, after being paused for two epochs
+    suite
+        .add_one_epoch()
+        .manage_farm(
+            &creator,
+            FarmAction::Resume {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_farms(
+            Some(FarmsBy::Identifier("m-paused".to_string())),
+            None,
+            None,
+            |result| {
+                let farms_response = result.unwrap();
+                assert_eq!(farms_response.farms[0].preliminary_end_epoch, 18);
+                assert_eq!(
+                    farms_response.farms[0].farm_asset,
+                    coin(FARM_AMOUNT_4K, DENOM_UUSDY)
+                );
+                assert_eq!(farms_response.farms[0].curve, Curve::Linear);
+            },
+        )
+        .query_farm_emissions("m-paused", None, None, |result| {
+            let emissions = result
+                .unwrap()
+                .emissions
+                .into_iter()
+                .map(|(epoch_id, emission)| (epoch_id, emission.amount.u128()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                emissions,
+                vec![
+                    (12, 1_000),
+                    (13, 0),
+                    (14, 0),
+                    (15, 1_000),
+                    (16, 1_000),
+                    (17, 1_000)
+                ]
+            );
+        })
+        // the resumed farm keeps its curve, so it can still be topped up
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-paused".to_string(),
+                asset: coin(ONE_THOUSAND, DENOM_UUSDY),
+            },
+            vec![coin(ONE_THOUSAND, DENOM_UUSDY)],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the accrued rewards of both farms are claimable
+    suite
+        .add_epochs(5)
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE + FARM_AMOUNT_4K));
+        })
+        .query_balance(DENOM_UOSMO.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE + FARM_AMOUNT_2K));
+        });
+}
+
+#[test]
+fn paused_farms_expire_if_not_resumed() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: START_EPOCH_12,
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                    farm_identifier: Some("paused".to_string()),
+                },
+            },
+            vec![
+                coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_epochs(2)
+        .manage_farm(
+            &creator,
+            FarmAction::Pause {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the farm can't be topped up while paused
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-paused".to_string(),
+                asset: coin(ONE_THOUSAND, DENOM_UUSDY),
+            },
+            vec![coin(ONE_THOUSAND, DENOM_UUSDY)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmPaused => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmPaused"),
+                }
+            },
+        );
+
+    // the farm expires a month after the epoch it was paused from
+    suite
+        .add_epochs(33)
+        .manage_farm(
+            &creator,
+            FarmAction::Resume {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmAlreadyExpired => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::FarmAlreadyExpired")
+                    }
+                }
+            },
+        )
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE + ONE_THOUSAND));
+        })
+        .manage_farm(
+            &creator,
+            FarmAction::Close {
+                farm_identifier: "m-paused".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(DENOM_UUSDY.to_string(), &creator, |balance| {
+            assert_eq!(balance, Uint128::new(INITIAL_BALANCE - ONE_THOUSAND));
+        });
+}
+
+#[test]
+fn contribute_to_farms_and_split_refunds_pro_rata() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: START_EPOCH_12,
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                    farm_identifier: Some("sponsored".to_string()),
+                },
+            },
+            vec![
+                coin(FARM_AMOUNT_4K, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-sponsored".to_string(),
+                asset: coin(3_000, DENOM_UUSDY),
+            },
+            vec![coin(FARM_AMOUNT_2K, DENOM_UUSDY)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        // a farm emitting 100 per epoch, which could otherwise be topped up with 100
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: START_EPOCH_12,
+                    preliminary_end_epoch: Some(22),
+                    curve: None,
+                    farm_asset: coin(ONE_THOUSAND, DENOM_UUSDY),
+                    farm_identifier: Some("slow".to_string()),
+                },
+            },
+            vec![
+                coin(ONE_THOUSAND, DENOM_UUSDY),
+                coin(ONE_THOUSAND, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-slow".to_string(),
+                asset: coin(100, DENOM_UUSDY),
+            },
+            vec![coin(100, DENOM_UUSDY)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidFarmAmount { min } => {
+                        assert_eq!(min, MIN_FARM_AMOUNT.u128());
+                    }
+                    _ => panic!("Wrong error type, should return ContractError::InvalidFarmAmount"),
+                }
+            },
+        )
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-sponsored".to_string(),
+                asset: coin(1_500, DENOM_UUSDY),
+            },
+            vec![coin(1_500, DENOM_UUSDY)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidExpansionAmount { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidExpansionAmount"
+                    ),
+                }
+            },
+        )
+        .manage_farm(
+            &alice,
+            FarmAction::Contribute {
+                farm_identifier: "m-sponsored".to_string(),
+                asset: coin(FARM_AMOUNT_2K, DENOM_UUSDY),
+            },
+            vec![coin(FARM_AMOUNT_2K, DENOM_UUSDY)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &bob,
+            FarmAction::Contribute {
+                farm_identifier: "m-sponsored".to_string(),
+                asset: coin(FARM_AMOUNT_2K, DENOM_UUSDY),
+            },
+            vec![coin(FARM_AMOUNT_2K, DENOM_UUSDY)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_farms(
+            Some(FarmsBy::Identifier("m-sponsored".to_string())),
+            None,
+            None,
+            |result| {
+                let farms_response = result.unwrap();
+                assert_eq!(farms_response.farms[0].preliminary_end_epoch, 20);
+                assert_eq!(
+                    farms_response.farms[0].farm_asset,
+                    coin(FARM_AMOUNT_8K, DENOM_UUSDY)
+                );
+            },
+        );
+
+    // the farm emits 2_000 on epochs 12 and 13, the remaining 6_000 are refunded pro-rata, i.e.
+    // half to the creator and a quarter to each contributor
+    suite
+        .add_epochs(3)
+        .manage_farm(
+            &creator,
+            FarmAction::Cancel {
+                farm_identifier: "m-sponsored".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(DENOM_UUSDY.to_string(), &creator, |balance| {
+            assert_eq!(
+                balance,
+                // the slow farm is still running
+                Uint128::new(INITIAL_BALANCE - FARM_AMOUNT_4K - ONE_THOUSAND + 3_000)
+            );
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &alice, |balance| {
+            assert_eq!(
+                balance,
+                Uint128::new(INITIAL_BALANCE - FARM_AMOUNT_2K + 1_500)
+            );
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &bob, |balance| {
+            assert_eq!(
+                balance,
+                Uint128::new(INITIAL_BALANCE - FARM_AMOUNT_2K + 1_500)
+            );
+        });
+}
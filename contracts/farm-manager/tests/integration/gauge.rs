@@ -0,0 +1,564 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+use farm_manager::ContractError;
+use mantra_dex_std::constants::LP_SYMBOL;
+use mantra_dex_std::farm_manager::{Curve, GaugeBudget, GaugeVote, PositionAction};
+
+use crate::common::suite::TestingSuite;
+use crate::common::MOCK_CONTRACT_ADDR_1;
+use test_utils::common_constants::ONE_BILLION;
+
+#[test]
+fn gauge_votes_direct_emissions_across_lp_denoms() {
+    let lp_denom_1 = format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}").to_string();
+    let lp_denom_2 = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, lp_denom_1.clone()),
+        coin(ONE_BILLION, lp_denom_2.clone()),
+        coin(ONE_BILLION, "invalid_lp"),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+    let carol = suite.senders[3].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .fund_gauge(
+            &alice,
+            Uint128::new(1_000),
+            vec![coin(10_000, "uusdy")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::OwnershipError(_) => {}
+                    _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+                }
+            },
+        )
+        .fund_gauge(
+            &creator,
+            Uint128::zero(),
+            vec![coin(10_000, "uusdy")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidGaugeEmission => {}
+                    _ => {
+                        panic!(
+                            "Wrong error type, should return ContractError::InvalidGaugeEmission"
+                        )
+                    }
+                }
+            },
+        )
+        .fund_gauge(
+            &creator,
+            Uint128::new(1_000),
+            vec![coin(10_000, "uusdy")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the budget denom can't be changed
+        .fund_gauge(
+            &creator,
+            Uint128::new(1_000),
+            vec![coin(10_000, "uom")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom_1.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the voting power is the share of the weight of each LP denom, so bob doesn't get more
+        // voting power for holding more units of a different LP denom
+        .manage_position(
+            &bob,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(50_000, lp_denom_2.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the positions opened on epoch 10 only get voting power on epoch 11
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: lp_denom_1.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoVotingPower => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoVotingPower"),
+                }
+            },
+        )
+        .add_one_epoch()
+        // carol has no positions, thus no voting power
+        .gauge_vote(
+            &carol,
+            vec![GaugeVote {
+                lp_denom: lp_denom_1.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoVotingPower => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoVotingPower"),
+                }
+            },
+        )
+        .gauge_vote(
+            &alice,
+            vec![
+                GaugeVote {
+                    lp_denom: lp_denom_1.clone(),
+                    weight: Decimal::percent(60),
+                },
+                GaugeVote {
+                    lp_denom: lp_denom_2.clone(),
+                    weight: Decimal::percent(60),
+                },
+            ],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidGaugeVotes { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidGaugeVotes"),
+                }
+            },
+        )
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: "invalid_lp".to_string(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: lp_denom_2.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // voting again replaces the previous votes
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: lp_denom_1.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .gauge_vote(
+            &bob,
+            vec![GaugeVote {
+                lp_denom: lp_denom_2.clone(),
+                weight: Decimal::percent(50),
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_gauge_votes(11, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![
+                    (lp_denom_1.clone(), Uint128::new(1_000_000_000_000_000_000)),
+                    (lp_denom_2.clone(), Uint128::new(500_000_000_000_000_000)),
+                ]
+            );
+        })
+        .query_gauge_votes(11, Some(&bob), |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![(lp_denom_2.clone(), Uint128::new(500_000_000_000_000_000))]
+            );
+        });
+
+    // the votes cast on epoch 11 direct the emissions of epoch 12
+    suite
+        .add_one_epoch()
+        .distribute_gauges(&carol, |result| {
+            result.unwrap();
+        })
+        // the emissions are distributed once per epoch
+        .distribute_gauges(&carol, |result| {
+            result.unwrap();
+        })
+        .query_gauge(|result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.budget,
+                Some(GaugeBudget {
+                    denom: "uusdy".to_string(),
+                    balance: Uint128::new(9_001),
+                    emission_per_epoch: Uint128::new(1_000),
+                })
+            );
+        })
+        .query_farms(None, None, None, |result| {
+            let farms = result.unwrap().farms;
+            assert_eq!(farms.len(), 2);
+
+            assert_eq!(farms[0].lp_denom, lp_denom_1);
+            assert_eq!(farms[0].farm_asset, coin(666, "uusdy"));
+            assert_eq!(
+                farms[0].curve,
+                Curve::Step {
+                    schedule: vec![(12, Uint128::new(666))]
+                }
+            );
+            assert_eq!(farms[1].lp_denom, lp_denom_2);
+            assert_eq!(farms[1].farm_asset, coin(333, "uusdy"));
+        })
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .claim(&bob, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 666));
+        })
+        .query_balance("uusdy".to_string(), &bob, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 333));
+        });
+
+    // nobody voted on epoch 12, so nothing is distributed on epoch 13
+    suite
+        .add_one_epoch()
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 666));
+        })
+        .query_gauge(|result| {
+            let response = result.unwrap();
+            assert_eq!(response.budget.unwrap().balance, Uint128::new(9_001));
+        });
+}
+
+#[test]
+fn gauge_votes_are_rescaled_when_the_voting_power_goes_down() {
+    let lp_denom_1 = format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}").to_string();
+    let lp_denom_2 = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, lp_denom_1.clone()),
+        coin(ONE_BILLION, lp_denom_2.clone()),
+    ]);
+
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("alice".to_string()),
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(10_000, lp_denom_1.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .gauge_vote(
+            &alice,
+            vec![
+                GaugeVote {
+                    lp_denom: lp_denom_1.clone(),
+                    weight: Decimal::percent(50),
+                },
+                GaugeVote {
+                    lp_denom: lp_denom_2.clone(),
+                    weight: Decimal::percent(50),
+                },
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_gauge_votes(11, Some(&alice), |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![
+                    (lp_denom_1.clone(), Uint128::new(500_000_000_000_000_000)),
+                    (lp_denom_2.clone(), Uint128::new(500_000_000_000_000_000)),
+                ]
+            );
+        })
+        // opening a new position doesn't add voting power until the next epoch
+        .manage_position(
+            &alice,
+            PositionAction::Expand {
+                identifier: "u-alice".to_string(),
+            },
+            vec![coin(10_000, lp_denom_1.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_gauge_votes(11, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![
+                    (lp_denom_1.clone(), Uint128::new(500_000_000_000_000_000)),
+                    (lp_denom_2.clone(), Uint128::new(500_000_000_000_000_000)),
+                ]
+            );
+        })
+        // closing most of the position scales the votes down
+        .manage_position(
+            &alice,
+            PositionAction::Close {
+                identifier: "u-alice".to_string(),
+                lp_asset: Some(coin(16_000, lp_denom_1.clone())),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_gauge_votes(11, Some(&alice), |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![
+                    (lp_denom_1.clone(), Uint128::new(200_000_000_000_000_000)),
+                    (lp_denom_2.clone(), Uint128::new(200_000_000_000_000_000)),
+                ]
+            );
+        })
+        .query_gauge_votes(11, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.votes,
+                vec![
+                    (lp_denom_1.clone(), Uint128::new(200_000_000_000_000_000)),
+                    (lp_denom_2.clone(), Uint128::new(200_000_000_000_000_000)),
+                ]
+            );
+        });
+}
+
+#[test]
+fn gauge_votes_are_removed_when_transferring_the_position() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("alice".to_string()),
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(10_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: lp_denom.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Transfer {
+                identifier: "u-alice".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_gauge_votes(11, Some(&alice), |result| {
+            assert!(result.unwrap().votes.is_empty());
+        })
+        .query_gauge_votes(11, None, |result| {
+            assert!(result.unwrap().votes.is_empty());
+        })
+        // the new receiver only gets voting power from the next epoch
+        .gauge_vote(
+            &bob,
+            vec![GaugeVote {
+                lp_denom: lp_denom.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoVotingPower => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoVotingPower"),
+                }
+            },
+        );
+}
+
+#[test]
+fn gauge_emissions_are_distributed_before_closing_a_position() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .fund_gauge(
+            &creator,
+            Uint128::new(1_000),
+            vec![coin(10_000, "uusdy")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("alice".to_string()),
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(10_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .gauge_vote(
+            &alice,
+            vec![GaugeVote {
+                lp_denom: lp_denom.clone(),
+                weight: Decimal::one(),
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        // nobody distributed the emissions of epoch 12 yet, closing the position does it first so
+        // the gauge rewards are not skipped
+        .manage_position(
+            &alice,
+            PositionAction::Close {
+                identifier: "u-alice".to_string(),
+                lp_asset: None,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::PendingRewards => {}
+                    _ => panic!("Wrong error type, should return ContractError::PendingRewards"),
+                }
+            },
+        )
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 1_000));
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Close {
+                identifier: "u-alice".to_string(),
+                lp_asset: None,
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        );
+}
@@ -4,6 +4,7 @@ pub mod error_handling;
 pub mod expiration;
 pub mod farm_creation;
 pub mod farm_management;
+pub mod gauge;
 pub mod instantiation;
 pub mod ownership_and_config;
 pub mod position_management;
@@ -3,7 +3,7 @@ extern crate core;
 use cosmwasm_std::{coin, Addr, Coin, Decimal, Uint128};
 use farm_manager::ContractError;
 use mantra_dex_std::constants::{LP_SYMBOL, MONTH_IN_SECONDS};
-use mantra_dex_std::farm_manager::Config;
+use mantra_dex_std::farm_manager::{Config, PenaltySplit};
 
 use crate::common::suite::TestingSuite;
 use crate::common::MOCK_CONTRACT_ADDR_1;
@@ -108,6 +108,11 @@ pub fn update_config() {
         max_unlocking_duration: 31_556_926u64,
         farm_expiration_time: MONTH_IN_SECONDS,
         emergency_unlock_penalty: Decimal::percent(10),
+        penalty_split: PenaltySplit {
+            farm_owners: Decimal::percent(50),
+            fee_collector: Decimal::percent(50),
+            redistribution: Decimal::zero(),
+        },
     };
 
     suite.query_config(|result| {
@@ -129,6 +134,7 @@ pub fn update_config() {
             Some(864_000u64),
             Some(NEW_FARM_EXPIRATION_TIME),
             Some(Decimal::percent(50)),
+            None,
             vec![coin(1_000, DENOM_UOM)],
             |result| {
                 let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -152,6 +158,7 @@ pub fn update_config() {
         Some(864_000u64),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(50)),
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -175,6 +182,7 @@ pub fn update_config() {
         Some(864_000u64),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(50)),
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -198,6 +206,7 @@ pub fn update_config() {
         Some(80_000u64),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(50)),
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -221,6 +230,7 @@ pub fn update_config() {
         Some(200_000u64),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(50)),
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -244,6 +254,7 @@ pub fn update_config() {
         Some(NEW_MAX_UNLOCKING_DURATION),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(105)),
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -267,6 +278,7 @@ pub fn update_config() {
         Some(NEW_MAX_UNLOCKING_DURATION),
         Some(NEW_FARM_EXPIRATION_TIME),
         Some(Decimal::percent(NEW_EMERGENCY_UNLOCK_PENALTY_PERCENT)),
+        None,
         vec![],
         |result| {
             result.unwrap();
@@ -287,6 +299,11 @@ pub fn update_config() {
         max_unlocking_duration: NEW_MAX_UNLOCKING_DURATION,
         farm_expiration_time: NEW_FARM_EXPIRATION_TIME,
         emergency_unlock_penalty: Decimal::percent(NEW_EMERGENCY_UNLOCK_PENALTY_PERCENT),
+        penalty_split: PenaltySplit {
+            farm_owners: Decimal::percent(50),
+            fee_collector: Decimal::percent(50),
+            redistribution: Decimal::zero(),
+        },
     };
 
     suite.query_config(|result| {
@@ -306,6 +323,7 @@ pub fn update_config() {
         None,
         Some(MONTH_IN_SECONDS - 100),
         None,
+        None,
         vec![],
         |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
@@ -2,12 +2,12 @@ extern crate core;
 
 use std::cell::RefCell;
 
-use cosmwasm_std::{coin, Coin, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{coin, Coin, Decimal, StdResult, Timestamp, Uint128};
 use farm_manager::state::{MAX_FARMS_LIMIT, MAX_POSITIONS_LIMIT};
 use farm_manager::ContractError;
 use mantra_dex_std::constants::LP_SYMBOL;
 use mantra_dex_std::farm_manager::{
-    FarmAction, FarmParams, LpWeightResponse, Position, PositionAction, PositionsBy,
+    BoostConfig, FarmAction, FarmParams, LpWeightResponse, Position, PositionAction, PositionsBy,
     PositionsResponse, RewardsResponse,
 };
 
@@ -2195,9 +2195,9 @@ fn positions_can_handled_by_pool_manager_for_the_user() {
         );
 }
 
-/// creates a MAX_ITEMS_LIMIT number of positions and farms. A user will claim for all the farms.
-/// This shouldn't leave any unclaimed amount, as the user shouldn't be able to participate in more farms
-/// than what the rewards calculation function iterates over.
+/// creates more positions and farms than the positions query limit. A user will claim for all the farms.
+/// This shouldn't leave any unclaimed amount, as the rewards calculation iterates over all the LP denoms
+/// the user has open positions for, no matter how many positions the user has.
 #[test]
 fn test_positions_limits() {
     let mut balances = vec![
@@ -2218,8 +2218,10 @@ fn test_positions_limits() {
     let alice = suite.senders[1].clone();
     suite.instantiate_default();
 
-    // prepare farms, create more than the user could participate on
-    for i in 1..MAX_POSITIONS_LIMIT * 2 {
+    let positions = MAX_POSITIONS_LIMIT * 2;
+
+    // prepare farms, one for each of the positions alice is going to open
+    for i in 1..=positions {
         suite.manage_farm(
             &creator,
             FarmAction::Create {
@@ -2242,8 +2244,8 @@ fn test_positions_limits() {
         );
     }
 
-    // open positions
-    for i in 1..=MAX_POSITIONS_LIMIT {
+    // open more positions than the query limit, all of them are accounted for
+    for i in 1..=positions {
         suite.manage_position(
             &alice,
             PositionAction::Create {
@@ -2261,43 +2263,54 @@ fn test_positions_limits() {
         );
     }
 
-    suite.query_positions(
-        Some(PositionsBy::Receiver(alice.to_string())),
-        Some(true),
-        None,
-        Some(MAX_POSITIONS_LIMIT),
-        |result| {
-            let response = result.unwrap();
-            assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
-        },
-    );
-
-    // alice can't create additional positions, as it hit the limit on open positions
+    // alice can have several positions for the same LP denom too
     suite.manage_position(
         &alice,
         PositionAction::Create {
-            identifier: Some("aditional_position".to_string()),
+            identifier: Some("additional_position".to_string()),
             unlocking_duration: UNLOCKING_DURATION_1_DAY,
             receiver: None,
         },
         vec![coin(
             1_000,
-            format!("factory/{MOCK_CONTRACT_ADDR_1}/102.{LP_SYMBOL}"),
+            format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}"),
         )],
         |result| {
-            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
-            match err {
-                ContractError::MaxPositionsPerUserExceeded { .. } => {}
-                _ => panic!(
-                    "Wrong error type, should return ContractError::MaxPositionsPerUserExceeded"
-                ),
-            }
+            result.unwrap();
+        },
+    );
+
+    // the query is still paginated
+    suite.query_positions(
+        Some(PositionsBy::Receiver(alice.to_string())),
+        Some(true),
+        None,
+        Some(positions),
+        |result| {
+            let response = result.unwrap();
+            assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
         },
     );
 
     // move an epoch and claim
     suite
         .add_one_epoch()
+        .query_rewards(&alice, None, |result| {
+            let rewards_response = result.unwrap();
+            match rewards_response {
+                RewardsResponse::RewardsResponse {
+                    total_rewards,
+                    rewards_per_lp_denom,
+                } => {
+                    assert_eq!(rewards_per_lp_denom.len(), positions as usize);
+                    assert_eq!(
+                        total_rewards,
+                        vec![coin(1_000u128 * positions as u128, "DENOM_UUSDY")]
+                    );
+                }
+                _ => panic!("shouldn't return this but RewardsResponse"),
+            }
+        })
         .query_balance("DENOM_UUSDY".to_string(), &alice, |balance| {
             assert_eq!(balance, Uint128::new(ONE_BILLION));
         })
@@ -2305,22 +2318,12 @@ fn test_positions_limits() {
             result.unwrap();
         })
         .query_balance("DENOM_UUSDY".to_string(), &alice, |balance| {
-            // all the rewards were claimed, 1000 DENOM_UUSDY * 10
-            assert_eq!(balance, Uint128::new(1_000_010_000u128));
-        })
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(true),
-            None,
-            Some(MAX_POSITIONS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
-            },
-        );
+            // all the rewards were claimed, 1000 DENOM_UUSDY * 20
+            assert_eq!(balance, Uint128::new(1_000_020_000u128));
+        });
 
-    // now let's try closing positions
-    for i in 1..=MAX_POSITIONS_LIMIT {
+    // now let's close all the positions
+    for i in 1..=positions {
         suite.manage_position(
             &alice,
             PositionAction::Close {
@@ -2334,161 +2337,37 @@ fn test_positions_limits() {
         );
     }
 
-    // no open positions are left, instead there are MAX_ITEMS_LIMIT closed positions
+    // alice still has a position open for the first LP denom
     suite
         .query_positions(
             Some(PositionsBy::Receiver(alice.to_string())),
             Some(true),
             None,
-            Some(MAX_FARMS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert!(response.positions.is_empty());
-            },
-        )
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(false),
-            None,
-            Some(MAX_POSITIONS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
-            },
-        );
-
-    // try opening more positions
-    for i in 1..=MAX_POSITIONS_LIMIT {
-        suite.manage_position(
-            &alice,
-            PositionAction::Create {
-                identifier: Some(format!("new_position{}", i)),
-                unlocking_duration: UNLOCKING_DURATION_1_DAY,
-                receiver: None,
-            },
-            vec![coin(
-                1_000,
-                format!("factory/{MOCK_CONTRACT_ADDR_1}/{i}.{LP_SYMBOL}"),
-            )],
-            |result| {
-                result.unwrap();
-            },
-        );
-    }
-
-    // alice has MAX_ITEMS_LIMIT open positions and MAX_ITEMS_LIMIT closed positions
-    suite
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(true),
             None,
-            Some(MAX_POSITIONS_LIMIT),
             |result| {
                 let response = result.unwrap();
-                assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
+                assert_eq!(response.positions.len(), 1);
+                assert_eq!(response.positions[0].identifier, "u-additional_position");
             },
         )
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(false),
-            None,
-            Some(MAX_POSITIONS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
-            },
-        );
-
-    // trying to close another position should err
-    suite
         .manage_position(
             &alice,
             PositionAction::Close {
-                identifier: "u-new_position1".to_string(),
+                identifier: "u-additional_position".to_string(),
                 lp_asset: None,
             },
             vec![],
-            |result| {
-                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
-                match err {
-                    ContractError::MaxPositionsPerUserExceeded { .. } => {}
-                    _ => panic!(
-                        "Wrong error type, should return ContractError::MaxPositionsPerUserExceeded"
-                    ),
-                }
-            },
-        )
-        // try closing partially
-        .manage_position(
-            &alice,
-            PositionAction::Close {
-                identifier: "u-new_position1".to_string(),
-                lp_asset: Some(coin(
-                    500,
-                    format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}"),
-                )),
-            },
-            vec![],
-            |result| {
-                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
-                match err {
-                    ContractError::MaxPositionsPerUserExceeded { .. } => {}
-                    _ => panic!(
-                        "Wrong error type, should return ContractError::MaxPositionsPerUserExceeded"
-                    ),
-                }
-            },
-        );
-
-    // let's move time so alice can withdraw a few positions and open some slots to close additional positions
-    suite
-        .add_one_epoch()
-        .manage_position(
-            &alice,
-            PositionAction::Withdraw {
-                identifier: "u-position1".to_string(),
-                emergency_unlock: None,
-            },
-            vec![],
-            |result| {
-                result.unwrap();
-            },
-        )
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(false),
-            None,
-            Some(MAX_POSITIONS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert_eq!(response.positions.len(), (MAX_POSITIONS_LIMIT - 1) as usize);
-            },
-        )
-        // try closing it a position partially
-        .manage_position(
-            &alice,
-            PositionAction::Close {
-                identifier: "u-new_position1".to_string(),
-                lp_asset: Some(coin(
-                    500,
-                    format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}"),
-                )),
-            },
-            vec![],
             |result| {
                 result.unwrap();
             },
         )
-        .query_positions(
-            Some(PositionsBy::Receiver(alice.to_string())),
-            Some(false),
-            None,
-            Some(MAX_POSITIONS_LIMIT),
-            |result| {
-                let response = result.unwrap();
-                assert_eq!(response.positions.len(), MAX_POSITIONS_LIMIT as usize);
-            },
-        );
+        .claim(&alice, vec![], None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::NoOpenPositions => {}
+                _ => panic!("Wrong error type, should return ContractError::NoOpenPositions"),
+            }
+        });
 }
 
 #[test]
@@ -2662,3 +2541,846 @@ fn providing_custom_position_id_doesnt_increment_position_counter() {
             },
         );
 }
+
+#[test]
+fn transfer_position_moves_the_weight_to_the_new_receiver() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, DENOM_UOM),
+        coin(ONE_BILLION, DENOM_UUSDY),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(8_000, DENOM_UUSDY),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(8_000, DENOM_UUSDY), coin(1_000, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_epochs(2)
+        .manage_position(
+            &alice,
+            PositionAction::Transfer {
+                identifier: "p-1".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::PendingRewards => {}
+                    _ => panic!("Wrong error type, should return ContractError::PendingRewards"),
+                }
+            },
+        )
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        })
+        .manage_position(
+            &bob,
+            PositionAction::Transfer {
+                identifier: "p-1".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Transfer {
+                identifier: "p-1".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            Some(PositionsBy::Receiver(bob.to_string())),
+            Some(true),
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 1);
+                assert_eq!(positions[0].identifier, "p-1");
+                assert_eq!(positions[0].receiver, bob);
+            },
+        )
+        .query_lp_weight(&alice, &lp_denom, 13, |result| {
+            result.unwrap_err();
+        })
+        .query_lp_weight(&bob, &lp_denom, 13, |result| {
+            let lp_weight = result.unwrap();
+            assert_eq!(lp_weight.lp_weight, Uint128::new(5_000));
+        })
+        .add_one_epoch()
+        .claim(&alice, vec![], None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::NoOpenPositions => {}
+                _ => panic!("Wrong error type, should return ContractError::NoOpenPositions"),
+            }
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("alice".to_string()),
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the new receiver can't have pending rewards either
+        .manage_position(
+            &alice,
+            PositionAction::Transfer {
+                identifier: "u-alice".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::PendingRewards => {}
+                    _ => panic!("Wrong error type, should return ContractError::PendingRewards"),
+                }
+            },
+        )
+        // bob gets the rewards from the epoch after the transfer
+        .claim(&bob, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &bob, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Transfer {
+                identifier: "u-alice".to_string(),
+                new_receiver: bob.to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        );
+}
+
+#[test]
+fn split_and_merge_positions() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+    let other_lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, DENOM_UOM),
+        coin(ONE_BILLION, lp_denom.clone()),
+        coin(ONE_BILLION, other_lp_denom.clone()),
+    ]);
+
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(10_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &bob,
+            PositionAction::Split {
+                identifier: "p-1".to_string(),
+                amount: Uint128::new(4_000),
+                new_identifier: None,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Split {
+                identifier: "p-1".to_string(),
+                amount: Uint128::new(10_000),
+                new_identifier: None,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidLpAmount { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidLpAmount"),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Split {
+                identifier: "p-1".to_string(),
+                amount: Uint128::new(4_000),
+                new_identifier: Some("ladder".to_string()),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            Some(PositionsBy::Receiver(alice.to_string())),
+            Some(true),
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 2);
+                assert_eq!(positions[0].identifier, "p-1");
+                assert_eq!(positions[0].lp_asset, coin(6_000, lp_denom.clone()));
+                assert_eq!(positions[1].identifier, "u-ladder");
+                assert_eq!(positions[1].lp_asset, coin(4_000, lp_denom.clone()));
+                assert_eq!(positions[1].unlocking_duration, 86_400);
+            },
+        )
+        // the weight of the user remains the same
+        .query_lp_weight(&alice, &lp_denom, 11, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(10_000));
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("long".to_string()),
+                unlocking_duration: 604_800,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: Some("other".to_string()),
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, other_lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Merge {
+                identifiers: vec!["p-1".to_string(), "p-1".to_string()],
+                unlocking_duration: None,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidPositionsToMerge => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidPositionsToMerge"
+                    ),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Merge {
+                identifiers: vec!["p-1".to_string(), "u-other".to_string()],
+                unlocking_duration: None,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        // the lock can't be shortened
+        .manage_position(
+            &alice,
+            PositionAction::Merge {
+                identifiers: vec![
+                    "p-1".to_string(),
+                    "u-ladder".to_string(),
+                    "u-long".to_string(),
+                ],
+                unlocking_duration: Some(86_400),
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidUnlockingDuration { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidUnlockingDuration"
+                    ),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Merge {
+                identifiers: vec![
+                    "p-1".to_string(),
+                    "u-ladder".to_string(),
+                    "u-long".to_string(),
+                ],
+                unlocking_duration: None,
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            Some(PositionsBy::Receiver(alice.to_string())),
+            Some(true),
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 2);
+                assert_eq!(positions[0].identifier, "p-1");
+                assert_eq!(positions[0].lp_asset, coin(15_000, lp_denom.clone()));
+                assert_eq!(positions[0].unlocking_duration, 604_800);
+                assert_eq!(positions[1].identifier, "u-other");
+            },
+        )
+        // the whole position now gets the multiplier of the longest lock
+        .query_lp_weight(&alice, &lp_denom, 11, |result| {
+            assert!(result.unwrap().lp_weight > Uint128::new(15_000));
+        });
+}
+
+#[test]
+fn extend_position_lock() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, DENOM_UOM),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(10_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .query_lp_weight(&alice, &lp_denom, 11, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(10_000));
+        })
+        .manage_position(
+            &bob,
+            PositionAction::ExtendLock {
+                identifier: "p-1".to_string(),
+                new_unlocking_duration: 604_800,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        // the lock can't be shortened nor kept the same
+        .manage_position(
+            &alice,
+            PositionAction::ExtendLock {
+                identifier: "p-1".to_string(),
+                new_unlocking_duration: 86_400,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidUnlockingDuration { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidUnlockingDuration"
+                    ),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::ExtendLock {
+                identifier: "p-1".to_string(),
+                new_unlocking_duration: 31_556_927,
+            },
+            vec![],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidUnlockingDuration { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidUnlockingDuration"
+                    ),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::ExtendLock {
+                identifier: "p-1".to_string(),
+                new_unlocking_duration: 31_556_926,
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            Some(PositionsBy::Identifier("p-1".to_string())),
+            None,
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions[0].unlocking_duration, 31_556_926);
+                assert_eq!(positions[0].lp_asset, coin(10_000, lp_denom.clone()));
+            },
+        )
+        // the current epoch keeps the old weight, the next one gets the max multiplier
+        .query_lp_weight(&alice, &lp_denom, 11, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(10_000));
+        })
+        .query_lp_weight(&alice, &lp_denom, 12, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(159_999));
+        });
+}
+
+#[test]
+fn stake_allowlisted_non_lp_denom() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, DENOM_UOM),
+        coin(ONE_BILLION, DENOM_UOSMO),
+        coin(ONE_BILLION, DENOM_UUSDY),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    // uosmo is not an LP denom, so it can't be staked until the owner allows it
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(5_000, DENOM_UOSMO)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        .update_stakeable_denoms(&alice, vec![DENOM_UOSMO.to_string()], vec![], |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::OwnershipError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+            }
+        })
+        // LP denoms are stakeable already
+        .update_stakeable_denoms(&creator, vec![lp_denom], vec![], |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::AssetMismatch => {}
+                _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+            }
+        })
+        .update_stakeable_denoms(&creator, vec![DENOM_UOSMO.to_string()], vec![], |result| {
+            result.unwrap();
+        })
+        .query_stakeable_denoms(None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.denoms, vec![DENOM_UOSMO.to_string()]);
+        })
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: DENOM_UOSMO.to_string(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(4_000, DENOM_UUSDY),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(4_000, DENOM_UUSDY), coin(1_000, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(5_000, DENOM_UOSMO)],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // rewards for epochs 12 and 13 go to alice, the only staker
+    suite
+        .add_epochs(3)
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance(DENOM_UUSDY.to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        });
+
+    // once removed from the allowlist, no new positions can be opened, but the existing ones can
+    // still be closed
+    suite
+        .update_stakeable_denoms(&creator, vec![], vec![DENOM_UOSMO.to_string()], |result| {
+            result.unwrap();
+        })
+        .query_stakeable_denoms(None, None, |result| {
+            let response = result.unwrap();
+            assert!(response.denoms.is_empty());
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(5_000, DENOM_UOSMO)],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Close {
+                identifier: "p-1".to_string(),
+                lp_asset: None,
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        );
+}
+
+#[test]
+fn boosts_weights_of_stakers() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+    let other_lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+        coin(INITIAL_BALANCE, other_lp_denom.clone()),
+    ]);
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    let farm_manager = suite.farm_manager_addr.clone();
+
+    // full boost of 2.5x when staking 1 uom per unit of weight
+    let boost_config = BoostConfig {
+        max_boost: Decimal::percent(250),
+        stake_per_weight: Decimal::one(),
+    };
+
+    suite
+        .update_boost_config(&alice, Some(boost_config.clone()), |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::OwnershipError { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::OwnershipError"),
+            }
+        })
+        .update_boost_config(
+            &creator,
+            Some(BoostConfig {
+                max_boost: Decimal::percent(50),
+                stake_per_weight: Decimal::one(),
+            }),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidBoostConfig => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidBoostConfig")
+                    }
+                }
+            },
+        )
+        .update_boost_config(
+            &creator,
+            Some(BoostConfig {
+                max_boost: Decimal::percent(250),
+                stake_per_weight: Decimal::zero(),
+            }),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidBoostConfig => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidBoostConfig")
+                    }
+                }
+            },
+        )
+        .update_boost_config(&creator, Some(boost_config.clone()), |result| {
+            result.unwrap();
+        })
+        .query_boost_config(|result| {
+            assert_eq!(result.unwrap().boost_config, Some(boost_config.clone()));
+        });
+
+    // alice stakes enough for half of the extra boost, bob doesn't stake
+    suite
+        .delegate(&alice, coin(500, DENOM_UOM), |result| {
+            result.unwrap();
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(LP_STAKE_AMOUNT_1K, &lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &bob,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(LP_STAKE_AMOUNT_1K, &lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(1_750))]);
+        })
+        .query_lp_weight_history(&bob, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights,
+                vec![(11, Uint128::new(EXPECTED_LP_WEIGHT_1K))]
+            );
+        })
+        .query_lp_weight_history(&farm_manager, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(2_750))]);
+        });
+
+    // the stake is split across the LP denoms, so a position for another LP denom halves the boost
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: UNLOCKING_DURATION_1_DAY,
+                receiver: None,
+            },
+            vec![coin(LP_STAKE_AMOUNT_1K, &other_lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_lp_weight_history(&alice, &other_lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(1_375))]);
+        })
+        .refresh_boost(&bob, &alice, &lp_denom, |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(1_375))]);
+        })
+        .query_lp_weight_history(&farm_manager, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(2_375))]);
+        });
+
+    // once alice unstakes, anyone can refresh their boost
+    suite
+        .add_one_epoch()
+        .undelegate(&alice, coin(500, DENOM_UOM), |result| {
+            result.unwrap();
+        })
+        .refresh_boost(&bob, &creator, &lp_denom, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::NoOpenPositions => {}
+                _ => panic!("Wrong error type, should return ContractError::NoOpenPositions"),
+            }
+        })
+        .refresh_boost(&bob, &alice, &lp_denom, |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights,
+                vec![
+                    (11, Uint128::new(1_375)),
+                    (12, Uint128::new(EXPECTED_LP_WEIGHT_1K))
+                ]
+            );
+        })
+        .query_total_lp_weight(&lp_denom, 12, |result| {
+            assert_eq!(
+                result.unwrap().lp_weight,
+                Uint128::new(EXPECTED_LP_WEIGHT_2K)
+            );
+        });
+
+    // claiming re-evaluates the boost as well, the full boost now applies to both LP denoms
+    suite
+        .add_one_epoch()
+        .delegate(&alice, coin(2_000, DENOM_UOM), |result| {
+            result.unwrap();
+        })
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights.last(),
+                Some(&(13, Uint128::new(2_500)))
+            );
+        })
+        .query_lp_weight_history(&alice, &other_lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights.last(),
+                Some(&(13, Uint128::new(2_500)))
+            );
+        })
+        .query_total_lp_weight(&lp_denom, 13, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(3_500));
+        });
+
+    // disabling the boost
+    suite
+        .update_boost_config(&creator, None, |result| {
+            result.unwrap();
+        })
+        .query_boost_config(|result| {
+            assert_eq!(result.unwrap().boost_config, None);
+        });
+}
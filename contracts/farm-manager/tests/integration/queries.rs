@@ -2,11 +2,13 @@ extern crate core;
 
 use std::cell::RefCell;
 
-use cosmwasm_std::{coin, coins, Coin, Uint128};
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, coins, Coin, Decimal, Uint128};
 use farm_manager::state::{MAX_FARMS_LIMIT, MAX_POSITIONS_LIMIT};
 use mantra_dex_std::constants::LP_SYMBOL;
 use mantra_dex_std::farm_manager::{
-    FarmAction, FarmParams, PositionAction, PositionsBy, RewardsResponse,
+    Curve, EpochRewards, FarmAction, FarmParams, PositionAction, PositionsBy, RewardsResponse,
 };
 
 use crate::common::suite::TestingSuite;
@@ -723,3 +725,394 @@ fn test_query_rewards_divide_by_zero_mitigated() {
         );
     });
 }
+
+#[test]
+fn test_query_farm_emissions() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, DENOM_UOSMO),
+    ]);
+    let creator = suite.creator();
+
+    suite.instantiate_default().add_epochs(10);
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(FARM_START_EPOCH),
+                    preliminary_end_epoch: Some(FARM_END_EPOCH),
+                    curve: None,
+                    farm_asset: coin(4_000, DENOM_UUSDY),
+                    farm_identifier: Some("linear".to_string()),
+                },
+            },
+            vec![coin(4_000, DENOM_UUSDY), coin(ONE_THOUSAND, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(FARM_START_EPOCH),
+                    preliminary_end_epoch: Some(FARM_END_EPOCH),
+                    curve: Some(Curve::Cliff),
+                    farm_asset: coin(4_000, DENOM_UOSMO),
+                    farm_identifier: Some("cliff".to_string()),
+                },
+            },
+            vec![coin(4_000, DENOM_UOSMO), coin(ONE_THOUSAND, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // defaults to the whole farm, the preliminary end epoch not being inclusive
+    suite
+        .query_farm_emissions("m-linear", None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.farm_identifier, "m-linear");
+            assert_eq!(
+                response.emissions,
+                vec![
+                    (12, coin(1_000, DENOM_UUSDY)),
+                    (13, coin(1_000, DENOM_UUSDY)),
+                    (14, coin(1_000, DENOM_UUSDY)),
+                    (15, coin(1_000, DENOM_UUSDY)),
+                ]
+            );
+        })
+        .query_farm_emissions("m-cliff", Some(10), Some(17), |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.emissions,
+                vec![
+                    (10, coin(0, DENOM_UOSMO)),
+                    (11, coin(0, DENOM_UOSMO)),
+                    (12, coin(0, DENOM_UOSMO)),
+                    (13, coin(0, DENOM_UOSMO)),
+                    (14, coin(0, DENOM_UOSMO)),
+                    (15, coin(4_000, DENOM_UOSMO)),
+                    (16, coin(0, DENOM_UOSMO)),
+                    (17, coin(0, DENOM_UOSMO)),
+                ]
+            );
+        })
+        // the range is truncated to the maximum number of epochs
+        .query_farm_emissions("m-linear", Some(1), Some(1_000), |result| {
+            let response = result.unwrap();
+            assert_eq!(response.emissions.len(), 100);
+            assert_eq!(response.emissions.last().unwrap().0, 100);
+        })
+        .query_farm_emissions("m-linear", Some(15), Some(14), |result| {
+            assert!(result.is_err());
+        })
+        .query_farm_emissions("m-non_existent", None, None, |result| {
+            assert!(result.is_err());
+        });
+}
+
+#[test]
+fn test_query_farm_apr_for_non_lp_denom() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, DENOM_UOSMO),
+    ]);
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    suite
+        .update_stakeable_denoms(&creator, vec![DENOM_UOSMO.to_string()], vec![], |result| {
+            result.unwrap();
+        })
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: DENOM_UOSMO.to_string(),
+                    start_epoch: Some(FARM_START_EPOCH),
+                    preliminary_end_epoch: Some(FARM_END_EPOCH),
+                    curve: None,
+                    farm_asset: coin(4_000, DENOM_UOSMO),
+                    farm_identifier: Some("uosmo".to_string()),
+                },
+            },
+            vec![coin(4_000, DENOM_UOSMO), coin(ONE_THOUSAND, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: DENOM_UOSMO.to_string(),
+                    start_epoch: Some(FARM_START_EPOCH),
+                    preliminary_end_epoch: Some(FARM_END_EPOCH),
+                    curve: None,
+                    farm_asset: coin(4_000, DENOM_UUSDY),
+                    farm_identifier: Some("uusdy".to_string()),
+                },
+            },
+            vec![coin(4_000, DENOM_UUSDY), coin(ONE_THOUSAND, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // nobody is staking yet, so there's no APR
+        .query_farm_apr(DENOM_UOSMO, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.epoch_id, 10);
+            assert_eq!(response.total_lp_weight, Uint128::zero());
+            assert!(response.farms.iter().all(|farm| farm.apr.is_none()));
+        })
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, DENOM_UOSMO)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_epochs(2)
+        .query_farm_apr(DENOM_UOSMO, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.lp_denom, DENOM_UOSMO);
+            assert_eq!(response.epoch_id, 12);
+            assert_eq!(response.total_lp_weight, Uint128::new(5_000));
+            assert_eq!(response.farms.len(), 2);
+
+            // 1_000 uosmo per daily epoch over 5_000 uosmo staked
+            let uosmo_farm = &response.farms[0];
+            assert_eq!(uosmo_farm.farm_identifier, "m-uosmo");
+            assert_eq!(uosmo_farm.emission, coin(1_000, DENOM_UOSMO));
+            assert_eq!(
+                uosmo_farm.apr,
+                Some(Decimal::from_str("73.048439814814814814").unwrap())
+            );
+
+            // uusdy can't be valued in terms of uosmo
+            let uusdy_farm = &response.farms[1];
+            assert_eq!(uusdy_farm.farm_identifier, "m-uusdy");
+            assert_eq!(uusdy_farm.emission, coin(1_000, DENOM_UUSDY));
+            assert_eq!(uusdy_farm.apr, None);
+        });
+}
+
+#[test]
+fn test_query_total_lp_weight_and_history() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    let farm_manager = suite.farm_manager_addr.clone();
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(POSITION_LP_AMOUNT, &lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .manage_position(
+            &bob,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(CREATOR_ANOTHER_POSITION_LP_AMOUNT, &lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_epochs(2);
+
+    // the total weight is carried over the epochs without a snapshot
+    suite
+        .query_total_lp_weight(&lp_denom, 10, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::zero());
+        })
+        .query_total_lp_weight(&lp_denom, 11, |result| {
+            assert_eq!(result.unwrap().lp_weight, Uint128::new(1_000));
+        })
+        .query_total_lp_weight(&lp_denom, 13, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.epoch_id, 13);
+            assert_eq!(response.lp_weight, Uint128::new(3_000));
+        })
+        .query_lp_weight_history(&farm_manager, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights,
+                vec![(11, Uint128::new(1_000)), (12, Uint128::new(3_000))]
+            );
+        })
+        .query_lp_weight_history(&farm_manager, &lp_denom, None, Some(1), |result| {
+            assert_eq!(result.unwrap().weights, vec![(11, Uint128::new(1_000))]);
+        })
+        .query_lp_weight_history(&farm_manager, &lp_denom, Some(11), None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(12, Uint128::new(3_000))]);
+        })
+        .query_lp_weight_history(&bob, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(12, Uint128::new(2_000))]);
+        });
+}
+
+#[test]
+fn test_query_detailed_and_filtered_rewards() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+    let other_lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(INITIAL_BALANCE, DENOM_UOM),
+        coin(INITIAL_BALANCE, DENOM_UUSDY),
+        coin(INITIAL_BALANCE, DENOM_UOSMO),
+        coin(INITIAL_BALANCE, lp_denom.clone()),
+    ]);
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    for (farm_asset, farm_identifier) in [(DENOM_UUSDY, "uusdy"), (DENOM_UOSMO, "uosmo")] {
+        suite.manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(FARM_START_EPOCH),
+                    preliminary_end_epoch: Some(FARM_END_EPOCH),
+                    curve: None,
+                    farm_asset: coin(4_000, farm_asset),
+                    farm_identifier: Some(farm_identifier.to_string()),
+                },
+            },
+            vec![coin(4_000, farm_asset), coin(ONE_THOUSAND, DENOM_UOM)],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    for (sender, amount) in [(&alice, 1_000u128), (&bob, 3_000u128)] {
+        suite.manage_position(
+            sender,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(amount, &lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    suite.add_epochs(3);
+
+    // alice has a quarter of the weight on epochs 12 and 13
+    let expected_epochs = vec![
+        EpochRewards {
+            epoch_id: 12,
+            emission: Uint128::new(1_000),
+            lp_weight: Uint128::new(1_000),
+            total_lp_weight: Uint128::new(4_000),
+            reward: Uint128::new(250),
+        },
+        EpochRewards {
+            epoch_id: 13,
+            emission: Uint128::new(1_000),
+            lp_weight: Uint128::new(1_000),
+            total_lp_weight: Uint128::new(4_000),
+            reward: Uint128::new(250),
+        },
+    ];
+
+    suite
+        .query_detailed_rewards(&alice, None, None, |result| {
+            let rewards = result.unwrap().rewards;
+            assert_eq!(rewards.len(), 2);
+
+            assert_eq!(rewards[0].farm_identifier, "m-uosmo");
+            assert_eq!(rewards[0].lp_denom, lp_denom);
+            assert_eq!(rewards[0].reward, coin(500, DENOM_UOSMO));
+            assert_eq!(rewards[0].epochs, expected_epochs);
+
+            assert_eq!(rewards[1].farm_identifier, "m-uusdy");
+            assert_eq!(rewards[1].reward, coin(500, DENOM_UUSDY));
+            assert_eq!(rewards[1].epochs, expected_epochs);
+        })
+        .query_detailed_rewards(&alice, None, Some("m-uusdy"), |result| {
+            let rewards = result.unwrap().rewards;
+            assert_eq!(rewards.len(), 1);
+            assert_eq!(rewards[0].reward, coin(500, DENOM_UUSDY));
+        })
+        .query_detailed_rewards(&alice, Some(&other_lp_denom), None, |result| {
+            assert!(result.unwrap().rewards.is_empty());
+        })
+        .query_filtered_rewards(&alice, Some(&lp_denom), Some("m-uosmo"), |result| {
+            let response = result.unwrap();
+            match response {
+                RewardsResponse::RewardsResponse {
+                    total_rewards,
+                    rewards_per_lp_denom,
+                } => {
+                    assert_eq!(total_rewards, vec![coin(500, DENOM_UOSMO)]);
+                    assert_eq!(
+                        rewards_per_lp_denom,
+                        vec![(lp_denom.clone(), vec![coin(500, DENOM_UOSMO)])]
+                    );
+                }
+                _ => panic!("shouldn't return this but RewardsResponse"),
+            }
+        })
+        .query_filtered_rewards(&alice, Some(&other_lp_denom), None, |result| {
+            let response = result.unwrap();
+            match response {
+                RewardsResponse::RewardsResponse { total_rewards, .. } => {
+                    assert!(total_rewards.is_empty());
+                }
+                _ => panic!("shouldn't return this but RewardsResponse"),
+            }
+        });
+
+    // once claimed, there's nothing left to detail
+    suite
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_detailed_rewards(&alice, None, None, |result| {
+            assert!(result.unwrap().rewards.is_empty());
+        });
+}
@@ -1,11 +1,14 @@
 extern crate core;
 
-use cosmwasm_std::{coin, coins, Coin, Uint128};
+use std::cell::RefCell;
+
+use cosmwasm_std::{coin, coins, Coin, Timestamp, Uint128};
+use cw_utils::Expiration;
 use farm_manager::ContractError;
 use mantra_dex_std::constants::LP_SYMBOL;
 use mantra_dex_std::farm_manager::{
-    Curve, Farm, FarmAction, FarmParams, LpWeightResponse, Position, PositionAction, PositionsBy,
-    RewardsResponse,
+    ClaimOperator, Curve, Farm, FarmAction, FarmParams, LpWeightResponse, Position, PositionAction,
+    PositionsBy, RewardsResponse,
 };
 
 use crate::common::suite::TestingSuite;
@@ -2114,3 +2117,765 @@ fn test_claiming_while_expanding_farm() {
             assert_eq!(balance, Uint128::new(1_000_000_000 + 10_000));
         });
 }
+
+#[test]
+fn claim_rewards_from_non_linear_farms() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, "uosmo"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: Some(Curve::ExponentialDecay {
+                        half_life_epochs: 1,
+                    }),
+                    farm_asset: Coin {
+                        denom: "uusdy".to_string(),
+                        amount: Uint128::new(15_000u128),
+                    },
+                    farm_identifier: Some("decay".to_string()),
+                },
+            },
+            vec![coin(15_000, "uusdy"), coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: Some(Curve::Cliff),
+                    farm_asset: Coin {
+                        denom: "uosmo".to_string(),
+                        amount: Uint128::new(4_000u128),
+                    },
+                    farm_identifier: Some("cliff".to_string()),
+                },
+            },
+            vec![coin(4_000, "uosmo"), coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // farms with a decaying emission can't be expanded
+        .manage_farm(
+            &creator,
+            FarmAction::Expand {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: Coin {
+                        denom: "uusdy".to_string(),
+                        amount: Uint128::new(8_000u128),
+                    },
+                    farm_identifier: Some("m-decay".to_string()),
+                },
+            },
+            vec![coin(8_000, "uusdy")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmNotExpandable { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmNotExpandable"),
+                }
+            },
+        )
+        .manage_position(
+            &other,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // epochs 12, 13 and 14 emit 8_000, 4_000 and 2_000 from the decaying farm, the cliff farm
+    // doesn't emit anything yet
+    suite
+        .add_epochs(4)
+        .claim(&other, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &other, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 14_000u128));
+        })
+        .query_balance("uosmo".to_string(), &other, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION));
+        })
+        // the cliff farm can still be topped up before its last epoch
+        .manage_farm(
+            &creator,
+            FarmAction::Expand {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: Coin {
+                        denom: "uosmo".to_string(),
+                        amount: Uint128::new(1_000u128),
+                    },
+                    farm_identifier: Some("m-cliff".to_string()),
+                },
+            },
+            vec![coin(1_000, "uosmo")],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // the cliff is reached on the last epoch of the farm, releasing everything
+    suite
+        .add_one_epoch()
+        .manage_farm(
+            &creator,
+            FarmAction::Expand {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: Coin {
+                        denom: "uosmo".to_string(),
+                        amount: Uint128::new(1_000u128),
+                    },
+                    farm_identifier: Some("m-cliff".to_string()),
+                },
+            },
+            vec![coin(1_000, "uosmo")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::FarmNotExpandable { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::FarmNotExpandable"),
+                }
+            },
+        )
+        .claim(&other, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &other, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 15_000u128));
+        })
+        .query_balance("uosmo".to_string(), &other, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 5_000u128));
+        });
+}
+
+#[test]
+fn claim_rewards_partially() {
+    let lp_denom_1 = format!("factory/{MOCK_CONTRACT_ADDR_1}/1.{LP_SYMBOL}").to_string();
+    let lp_denom_2 = format!("factory/{MOCK_CONTRACT_ADDR_1}/2.{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, "uosmo"),
+        coin(ONE_BILLION, lp_denom_1.clone()),
+        coin(ONE_BILLION, lp_denom_2.clone()),
+        coin(
+            ONE_BILLION,
+            format!("factory/{MOCK_CONTRACT_ADDR_1}/3.{LP_SYMBOL}"),
+        ),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    for (farm_identifier, lp_denom, farm_asset) in [
+        ("farm_a", lp_denom_1.clone(), coin(4_000, "uusdy")),
+        ("farm_b", lp_denom_1.clone(), coin(4_000, "uosmo")),
+        ("farm_c", lp_denom_2.clone(), coin(8_000, "uusdy")),
+    ] {
+        suite.manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom,
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: farm_asset.clone(),
+                    farm_identifier: Some(farm_identifier.to_string()),
+                },
+            },
+            vec![farm_asset, coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    for lp_denom in [&lp_denom_1, &lp_denom_2] {
+        suite.manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom)],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    suite
+        .add_epochs(3)
+        .claim_partially(
+            &alice,
+            None,
+            Some(vec![format!(
+                "factory/{MOCK_CONTRACT_ADDR_1}/3.{LP_SYMBOL}"
+            )]),
+            None,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NoOpenPositions => {}
+                    _ => panic!("Wrong error type, should return ContractError::NoOpenPositions"),
+                }
+            },
+        )
+        .claim_partially(
+            &alice,
+            None,
+            None,
+            Some(vec!["m-farm_x".to_string()]),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::NonExistentFarm => {}
+                    _ => panic!("Wrong error type, should return ContractError::NonExistentFarm"),
+                }
+            },
+        )
+        // claim the rewards of the second lp denom only, epochs 12 and 13
+        .claim_partially(
+            &alice,
+            None,
+            Some(vec![lp_denom_2.clone()]),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 4_000));
+        })
+        // claim the rewards of farm b only
+        .claim_partially(
+            &alice,
+            None,
+            None,
+            Some(vec!["m-farm_b".to_string()]),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance("uosmo".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        })
+        // only the rewards of farm a are pending
+        .query_rewards(&alice, None, |result| {
+            let rewards_response = result.unwrap();
+            match rewards_response {
+                RewardsResponse::RewardsResponse { total_rewards, .. } => {
+                    assert_eq!(total_rewards, vec![coin(2_000, "uusdy")]);
+                }
+                _ => panic!("shouldn't return this but RewardsResponse"),
+            }
+        })
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 6_000));
+        })
+        .query_balance("uosmo".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        });
+
+    // all the farms are claimed as usual from now on
+    suite
+        .add_one_epoch()
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 9_000));
+        })
+        .query_balance("uosmo".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 3_000));
+        });
+}
+
+#[test]
+fn claim_rewards_through_an_approved_operator() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+    let carol = suite.senders[3].clone();
+
+    suite.instantiate_default();
+
+    for _ in 0..10 {
+        suite.add_one_epoch();
+    }
+
+    suite
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(4_000, "uusdy"),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(4_000, "uusdy"), coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(5_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = RefCell::new(Timestamp::default());
+
+    suite
+        .add_epochs(3)
+        .claim_on_behalf(&bob, &alice, None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .approve_claim_operator(&alice, &bob, Expiration::AtHeight(1), None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::ClaimOperatorExpired => {}
+                _ => panic!("Wrong error type, should return ContractError::ClaimOperatorExpired"),
+            }
+        })
+        .approve_claim_operator(&alice, &alice, Expiration::Never {}, None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .get_time(|result| {
+            *time.borrow_mut() = result;
+        });
+
+    let expires = Expiration::AtTime(time.borrow().plus_days(2));
+
+    suite
+        .approve_claim_operator(&alice, &bob, expires, None, |result| {
+            result.unwrap();
+        })
+        .query_claim_operators(&alice, None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.operators,
+                vec![ClaimOperator {
+                    operator: bob.to_string(),
+                    expires,
+                    receiver: None,
+                }]
+            );
+        })
+        // the operator can't send the rewards to a third party
+        .claim_on_behalf(&bob, &alice, Some(&carol), |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        // nor to itself, unless allowed by the owner
+        .claim_on_behalf(&bob, &alice, Some(&bob), |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        // rewards for epochs 12 and 13 go to alice
+        .claim_on_behalf(&bob, &alice, None, |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &alice, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 2_000));
+        })
+        // alice allows the operator to forward the rewards to itself
+        .approve_claim_operator(&alice, &bob, expires, Some(&bob), |result| {
+            result.unwrap();
+        })
+        .query_claim_operators(&alice, None, None, |result| {
+            assert_eq!(result.unwrap().operators[0].receiver, Some(bob.to_string()));
+        })
+        // rewards for epoch 14 are forwarded to the operator
+        .add_one_epoch()
+        .claim_on_behalf(&bob, &alice, Some(&carol), |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .claim_on_behalf(&bob, &alice, Some(&bob), |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &bob, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 1_000));
+        })
+        // alice can send her own rewards anywhere
+        .add_one_epoch()
+        .claim_on_behalf(&alice, &alice, Some(&carol), |result| {
+            result.unwrap();
+        })
+        .query_balance("uusdy".to_string(), &carol, |balance| {
+            assert_eq!(balance, Uint128::new(ONE_BILLION + 1_000));
+        })
+        // the approval expires
+        .add_one_epoch()
+        .claim_on_behalf(&bob, &alice, None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::ClaimOperatorExpired => {}
+                _ => panic!("Wrong error type, should return ContractError::ClaimOperatorExpired"),
+            }
+        })
+        .approve_claim_operator(&alice, &bob, Expiration::Never {}, None, |result| {
+            result.unwrap();
+        })
+        .revoke_claim_operator(&alice, &bob, |result| {
+            result.unwrap();
+        })
+        .revoke_claim_operator(&alice, &bob, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .query_claim_operators(&alice, None, None, |result| {
+            assert!(result.unwrap().operators.is_empty());
+        })
+        .claim_on_behalf(&bob, &alice, None, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        });
+}
+
+#[test]
+fn claim_history_tracks_lifetime_rewards() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, "uosmo"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    for denom in ["uusdy", "uosmo"] {
+        suite.manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(4_000, denom),
+                    farm_identifier: Some(denom.to_string()),
+                },
+            },
+            vec![coin(4_000, denom), coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    suite
+        .manage_position(
+            &alice,
+            PositionAction::Create {
+                identifier: None,
+                unlocking_duration: 86_400,
+                receiver: None,
+            },
+            vec![coin(1_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_claim_history(&alice, None, None, |result| {
+            let response = result.unwrap();
+            assert!(response.total_claimed.is_empty());
+            assert!(response.farms.is_empty());
+        })
+        .add_epochs(3)
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .add_epochs(2)
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        });
+
+    // the claims of epochs 12-13 and 14-15 add up
+    suite
+        .query_claim_history(&alice, None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(
+                response.total_claimed,
+                vec![coin(4_000, "uosmo"), coin(4_000, "uusdy")]
+            );
+            assert_eq!(
+                response.farms,
+                vec![
+                    ("m-uosmo".to_string(), vec![coin(4_000, "uosmo")]),
+                    ("m-uusdy".to_string(), vec![coin(4_000, "uusdy")]),
+                ]
+            );
+        })
+        .query_claim_history(&alice, None, Some(1), |result| {
+            let response = result.unwrap();
+            assert_eq!(response.total_claimed.len(), 2);
+            assert_eq!(
+                response.farms,
+                vec![("m-uosmo".to_string(), vec![coin(4_000, "uosmo")])]
+            );
+        })
+        .query_claim_history(&alice, Some("m-uosmo".to_string()), None, |result| {
+            assert_eq!(
+                result.unwrap().farms,
+                vec![("m-uusdy".to_string(), vec![coin(4_000, "uusdy")])]
+            );
+        })
+        .query_claim_history(&bob, None, None, |result| {
+            let response = result.unwrap();
+            assert!(response.total_claimed.is_empty());
+            assert!(response.farms.is_empty());
+        });
+}
+
+#[test]
+fn prune_lp_weight_history_of_claimed_epochs() {
+    let lp_denom = format!("factory/{MOCK_CONTRACT_ADDR_1}/{LP_SYMBOL}").to_string();
+
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(ONE_BILLION, "uom"),
+        coin(ONE_BILLION, "uusdy"),
+        coin(ONE_BILLION, "uosmo"),
+        coin(ONE_BILLION, lp_denom.clone()),
+    ]);
+
+    let creator = suite.creator();
+    let alice = suite.senders[1].clone();
+    let bob = suite.senders[2].clone();
+
+    suite.instantiate_default().add_epochs(10);
+
+    for denom in ["uusdy", "uosmo"] {
+        suite.manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: Some(12),
+                    preliminary_end_epoch: Some(16),
+                    curve: None,
+                    farm_asset: coin(4_000, denom),
+                    farm_identifier: Some(denom.to_string()),
+                },
+            },
+            vec![coin(4_000, denom), coin(1_000, "uom")],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    let create_position = PositionAction::Create {
+        identifier: None,
+        unlocking_duration: 86_400,
+        receiver: None,
+    };
+
+    suite
+        .manage_position(
+            &alice,
+            create_position.clone(),
+            vec![coin(1_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_epochs(2)
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        })
+        .manage_position(
+            &alice,
+            create_position.clone(),
+            vec![coin(1_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .manage_position(
+            &alice,
+            create_position.clone(),
+            vec![coin(1_000, lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        // only one of the farms is claimed, the other one still needs the history
+        .claim_partially(
+            &alice,
+            None,
+            None,
+            Some(vec!["m-uusdy".to_string()]),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights,
+                vec![
+                    (12, Uint128::new(1_000)),
+                    (13, Uint128::new(2_000)),
+                    (14, Uint128::new(3_000)),
+                ]
+            );
+        })
+        .prune_history(&bob, &alice, None, |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights.len(), 3);
+        })
+        // once the unclaimed farm is gone, the history before epoch 14 is not needed anymore
+        .manage_farm(
+            &creator,
+            FarmAction::Close {
+                farm_identifier: "m-uosmo".to_string(),
+            },
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .prune_history(&bob, &alice, Some(1), |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(
+                result.unwrap().weights,
+                vec![(13, Uint128::new(2_000)), (14, Uint128::new(3_000))]
+            );
+        })
+        .prune_history(&bob, &alice, None, |result| {
+            result.unwrap();
+        })
+        .query_lp_weight_history(&alice, &lp_denom, None, None, |result| {
+            assert_eq!(result.unwrap().weights, vec![(14, Uint128::new(3_000))]);
+        })
+        .prune_history(&bob, &alice, None, |result| {
+            result.unwrap();
+        });
+
+    // the rewards are not affected
+    suite
+        .add_one_epoch()
+        .query_rewards(&alice, None, |result| {
+            let response = result.unwrap();
+            match response {
+                RewardsResponse::RewardsResponse { total_rewards, .. } => {
+                    assert_eq!(total_rewards, vec![coin(1_000, "uusdy")]);
+                }
+                _ => panic!("shouldn't return this but RewardsResponse"),
+            }
+        })
+        .claim(&alice, vec![], None, |result| {
+            result.unwrap();
+        });
+}
@@ -1,17 +1,17 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+    entry_point, to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
 };
 use cosmwasm_std::{wasm_execute, Reply, StdError};
 use cw2::{get_contract_version, set_contract_version};
 
 use crate::error::ContractError;
 use crate::helpers::validate_asset_balance;
-use crate::migrations::migrate_to_v130;
+use crate::migrations::{migrate_to_v130, migrate_to_v210};
 use crate::state::{
     Config, SingleSideLiquidityProvisionBuffer, CONFIG, POOL_COUNTER,
     SINGLE_SIDE_LIQUIDITY_PROVISION_BUFFER,
 };
-use crate::{liquidity, manager, queries, router, swap};
+use crate::{dca, liquidity, manager, orders, queries, router, swap};
 use mantra_dex_std::pool_manager::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use mantra_utils::validate_contract;
 use semver::Version;
@@ -32,7 +32,14 @@ pub fn instantiate(
     let config: Config = Config {
         fee_collector_addr: deps.api.addr_validate(&msg.fee_collector_addr)?,
         farm_manager_addr: deps.api.addr_validate(&msg.farm_manager_addr)?,
+        epoch_manager_addr: msg
+            .epoch_manager_addr
+            .as_ref()
+            .map(|addr| deps.api.addr_validate(addr))
+            .transpose()?,
         pool_creation_fee: msg.pool_creation_fee.clone(),
+        // referrals are disabled until the owner sets a max commission
+        max_referral_commission: Decimal::zero(),
     };
     CONFIG.save(deps.storage, &config)?;
     // initialize pool counter
@@ -44,6 +51,10 @@ pub fn instantiate(
         ("owner", info.sender.to_string()),
         ("fee_collector_addr", msg.fee_collector_addr),
         ("farm_manager_addr", msg.farm_manager_addr),
+        (
+            "epoch_manager_addr",
+            msg.epoch_manager_addr.unwrap_or_default(),
+        ),
         ("pool_creation_fee", msg.pool_creation_fee.to_string()),
     ]))
 }
@@ -131,8 +142,10 @@ pub fn execute(
             max_slippage,
             receiver,
             pool_identifier,
+            referral,
         } => swap::commands::swap(
             deps,
+            env,
             info.clone(),
             info.sender,
             ask_asset_denom,
@@ -140,6 +153,7 @@ pub fn execute(
             max_slippage,
             receiver,
             pool_identifier,
+            referral,
         ),
         ExecuteMsg::WithdrawLiquidity { pool_identifier } => {
             liquidity::commands::withdraw_liquidity(deps, env, info, pool_identifier)
@@ -153,19 +167,24 @@ pub fn execute(
             minimum_receive,
             receiver,
             max_slippage,
+            referral,
         } => router::commands::execute_swap_operations(
             deps,
+            env,
             info,
             operations,
             minimum_receive,
             receiver,
             max_slippage,
+            referral,
         ),
         ExecuteMsg::UpdateConfig {
             fee_collector_addr,
             farm_manager_addr,
+            epoch_manager_addr,
             pool_creation_fee,
             feature_toggle,
+            max_referral_commission,
         } => {
             cw_utils::nonpayable(&info)?;
             manager::update_config(
@@ -173,10 +192,61 @@ pub fn execute(
                 info,
                 fee_collector_addr,
                 farm_manager_addr,
+                epoch_manager_addr,
                 pool_creation_fee,
                 feature_toggle,
+                max_referral_commission,
             )
         }
+        ExecuteMsg::PlaceOrder {
+            pool_identifier,
+            ask_asset_denom,
+            price,
+            keeper_tip,
+        } => orders::commands::place_order(
+            deps,
+            info,
+            pool_identifier,
+            ask_asset_denom,
+            price,
+            keeper_tip,
+        ),
+        ExecuteMsg::CancelOrder { order_id } => {
+            cw_utils::nonpayable(&info)?;
+            orders::commands::cancel_order(deps, info, order_id)
+        }
+        ExecuteMsg::FillOrder { order_id } => {
+            cw_utils::nonpayable(&info)?;
+            orders::commands::fill_order(deps, env, info, order_id)
+        }
+        ExecuteMsg::CreateDcaOrder {
+            ask_asset_denom,
+            operations,
+            interval,
+            num_swaps,
+            min_price,
+            executor_tip,
+            max_slippage,
+        } => dca::commands::create_dca_order(
+            deps,
+            env,
+            info,
+            ask_asset_denom,
+            operations,
+            interval,
+            num_swaps,
+            min_price,
+            executor_tip,
+            max_slippage,
+        ),
+        ExecuteMsg::ExecuteDcaOrder { order_id } => {
+            cw_utils::nonpayable(&info)?;
+            dca::commands::execute_dca_order(deps, env, info, order_id)
+        }
+        ExecuteMsg::CancelDcaOrder { order_id } => {
+            cw_utils::nonpayable(&info)?;
+            dca::commands::cancel_dca_order(deps, info, order_id)
+        }
     }
 }
 
@@ -196,35 +266,45 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             offer_asset,
             ask_asset_denom,
             pool_identifier,
+            referral_commission,
         } => Ok(to_json_binary(&queries::query_simulation(
             deps,
             offer_asset,
             ask_asset_denom,
             pool_identifier,
+            referral_commission,
         )?)?),
         QueryMsg::ReverseSimulation {
             ask_asset,
             offer_asset_denom,
             pool_identifier,
+            referral_commission,
         } => Ok(to_json_binary(&queries::query_reverse_simulation(
             deps,
             ask_asset,
             offer_asset_denom,
             pool_identifier,
+            referral_commission,
         )?)?),
         QueryMsg::SimulateSwapOperations {
             offer_amount,
             operations,
+            referral_commission,
         } => Ok(to_json_binary(&queries::simulate_swap_operations(
             deps,
             offer_amount,
             operations,
+            referral_commission,
         )?)?),
         QueryMsg::ReverseSimulateSwapOperations {
             ask_amount,
             operations,
+            referral_commission,
         } => Ok(to_json_binary(&queries::reverse_simulate_swap_operations(
-            deps, ask_amount, operations,
+            deps,
+            ask_amount,
+            operations,
+            referral_commission,
         )?)?),
         QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
         QueryMsg::Pools {
@@ -237,6 +317,54 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             start_after,
             limit,
         )?)?),
+        QueryMsg::PoolStats {
+            pool_identifier,
+            epoch,
+        } => Ok(to_json_binary(&queries::query_pool_stats(
+            deps,
+            pool_identifier,
+            epoch,
+        )?)?),
+        QueryMsg::SimulateWithdrawal {
+            pool_identifier,
+            lp_amount,
+        } => Ok(to_json_binary(&queries::query_simulate_withdrawal(
+            deps,
+            pool_identifier,
+            lp_amount,
+        )?)?),
+        QueryMsg::SimulateProvideLiquidity {
+            pool_identifier,
+            assets,
+        } => Ok(to_json_binary(&queries::query_simulate_provide_liquidity(
+            deps,
+            pool_identifier,
+            assets,
+        )?)?),
+        QueryMsg::Orders {
+            owner,
+            pool_identifier,
+            offer_denom,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_orders(
+            deps,
+            owner,
+            pool_identifier,
+            offer_denom,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::DcaOrders {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::query_dca_orders(
+            deps,
+            owner,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
@@ -249,6 +377,10 @@ pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Respons
         migrate_to_v130(deps.branch())?;
     }
 
+    if storage_version < Version::parse("2.1.0")? {
+        migrate_to_v210(deps.branch())?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
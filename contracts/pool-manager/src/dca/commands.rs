@@ -0,0 +1,256 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    attr, coin, ensure, BankMsg, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
+};
+
+use mantra_dex_std::pool_manager::{DcaOrder, SwapOperation};
+
+use crate::router::commands::{assert_operations, perform_swap_operations, SwapOperationsResult};
+use crate::state::{DCA_ORDERS, DCA_ORDER_COUNTER};
+use crate::swap::perform_swap::DEFAULT_SLIPPAGE;
+use crate::ContractError;
+
+/// Cap on the max slippage of the tranches of a DCA order. Tranches are executed by anyone, so it's
+/// way tighter than the one of regular swaps to keep them from being sandwiched.
+pub const MAX_DCA_SLIPPAGE: &str = "0.05";
+
+/// Creates a DCA order. The offer asset sent with the message is escrowed in the contract and
+/// swapped in `num_swaps` tranches through the given route, one every `interval` seconds. The
+/// max slippage of the tranches defaults to the one of swaps, and is capped to [MAX_DCA_SLIPPAGE].
+#[allow(clippy::too_many_arguments)]
+pub fn create_dca_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ask_asset_denom: String,
+    operations: Vec<SwapOperation>,
+    interval: u64,
+    num_swaps: u64,
+    min_price: Option<Decimal>,
+    executor_tip: Option<Decimal>,
+    max_slippage: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let offer_asset_denom = operations
+        .first()
+        .ok_or(ContractError::NoSwapOperationsProvided)?
+        .get_input_asset_info()
+        .clone();
+
+    ensure!(
+        operations
+            .last()
+            .ok_or(ContractError::NoSwapOperationsProvided)?
+            .get_target_asset_info()
+            == ask_asset_denom,
+        ContractError::AssetMismatch
+    );
+
+    assert_operations(operations.clone())?;
+
+    let offer_asset = coin(
+        cw_utils::must_pay(&info, &offer_asset_denom)?.u128(),
+        offer_asset_denom,
+    );
+
+    ensure!(interval > 0u64, ContractError::InvalidDcaInterval);
+    ensure!(num_swaps > 0u64, ContractError::InvalidDcaNumSwaps);
+
+    let tranche_amount = offer_asset.amount.checked_div(num_swaps.into())?;
+    ensure!(!tranche_amount.is_zero(), ContractError::DcaTrancheTooSmall);
+
+    if let Some(min_price) = min_price {
+        ensure!(!min_price.is_zero(), ContractError::InvalidDcaMinPrice);
+    }
+
+    let executor_tip = executor_tip.unwrap_or_default();
+    ensure!(
+        executor_tip < Decimal::one(),
+        ContractError::InvalidKeeperTip {
+            keeper_tip: executor_tip
+        }
+    );
+
+    let max_slippage = max_slippage
+        .unwrap_or(Decimal::from_str(DEFAULT_SLIPPAGE)?)
+        .min(Decimal::from_str(MAX_DCA_SLIPPAGE)?);
+
+    let order_id = DCA_ORDER_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(1u64)
+        .ok_or(ContractError::OrderIdOverflow)?;
+    DCA_ORDER_COUNTER.save(deps.storage, &order_id)?;
+
+    // the first tranche can be executed right away
+    let order = DcaOrder {
+        order_id,
+        owner: info.sender,
+        remaining_offer_asset: offer_asset,
+        tranche_amount,
+        ask_asset_denom,
+        operations,
+        interval,
+        swaps_remaining: num_swaps,
+        min_price,
+        executor_tip,
+        max_slippage,
+        next_execution_time: env.block.time.seconds(),
+    };
+
+    DCA_ORDERS.save(deps.storage, order_id, &order)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "create_dca_order".to_string()),
+        ("order_id", order_id.to_string()),
+        ("owner", order.owner.into_string()),
+        ("offer_asset", order.remaining_offer_asset.to_string()),
+        ("ask_asset_denom", order.ask_asset_denom),
+        ("interval", interval.to_string()),
+        ("num_swaps", num_swaps.to_string()),
+        ("executor_tip", executor_tip.to_string()),
+        ("max_slippage", max_slippage.to_string()),
+    ]))
+}
+
+/// Executes the next tranche of a DCA order. It can be called by anyone once the interval since
+/// the previous tranche has passed, and the executor gets the order's tip out of the return amount.
+pub fn execute_dca_order(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let mut order = DCA_ORDERS
+        .may_load(deps.storage, order_id)?
+        .ok_or(ContractError::DcaOrderNotFound { order_id })?;
+
+    ensure!(
+        env.block.time.seconds() >= order.next_execution_time,
+        ContractError::DcaTrancheNotReady {
+            next_execution_time: order.next_execution_time
+        }
+    );
+
+    // the last tranche takes whatever is left in escrow, i.e. the rounding remainder
+    let tranche = if order.swaps_remaining == 1u64 {
+        order.remaining_offer_asset.clone()
+    } else {
+        coin(
+            order.tranche_amount.u128(),
+            order.remaining_offer_asset.denom.clone(),
+        )
+    };
+
+    let SwapOperationsResult {
+        return_asset,
+        fee_messages,
+        swap_attributes,
+    } = perform_swap_operations(
+        deps.branch(),
+        &env,
+        tranche.clone(),
+        order.operations.clone(),
+        Some(order.max_slippage),
+        None,
+    )?;
+
+    let executor_tip_amount = return_asset.amount.mul_floor(order.executor_tip);
+    let owner_amount = return_asset.amount.checked_sub(executor_tip_amount)?;
+
+    if let Some(min_price) = order.min_price {
+        let expected_amount = tranche.amount.mul_floor(min_price);
+        ensure!(
+            owner_amount >= expected_amount,
+            ContractError::DcaMinPriceNotMet {
+                expected: expected_amount,
+                actual: owner_amount,
+            }
+        );
+    }
+
+    order.remaining_offer_asset.amount = order
+        .remaining_offer_asset
+        .amount
+        .checked_sub(tranche.amount)?;
+    order.swaps_remaining = order.swaps_remaining.saturating_sub(1u64);
+    order.next_execution_time = env.block.time.plus_seconds(order.interval).seconds();
+
+    if order.swaps_remaining == 0u64 {
+        DCA_ORDERS.remove(deps.storage, order_id)?;
+    } else {
+        DCA_ORDERS.save(deps.storage, order_id, &order)?;
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    if !owner_amount.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: order.owner.to_string(),
+                amount: vec![coin(owner_amount.u128(), return_asset.denom.clone())],
+            }
+            .into(),
+        );
+    }
+
+    if !executor_tip_amount.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![coin(executor_tip_amount.u128(), return_asset.denom.clone())],
+            }
+            .into(),
+        );
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_messages(fee_messages)
+        .add_attributes(vec![
+            attr("action", "execute_dca_order"),
+            attr("order_id", order_id.to_string()),
+            attr("owner", order.owner.to_string()),
+            attr("executor", info.sender.to_string()),
+            attr("offer_asset", tranche.to_string()),
+            attr("return_amount", owner_amount.to_string()),
+            attr("executor_tip_amount", executor_tip_amount.to_string()),
+            attr("swaps_remaining", order.swaps_remaining.to_string()),
+        ])
+        .add_attributes(swap_attributes))
+}
+
+/// Cancels a DCA order, refunding the remaining escrowed offer asset to its owner.
+pub fn cancel_dca_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = DCA_ORDERS
+        .may_load(deps.storage, order_id)?
+        .ok_or(ContractError::DcaOrderNotFound { order_id })?;
+
+    ensure!(order.owner == info.sender, ContractError::Unauthorized);
+
+    DCA_ORDERS.remove(deps.storage, order_id)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !order.remaining_offer_asset.amount.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: order.owner.to_string(),
+                amount: vec![order.remaining_offer_asset.clone()],
+            }
+            .into(),
+        );
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "cancel_dca_order".to_string()),
+            ("order_id", order_id.to_string()),
+            ("owner", order.owner.into_string()),
+            ("refund", order.remaining_offer_asset.to_string()),
+        ]))
+}
@@ -1,8 +1,8 @@
 use crate::manager::commands::MAX_ASSETS_PER_POOL;
 use cosmwasm_std::{
     CheckedFromRatioError, CheckedMultiplyFractionError, CheckedMultiplyRatioError,
-    ConversionOverflowError, DivideByZeroError, Instantiate2AddressError, OverflowError, StdError,
-    Uint128,
+    ConversionOverflowError, Decimal, DivideByZeroError, Instantiate2AddressError, OverflowError,
+    StdError, Uint128,
 };
 use cw_migrate_error_derive::cw_migrate_invalid_version_error;
 use cw_ownable::OwnershipError;
@@ -169,6 +169,56 @@ pub enum ContractError {
         amount: Uint128,
         expected: Uint128,
     },
+
+    #[error("The max referral commission can't be greater than 1, got {max_referral_commission}")]
+    InvalidMaxReferralCommission { max_referral_commission: Decimal },
+
+    #[error("The referral commission {commission} exceeds the max allowed commission of {max}")]
+    ReferralCommissionTooHigh { commission: Decimal, max: Decimal },
+
+    #[error("Limit orders are only supported on constant product pools")]
+    LimitOrdersNotSupported,
+
+    #[error("The limit order price must be greater than zero")]
+    InvalidLimitOrderPrice,
+
+    #[error("The keeper tip must be lower than 1, got {keeper_tip}")]
+    InvalidKeeperTip { keeper_tip: Decimal },
+
+    #[error("Limit order {order_id} not found")]
+    LimitOrderNotFound { order_id: u64 },
+
+    #[error("The offer denom must be provided to query the orders of a pool")]
+    OrderOfferDenomRequired,
+
+    #[error("The order id overflowed")]
+    OrderIdOverflow,
+
+    #[error("The limit order can't be filled at the current pool price, expected at least {expected} got {actual}")]
+    LimitOrderNotFillable { expected: Uint128, actual: Uint128 },
+
+    #[error("The DCA order interval must be greater than zero")]
+    InvalidDcaInterval,
+
+    #[error("The DCA order number of swaps must be greater than zero")]
+    InvalidDcaNumSwaps,
+
+    #[error("The DCA order min price must be greater than zero")]
+    InvalidDcaMinPrice,
+
+    #[error("The DCA order offer amount is too small to be split into the given number of swaps")]
+    DcaTrancheTooSmall,
+
+    #[error("DCA order {order_id} not found")]
+    DcaOrderNotFound { order_id: u64 },
+
+    #[error("The next DCA tranche can't be executed before {next_execution_time}")]
+    DcaTrancheNotReady { next_execution_time: u64 },
+
+    #[error(
+        "The DCA tranche doesn't meet the min price, expected at least {expected} got {actual}"
+    )]
+    DcaMinPriceNotMet { expected: Uint128, actual: Uint128 },
 }
 
 impl From<semver::Error> for ContractError {
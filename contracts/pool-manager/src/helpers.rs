@@ -2,17 +2,19 @@ use std::ops::Mul;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    coin, ensure, Addr, Coin, Decimal, Decimal256, Deps, DepsMut, Env, Isqrt, MessageInfo,
-    StdError, StdResult, Uint128, Uint256, Uint512,
+    coin, ensure, Addr, Coin, Decimal, Decimal256, Deps, DepsMut, Env, MessageInfo, StdError,
+    StdResult, Uint128, Uint256, Uint512,
 };
 use mantra_dex_std::coin::{add_coins, aggregate_coins, FACTORY_MAX_SUBDENOM_SIZE};
 use mantra_dex_std::constants::LP_SYMBOL;
-use mantra_dex_std::fee::PoolFee;
+use mantra_dex_std::fee::{Fee, PoolFee};
 use mantra_dex_std::lp_common::MINIMUM_LIQUIDITY_AMOUNT;
-use mantra_dex_std::pool_manager::{PoolInfo, PoolType, SimulationResponse};
+use mantra_dex_std::pool_manager::{PoolInfo, PoolType, Referral, SimulationResponse};
+use mantra_dex_std::U256;
 
 use crate::error::ContractError;
 use crate::math::Decimal256Helper;
+use crate::state::{CONFIG, EPOCH_CONFIG};
 
 /// The amount of iterations to perform when calculating the Newton-Raphson approximation.
 const NEWTON_ITERATIONS: u64 = 255;
@@ -488,11 +490,13 @@ mod test {
     }
 }
 
-/// computes a swap
+/// computes a swap. The referral commission, if any, is taken from the return amount together with
+/// the pool fees, but it doesn't count towards the slippage of the swap.
 pub fn compute_swap(
     pool_info: &PoolInfo,
     offer_asset: &Coin,
     ask_asset_denom: &str,
+    referral_commission: Option<Decimal>,
 ) -> Result<SwapComputation, ContractError> {
     let (offer_pool, ask_pool, _, _, offer_precision, ask_precision) =
         get_asset_indexes_in_pool(pool_info, &offer_asset.denom, ask_asset_denom)?;
@@ -519,7 +523,8 @@ pub fn compute_swap(
                 .to_uint_floor())
             .checked_sub(return_amount)?;
 
-            let fees_computation = compute_fees(&pool_info.pool_fees, return_amount)?;
+            let fees_computation =
+                compute_fees(&pool_info.pool_fees, return_amount, referral_commission)?;
 
             Ok(get_swap_computation(
                 return_amount,
@@ -572,7 +577,8 @@ pub fn compute_swap(
                 .to_uint_floor();
             }
 
-            let fees_computation = compute_fees(&pool_info.pool_fees, return_amount)?;
+            let fees_computation =
+                compute_fees(&pool_info.pool_fees, return_amount, referral_commission)?;
 
             Ok(get_swap_computation(
                 return_amount,
@@ -583,8 +589,12 @@ pub fn compute_swap(
     }
 }
 
-/// Computes the pool fees for a given (return) amount
-fn compute_fees(pool_fees: &PoolFee, amount: Uint256) -> Result<FeesComputation, ContractError> {
+/// Computes the pool fees and the referral fee for a given (return) amount
+fn compute_fees(
+    pool_fees: &PoolFee,
+    amount: Uint256,
+    referral_commission: Option<Decimal>,
+) -> Result<FeesComputation, ContractError> {
     let swap_fee_amount: Uint256 = pool_fees.swap_fee.compute(amount)?;
     let protocol_fee_amount: Uint256 = pool_fees.protocol_fee.compute(amount)?;
     let burn_fee_amount: Uint256 = pool_fees.burn_fee.compute(amount)?;
@@ -601,11 +611,21 @@ fn compute_fees(pool_fees: &PoolFee, amount: Uint256) -> Result<FeesComputation,
         Uint256::zero()
     };
 
+    let referral_fee_amount: Uint256 = if let Some(referral_commission) = referral_commission {
+        Fee {
+            share: referral_commission,
+        }
+        .compute(amount)?
+    } else {
+        Uint256::zero()
+    };
+
     Ok(FeesComputation {
         swap_fee_amount,
         protocol_fee_amount,
         burn_fee_amount,
         extra_fees_amount,
+        referral_fee_amount,
     })
 }
 
@@ -619,7 +639,8 @@ fn get_swap_computation(
         .checked_sub(fees_computation.swap_fee_amount)?
         .checked_sub(fees_computation.protocol_fee_amount)?
         .checked_sub(fees_computation.burn_fee_amount)?
-        .checked_sub(fees_computation.extra_fees_amount)?;
+        .checked_sub(fees_computation.extra_fees_amount)?
+        .checked_sub(fees_computation.referral_fee_amount)?;
 
     let slippage_amount = slippage_amount
         .checked_add(fees_computation.swap_fee_amount)?
@@ -650,6 +671,10 @@ fn get_swap_computation(
             .extra_fees_amount
             .try_into()
             .map_err(|_| ContractError::SwapOverflowError)?,
+        referral_fee_amount: fees_computation
+            .referral_fee_amount
+            .try_into()
+            .map_err(|_| ContractError::SwapOverflowError)?,
     })
 }
 
@@ -660,6 +685,7 @@ pub struct FeesComputation {
     pub protocol_fee_amount: Uint256,
     pub burn_fee_amount: Uint256,
     pub extra_fees_amount: Uint256,
+    pub referral_fee_amount: Uint256,
 }
 
 /// Represents the swap computation values
@@ -671,6 +697,7 @@ pub struct SwapComputation {
     pub protocol_fee_amount: Uint128,
     pub burn_fee_amount: Uint128,
     pub extra_fees_amount: Uint128,
+    pub referral_fee_amount: Uint128,
 }
 
 impl SwapComputation {
@@ -683,6 +710,7 @@ impl SwapComputation {
             protocol_fee_amount: self.protocol_fee_amount,
             burn_fee_amount: self.burn_fee_amount,
             extra_fees_amount: self.extra_fees_amount,
+            referral_fee_amount: self.referral_fee_amount,
         }
     }
 }
@@ -692,6 +720,7 @@ pub fn compute_offer_amount(
     ask_asset_in_pool: Uint128,
     ask_amount: Uint128,
     pool_fees: PoolFee,
+    referral_commission: Option<Decimal>,
 ) -> StdResult<OfferAmountComputation> {
     // Convert Uint128 to Uint256 once
     let offer_asset_in_pool: Uint256 = offer_asset_in_pool.into();
@@ -710,6 +739,12 @@ pub fn compute_offer_amount(
         fees = fees.checked_add(extra_fee.to_decimal_256())?;
     }
 
+    // the referral fee is taken from the return amount together with the pool fees
+    let referral_fee = Fee {
+        share: referral_commission.unwrap_or_default(),
+    };
+    fees = fees.checked_add(referral_fee.to_decimal_256())?;
+
     let one_minus_commission = Decimal256::one() - fees;
     let inv_one_minus_commission = Decimal256::one() / one_minus_commission;
 
@@ -751,6 +786,8 @@ pub fn compute_offer_amount(
             extra_fees_amount.checked_add(extra_fee.compute(before_commission_deduction)?)?;
     }
 
+    let referral_fee_amount: Uint256 = referral_fee.compute(before_commission_deduction)?;
+
     Ok(OfferAmountComputation {
         offer_amount: offer_amount.try_into()?,
         slippage_amount: slippage_amount.try_into()?,
@@ -758,6 +795,7 @@ pub fn compute_offer_amount(
         protocol_fee_amount: protocol_fee_amount.try_into()?,
         burn_fee_amount: burn_fee_amount.try_into()?,
         extra_fees_amount: extra_fees_amount.try_into()?,
+        referral_fee_amount: referral_fee_amount.try_into()?,
     })
 }
 
@@ -770,78 +808,112 @@ pub struct OfferAmountComputation {
     pub protocol_fee_amount: Uint128,
     pub burn_fee_amount: Uint128,
     pub extra_fees_amount: Uint128,
+    pub referral_fee_amount: Uint128,
 }
 
 pub fn assert_slippage_tolerance(
     slippage_tolerance: &Option<Decimal>,
     deposits: &[Coin],
-    pool_assets: &mut [Coin],
-    pool_type: PoolType,
+    pool: &PoolInfo,
 ) -> Result<(), ContractError> {
-    // if the pool assets are zero, there is nothing to check for slippage
-    if pool_assets
-        .iter()
-        .any(|asset| asset.amount == Uint128::zero())
-    {
-        return Ok(());
-    }
-
     if let Some(slippage_tolerance) = *slippage_tolerance {
         let slippage_tolerance: Decimal256 = slippage_tolerance.into();
         if slippage_tolerance > Decimal256::one() {
             return Err(StdError::generic_err("slippage_tolerance cannot bigger than 1").into());
         }
 
-        let one_minus_slippage_tolerance = Decimal256::one() - slippage_tolerance;
-        let deposit_amounts: Vec<Uint256> =
-            deposits.iter().map(|coin| coin.amount.into()).collect();
+        // Ensure each prices are not dropped as much as slippage tolerance rate
+        if compute_liquidity_slippage(deposits, pool)? > slippage_tolerance {
+            return Err(ContractError::MaxSlippageAssertion);
+        }
+    }
 
-        // Sort assets by denom to ensure the order of the assets in the pool is the same as the
-        // deposits, which are sorted previously
-        pool_assets.sort_by(|a, b| a.denom.cmp(&b.denom));
+    Ok(())
+}
 
-        let pools: Vec<Uint256> = pool_assets.iter().map(|coin| coin.amount.into()).collect();
+/// Computes the slippage of a liquidity provision as a fraction, i.e. the value
+/// [assert_slippage_tolerance] compares against the slippage tolerance. Returns zero if the pool is
+/// empty, as there is nothing to check for slippage in that case.
+pub fn compute_liquidity_slippage(
+    deposits: &[Coin],
+    pool: &PoolInfo,
+) -> Result<Decimal256, ContractError> {
+    if pool
+        .assets
+        .iter()
+        .any(|asset| asset.amount == Uint128::zero())
+    {
+        return Ok(Decimal256::zero());
+    }
 
-        // Ensure each prices are not dropped as much as slippage tolerance rate
-        match pool_type {
-            PoolType::StableSwap { amp: amp_factor } => {
-                let d_initial = compute_d(&amp_factor, pool_assets).unwrap();
-                let final_pool_assets = add_coins(pool_assets.to_vec(), deposits.to_vec())?;
-                let d_final = compute_d(&amp_factor, &final_pool_assets).unwrap();
-
-                // Safe conversion to Uint256, since a Sqrt of a Uint512 will always fit into a Uint256
-                let d_initial_sqrt: Uint256 = d_initial.isqrt().try_into().unwrap();
-                let d_final_sqrt: Uint256 = d_final.isqrt().try_into().unwrap();
-                // Determine the ratio of the final and initial D values by squaring the ratio of the square roots
-                let d_ratio_delta = Decimal256::from_ratio(d_final_sqrt, d_initial_sqrt).pow(2);
-
-                if d_ratio_delta > slippage_tolerance {
-                    return Err(ContractError::MaxSlippageAssertion);
-                }
+    // Sort assets by denom to ensure the order of the assets in the pool is the same as the
+    // deposits, which are sorted previously
+    let mut pool_assets = pool.assets.clone();
+    pool_assets.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    match &pool.pool_type {
+        PoolType::StableSwap { amp: amp_factor } => {
+            let d_initial = compute_d_with_pool_info(amp_factor, &pool_assets, pool)
+                .ok_or(ContractError::StableInvariantError)?;
+            let final_pool_assets = add_coins(pool_assets.to_vec(), deposits.to_vec())?;
+            let d_final = compute_d_with_pool_info(amp_factor, &final_pool_assets, pool)
+                .ok_or(ContractError::StableInvariantError)?;
+
+            // a deposit at the peg grows D by the value deposited, so the slippage is the share of
+            // the deposited value, normalized to the same precision as D, that doesn't make it
+            // into D
+            let max_decimals = pool
+                .asset_decimals
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or_default() as u32;
+            let mut deposits_value = Uint256::zero();
+            for deposit in deposits {
+                let decimals = find_denom_decimals(pool, &deposit.denom)
+                    .ok_or(ContractError::AssetMismatch)? as u32;
+                let normalized = normalize_amount(deposit.amount, decimals, max_decimals)
+                    .ok_or(ContractError::StableInvariantError)?;
+                deposits_value = deposits_value.checked_add(normalized.into())?;
             }
-            PoolType::ConstantProduct => {
-                if deposit_amounts.len() != 2 || pools.len() != 2 {
-                    return Err(ContractError::InvalidPoolAssetsLength {
-                        expected: 2,
-                        actual: deposit_amounts.len(),
-                    });
+
+            if deposits_value.is_zero() {
+                return Ok(Decimal256::zero());
+            }
+
+            let d_delta: Uint256 = d_final.saturating_sub(d_initial).try_into()?;
+
+            Ok(Decimal256::one()
+                .saturating_sub(Decimal256::checked_from_ratio(d_delta, deposits_value)?))
+        }
+        PoolType::ConstantProduct => {
+            ensure!(
+                deposits.len() == 2 && pool_assets.len() == 2,
+                ContractError::InvalidPoolAssetsLength {
+                    expected: 2,
+                    actual: deposits.len(),
                 }
+            );
 
-                // both deposits and pools are sorted by denom so the indexes match
-                if Decimal256::from_ratio(deposit_amounts[0], deposit_amounts[1])
-                    * one_minus_slippage_tolerance
-                    > Decimal256::from_ratio(pools[0], pools[1])
-                    || Decimal256::from_ratio(deposit_amounts[1], deposit_amounts[0])
-                        * one_minus_slippage_tolerance
-                        > Decimal256::from_ratio(pools[1], pools[0])
-                {
-                    return Err(ContractError::MaxSlippageAssertion);
+            // the slippage is the largest relative drop between the deposit ratio and the pool
+            // ratio, for either of the assets
+            let mut slippage = Decimal256::zero();
+            for (i, j) in [(0usize, 1usize), (1usize, 0usize)] {
+                let deposit_ratio =
+                    Decimal256::checked_from_ratio(deposits[i].amount, deposits[j].amount)?;
+                let pool_ratio =
+                    Decimal256::checked_from_ratio(pool_assets[i].amount, pool_assets[j].amount)?;
+
+                if deposit_ratio > pool_ratio {
+                    slippage = slippage.max(
+                        Decimal256::one().checked_sub(pool_ratio.checked_div(deposit_ratio)?)?,
+                    );
                 }
             }
+
+            Ok(slippage)
         }
     }
-
-    Ok(())
 }
 
 /// This function compares the address of the message sender with the contract admin
@@ -859,6 +931,36 @@ pub fn assert_admin(deps: Deps, env: &Env, sender: &Addr) -> Result<(), Contract
     Ok(())
 }
 
+/// Gets the current epoch id, derived from the block time and the epoch configuration of the epoch
+/// manager. The epoch configuration is queried once and cached. Returns `None` if there's no epoch
+/// manager set or the genesis epoch hasn't started yet.
+pub fn get_current_epoch_id(deps: DepsMut, env: &Env) -> Result<Option<u64>, ContractError> {
+    let Some(epoch_manager_addr) = CONFIG.load(deps.storage)?.epoch_manager_addr else {
+        return Ok(None);
+    };
+
+    let epoch_config = match EPOCH_CONFIG.may_load(deps.storage)? {
+        Some(epoch_config) => epoch_config,
+        None => {
+            let epoch_manager_config: mantra_dex_std::epoch_manager::Config =
+                deps.querier.query_wasm_smart(
+                    epoch_manager_addr,
+                    &mantra_dex_std::epoch_manager::QueryMsg::Config {},
+                )?;
+            EPOCH_CONFIG.save(deps.storage, &epoch_manager_config.epoch_config)?;
+            epoch_manager_config.epoch_config
+        }
+    };
+
+    let now = env.block.time.seconds();
+    let genesis_epoch = epoch_config.genesis_epoch.u64();
+    if now < genesis_epoch || epoch_config.duration.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some((now - genesis_epoch) / epoch_config.duration.u64()))
+}
+
 /// Validates the amounts after a single side liquidity provision swap are correct.
 pub fn validate_asset_balance(
     deps: &DepsMut,
@@ -896,18 +998,45 @@ pub fn validate_pool_identifier(identifier: &str) -> Result<(), ContractError> {
     Ok(())
 }
 
-/// Aggregates the fees from a simulation response that go out of the contract, i.e. protocol fee and burn fee.
-/// Doesn't know about the denom, just the amount.
+/// Aggregates the fees from a simulation response that go out of the contract, i.e. protocol fee,
+/// burn fee and referral fee. Doesn't know about the denom, just the amount.
 pub fn aggregate_outgoing_fees(
     simulation_response: &SimulationResponse,
 ) -> Result<Uint128, ContractError> {
     let fees = simulation_response
         .protocol_fee_amount
-        .checked_add(simulation_response.burn_fee_amount)?;
+        .checked_add(simulation_response.burn_fee_amount)?
+        .checked_add(simulation_response.referral_fee_amount)?;
 
     Ok(fees)
 }
 
+/// Validates the referral of a swap, making sure the address is valid and the commission doesn't
+/// exceed the max referral commission set in the config. Returns the validated referral address
+/// and commission.
+pub fn validate_referral(
+    deps: Deps,
+    referral: Option<Referral>,
+) -> Result<Option<(Addr, Decimal)>, ContractError> {
+    let Some(referral) = referral else {
+        return Ok(None);
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure!(
+        referral.commission <= config.max_referral_commission,
+        ContractError::ReferralCommissionTooHigh {
+            commission: referral.commission,
+            max: config.max_referral_commission,
+        }
+    );
+
+    let referral_addr = deps.api.addr_validate(&referral.address)?;
+
+    Ok(Some((referral_addr, referral.commission)))
+}
+
 /// Validates that the pool creation and token factory fees are paid with the transaction.
 /// Returns the total amount of fees paid.
 pub fn validate_fees_are_paid(
@@ -1297,6 +1426,140 @@ pub fn compute_lp_mint_amount_for_stableswap_deposit(
     Ok(Some(Uint128::try_from(amount)?))
 }
 
+/// Computes the amount of LP shares to be minted to the liquidity provider for the given deposits.
+/// When the pool is empty, the minimum liquidity amount that is minted to the contract is not
+/// included in the returned amount, see [get_minimum_liquidity_amount].
+pub fn compute_lp_shares(
+    pool: &PoolInfo,
+    deposits: &[Coin],
+    total_shares: Uint128,
+) -> Result<Uint128, ContractError> {
+    let pool_assets = &pool.assets;
+
+    match &pool.pool_type {
+        PoolType::ConstantProduct => {
+            if total_shares == Uint128::zero() {
+                let share = Uint128::new(
+                    (U256::from(deposits[0].amount.u128())
+                        .checked_mul(U256::from(deposits[1].amount.u128()))
+                        .ok_or::<ContractError>(ContractError::LiquidityShareComputationFailed))?
+                    .integer_sqrt()
+                    .as_u128(),
+                )
+                .saturating_sub(MINIMUM_LIQUIDITY_AMOUNT);
+
+                // share should be above zero after subtracting the MINIMUM_LIQUIDITY_AMOUNT
+                if share.is_zero() {
+                    return Err(ContractError::InvalidInitialLiquidityAmount(
+                        MINIMUM_LIQUIDITY_AMOUNT,
+                    ));
+                }
+
+                Ok(share)
+            } else {
+                let mut asset_shares = vec![];
+
+                for deposit in deposits.iter() {
+                    let asset_denom = &deposit.denom;
+                    let pool_asset_index = pool_assets
+                        .iter()
+                        .position(|pool_asset| &pool_asset.denom == asset_denom)
+                        .ok_or(ContractError::AssetMismatch)?;
+
+                    asset_shares.push(
+                        deposit
+                            .amount
+                            .multiply_ratio(total_shares, pool_assets[pool_asset_index].amount),
+                    );
+                }
+
+                Ok(std::cmp::min(asset_shares[0], asset_shares[1]))
+            }
+        }
+        // StableSwap LP calculation has been updated to match the Python simulation
+        // found in contracts/pool-manager/src/tests/integration/lp_actions/stableswap_lp.py.
+        // The key differences are:
+        // 1. For initial deposits, we use the full D value (instead of D - MINIMUM_LIQUIDITY_AMOUNT)
+        // 2. We still mint MINIMUM_LIQUIDITY_AMOUNT to the contract as protection
+        // This approach better aligns with the Curve Finance implementation
+        PoolType::StableSwap { amp: amp_factor } => {
+            if total_shares == Uint128::zero() {
+                // ensure all assets in the pool are provided and the amounts are greater than zero
+                ensure!(
+                    pool_assets.len() == deposits.len()
+                        && deposits.iter().all(|asset| pool_assets
+                            .iter()
+                            .any(|pool_asset| pool_asset.denom == asset.denom
+                                && asset.amount > Uint128::zero())),
+                    ContractError::AssetMismatch
+                );
+            }
+
+            compute_lp_mint_amount_for_stableswap_deposit(
+                amp_factor,
+                pool_assets,
+                &add_coins(pool_assets.clone(), deposits.to_vec())?,
+                total_shares,
+                pool,
+            )?
+            .ok_or(ContractError::StableLpMintError)
+        }
+    }
+}
+
+/// Gets the amount of LP shares that are minted to the contract when liquidity is provided to an
+/// empty pool.
+pub fn get_minimum_liquidity_amount(pool: &PoolInfo) -> Uint128 {
+    match &pool.pool_type {
+        PoolType::ConstantProduct => MINIMUM_LIQUIDITY_AMOUNT,
+        PoolType::StableSwap { .. } => {
+            let min_decimals = *pool.asset_decimals.iter().min().unwrap();
+            let max_decimals = *pool.asset_decimals.iter().max().unwrap();
+
+            get_minimum_liquidity_amount_stableswap(min_decimals, max_decimals)
+        }
+    }
+}
+
+/// Computes the assets to be refunded when withdrawing the given amount of LP shares from a pool.
+/// Assets with a zero amount are filtered out.
+pub fn compute_withdrawal_refund(
+    pool: &PoolInfo,
+    lp_amount: Uint128,
+    total_shares: Uint128,
+) -> Result<Vec<Coin>, ContractError> {
+    // Get the ratio of the amount to withdraw to the total share
+    let share_ratio: Decimal256 = Decimal256::checked_from_ratio(lp_amount, total_shares)?;
+
+    // sanity check, the share_ratio cannot possibly be greater than 1
+    ensure!(
+        share_ratio <= Decimal256::one(),
+        ContractError::InvalidLpShareToWithdraw
+    );
+
+    // Use the ratio to calculate the amount of each pool asset to refund
+    let refund_assets: Vec<Coin> = pool
+        .assets
+        .iter()
+        .map(|pool_asset| {
+            Ok(Coin {
+                denom: pool_asset.denom.clone(),
+                amount: Uint128::try_from(
+                    Decimal256::from_ratio(pool_asset.amount, Uint256::one())
+                        .checked_mul(share_ratio)?
+                        .to_uint_floor(),
+                )?,
+            })
+        })
+        .collect::<Result<Vec<Coin>, ContractError>>()?
+        .into_iter()
+        // filter out assets with zero amount
+        .filter(|coin| coin.amount > Uint128::zero())
+        .collect();
+
+    Ok(refund_assets)
+}
+
 /// Gets the minimum liquidity amount for a stableswap pool, scaled to the given precision.
 pub fn get_minimum_liquidity_amount_stableswap(min_precision: u8, max_precision: u8) -> Uint128 {
     normalize_amount(
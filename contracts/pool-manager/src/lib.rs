@@ -1,6 +1,7 @@
 extern crate alloc;
 
 pub mod contract;
+pub mod dca;
 pub mod error;
 pub mod state;
 pub use crate::error::ContractError;
@@ -9,6 +10,7 @@ pub mod liquidity;
 pub mod manager;
 pub mod math;
 mod migrations;
+pub mod orders;
 pub mod queries;
 pub mod router;
 pub mod swap;
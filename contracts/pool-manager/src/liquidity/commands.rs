@@ -1,14 +1,12 @@
+use cosmwasm_std::Decimal;
 use cosmwasm_std::{
-    coin, coins, ensure, to_json_binary, wasm_execute, BankMsg, Coin, CosmosMsg, Decimal256,
-    DepsMut, Env, MessageInfo, Response, StdResult, SubMsg, Uint256,
+    coin, coins, ensure, to_json_binary, wasm_execute, BankMsg, Coin, CosmosMsg, DepsMut, Env,
+    MessageInfo, Response, StdResult, SubMsg,
 };
-use cosmwasm_std::{Decimal, Uint128};
-use mantra_dex_std::coin::{add_coins, aggregate_coins};
+use mantra_dex_std::coin::aggregate_coins;
 use mantra_dex_std::common::validate_addr_or_default;
 use mantra_dex_std::farm_manager::{PositionsBy, PositionsResponse};
-use mantra_dex_std::lp_common::MINIMUM_LIQUIDITY_AMOUNT;
-use mantra_dex_std::pool_manager::{get_total_share, ExecuteMsg, PoolType};
-use mantra_dex_std::U256;
+use mantra_dex_std::pool_manager::{get_total_share, ExecuteMsg};
 
 use crate::{
     helpers::{self},
@@ -21,10 +19,7 @@ use crate::{
 // After writing create_pool I see this can get quite verbose so attempting to
 // break it down into smaller modules which house some things like swap, liquidity etc
 use crate::contract::SINGLE_SIDE_LIQUIDITY_PROVISION_REPLY_ID;
-use crate::helpers::{
-    aggregate_outgoing_fees, compute_lp_mint_amount_for_stableswap_deposit,
-    get_minimum_liquidity_amount_stableswap,
-};
+use crate::helpers::aggregate_outgoing_fees;
 use crate::queries::query_simulation;
 use crate::state::{
     LiquidityProvisionData, SingleSideLiquidityProvisionBuffer,
@@ -72,10 +67,12 @@ pub fn provide_liquidity(
 
     if is_single_asset_provision {
         // ensure the receiver is the same as the sender if  the intention is to lock the LP tokens
-        // on the farm manager
+        // on the farm manager. The farm manager itself can lock LP tokens on behalf of the receiver
+        // when compounding farm rewards into their position.
         if unlocking_duration.is_some() {
+            let config = CONFIG.load(deps.storage)?;
             ensure!(
-                receiver == info.sender.to_string(),
+                receiver == info.sender.to_string() || info.sender == config.farm_manager_addr,
                 ContractError::Unauthorized
             );
         }
@@ -111,6 +108,7 @@ pub fn provide_liquidity(
             swap_half.clone(),
             ask_asset_denom.clone(),
             pool_identifier.clone(),
+            None,
         )?;
 
         // let's compute the expected offer asset balance in the contract after the swap and liquidity
@@ -171,6 +169,7 @@ pub fn provide_liquidity(
                         max_slippage: swap_max_slippage,
                         receiver: None,
                         pool_identifier,
+                        referral: None,
                     },
                     vec![swap_half],
                 )?,
@@ -185,104 +184,22 @@ pub fn provide_liquidity(
         // Compute share and other logic based on the number of assets
         let total_shares = get_total_share(&deps.as_ref(), liquidity_token.clone())?;
 
-        let shares = match &pool.pool_type {
-            PoolType::ConstantProduct => {
-                if total_shares == Uint128::zero() {
-                    // Make sure at least MINIMUM_LIQUIDITY_AMOUNT is deposited to mitigate the risk of the first
-                    // depositor preventing small liquidity providers from joining the pool
-                    let share = Uint128::new(
-                        (U256::from(deposits[0].amount.u128())
-                            .checked_mul(U256::from(deposits[1].amount.u128()))
-                            .ok_or::<ContractError>(
-                                ContractError::LiquidityShareComputationFailed,
-                            ))?
-                        .integer_sqrt()
-                        .as_u128(),
-                    )
-                    .saturating_sub(MINIMUM_LIQUIDITY_AMOUNT);
-
-                    // share should be above zero after subtracting the MINIMUM_LIQUIDITY_AMOUNT
-                    if share.is_zero() {
-                        return Err(ContractError::InvalidInitialLiquidityAmount(
-                            MINIMUM_LIQUIDITY_AMOUNT,
-                        ));
-                    }
-
-                    messages.push(mantra_dex_std::lp_common::mint_lp_token_msg(
-                        liquidity_token.clone(),
-                        &env.contract.address,
-                        &env.contract.address,
-                        MINIMUM_LIQUIDITY_AMOUNT,
-                    )?);
-
-                    share
-                } else {
-                    let mut asset_shares = vec![];
-
-                    for deposit in deposits.iter() {
-                        let asset_denom = &deposit.denom;
-                        let pool_asset_index = pool_assets
-                            .iter()
-                            .position(|pool_asset| &pool_asset.denom == asset_denom)
-                            .ok_or(ContractError::AssetMismatch)?;
-
-                        asset_shares.push(
-                            deposit
-                                .amount
-                                .multiply_ratio(total_shares, pool_assets[pool_asset_index].amount),
-                        );
-                    }
-
-                    std::cmp::min(asset_shares[0], asset_shares[1])
-                }
-            }
-            // StableSwap LP calculation has been updated to match the Python simulation
-            // found in contracts/pool-manager/src/tests/integration/lp_actions/stableswap_lp.py.
-            // The key differences are:
-            // 1. For initial deposits, we use the full D value (instead of D - MINIMUM_LIQUIDITY_AMOUNT)
-            // 2. We still mint MINIMUM_LIQUIDITY_AMOUNT to the contract as protection
-            // This approach better aligns with the Curve Finance implementation
-            PoolType::StableSwap { amp: amp_factor } => {
-                if total_shares == Uint128::zero() {
-                    // ensure all assets in the pool are provided and the amounts are greater than zero
-                    ensure!(
-                        pool_assets.len() == deposits.len()
-                            && deposits.iter().all(|asset| pool_assets
-                                .iter()
-                                .any(|pool_asset| pool_asset.denom == asset.denom
-                                    && asset.amount > Uint128::zero())),
-                        ContractError::AssetMismatch
-                    );
+        let shares = helpers::compute_lp_shares(&pool, &deposits, total_shares)?;
 
-                    let min_decimals = *pool.asset_decimals.iter().min().unwrap();
-                    let max_decimals = *pool.asset_decimals.iter().max().unwrap();
-
-                    // mint the minimum lp tokens to the contract
-                    messages.push(mantra_dex_std::lp_common::mint_lp_token_msg(
-                        liquidity_token.clone(),
-                        &env.contract.address,
-                        &env.contract.address,
-                        get_minimum_liquidity_amount_stableswap(min_decimals, max_decimals),
-                    )?);
-                }
-                compute_lp_mint_amount_for_stableswap_deposit(
-                    amp_factor,
-                    &pool_assets,
-                    &add_coins(pool_assets.clone(), deposits.clone())?,
-                    total_shares,
-                    &pool,
-                )?
-                .ok_or(ContractError::StableLpMintError)?
-            }
-        };
+        // Make sure at least the minimum liquidity amount is minted to the contract when the pool
+        // is empty to mitigate the risk of the first depositor preventing small liquidity providers
+        // from joining the pool
+        if total_shares.is_zero() {
+            messages.push(mantra_dex_std::lp_common::mint_lp_token_msg(
+                liquidity_token.clone(),
+                &env.contract.address,
+                &env.contract.address,
+                helpers::get_minimum_liquidity_amount(&pool),
+            )?);
+        }
 
         // assert slippage tolerance
-        helpers::assert_slippage_tolerance(
-            &liquidity_max_slippage,
-            &deposits,
-            &mut pool_assets,
-            pool.pool_type.clone(),
-        )?;
+        helpers::assert_slippage_tolerance(&liquidity_max_slippage, &deposits, &pool)?;
 
         // if the unlocking duration is set, lock the LP tokens in the farm manager
         if let Some(unlocking_duration) = unlocking_duration {
@@ -442,34 +359,8 @@ pub fn withdraw_liquidity(
 
     // Get the total share of the pool
     let total_shares = get_total_share(&deps.as_ref(), liquidity_token.clone())?;
-    // Get the ratio of the amount to withdraw to the total share
-    let share_ratio: Decimal256 = Decimal256::from_ratio(amount, total_shares);
-
-    // sanity check, the share_ratio cannot possibly be greater than 1
-    ensure!(
-        share_ratio <= Decimal256::one(),
-        ContractError::InvalidLpShareToWithdraw
-    );
-
-    // Use the ratio to calculate the amount of each pool asset to refund
-    let refund_assets: Vec<Coin> = pool
-        .assets
-        .iter()
-        .map(|pool_asset| {
-            Ok(Coin {
-                denom: pool_asset.denom.clone(),
-                amount: Uint128::try_from(
-                    Decimal256::from_ratio(pool_asset.amount, Uint256::one())
-                        .checked_mul(share_ratio)?
-                        .to_uint_floor(),
-                )?,
-            })
-        })
-        .collect::<Result<Vec<Coin>, ContractError>>()?
-        .into_iter()
-        // filter out assets with zero amount
-        .filter(|coin| coin.amount > Uint128::zero())
-        .collect();
+    // Compute the amount of each pool asset to refund
+    let refund_assets = helpers::compute_withdrawal_refund(&pool, amount, total_shares)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
 
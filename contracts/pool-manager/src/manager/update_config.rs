@@ -1,7 +1,7 @@
-use cosmwasm_std::{Coin, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{ensure, Coin, Decimal, DepsMut, MessageInfo, Response};
 use mantra_dex_std::pool_manager::FeatureToggle;
 
-use crate::state::{get_pool_by_identifier, POOLS};
+use crate::state::{get_pool_by_identifier, EPOCH_CONFIG, POOLS};
 use crate::{state::CONFIG, ContractError};
 
 pub fn update_config(
@@ -9,8 +9,10 @@ pub fn update_config(
     info: MessageInfo,
     fee_collector_addr: Option<String>,
     farm_manager_addr: Option<String>,
+    epoch_manager_addr: Option<String>,
     pool_creation_fee: Option<Coin>,
     feature_toggle: Option<FeatureToggle>,
+    max_referral_commission: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     // permission check
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
@@ -27,10 +29,27 @@ pub fn update_config(
         config.farm_manager_addr = farm_manager_addr;
     }
 
+    if let Some(new_epoch_manager_addr) = epoch_manager_addr {
+        let epoch_manager_addr = deps.api.addr_validate(&new_epoch_manager_addr)?;
+        config.epoch_manager_addr = Some(epoch_manager_addr);
+        // the epoch config is cached from the epoch manager, so it's refreshed on the next swap
+        EPOCH_CONFIG.remove(deps.storage);
+    }
+
     if let Some(pool_creation_fee) = pool_creation_fee {
         config.pool_creation_fee = pool_creation_fee;
     }
 
+    if let Some(max_referral_commission) = max_referral_commission {
+        ensure!(
+            max_referral_commission <= Decimal::one(),
+            ContractError::InvalidMaxReferralCommission {
+                max_referral_commission
+            }
+        );
+        config.max_referral_commission = max_referral_commission;
+    }
+
     if let Some(feature_toggle) = feature_toggle {
         let mut pool_info =
             get_pool_by_identifier(&deps.as_ref(), &feature_toggle.pool_identifier)?;
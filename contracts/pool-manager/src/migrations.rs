@@ -1,9 +1,9 @@
-use crate::state::POOLS;
+use crate::state::{CONFIG, POOLS};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, DepsMut, Order, StdError};
-use cw_storage_plus::{Index, IndexList, IndexedMap, UniqueIndex};
+use cosmwasm_std::{Addr, Coin, Decimal, DepsMut, Order, StdError};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
 use mantra_dex_std::fee::PoolFee;
-use mantra_dex_std::pool_manager::{PoolInfo, PoolStatus, PoolType};
+use mantra_dex_std::pool_manager::{Config, PoolInfo, PoolStatus, PoolType};
 
 /// Migrates to v1.3.0, which adds status to the PoolInfo struct
 pub fn migrate_to_v130(deps: DepsMut) -> Result<(), StdError> {
@@ -84,3 +84,37 @@ pub fn migrate_to_v130(deps: DepsMut) -> Result<(), StdError> {
 
     Ok(())
 }
+
+/// Migrates to v2.1.0, which adds the max referral commission and the epoch manager address to
+/// the Config struct. The epoch manager address is left unset, the owner can set it with
+/// `UpdateConfig` to start bucketing the pool stats by epoch.
+pub fn migrate_to_v210(deps: DepsMut) -> Result<(), StdError> {
+    // recreate the old structure
+    #[cw_serde]
+    struct OldConfig {
+        /// The address where the collected protocol fees go.
+        pub fee_collector_addr: Addr,
+        /// The address of the farm manager contract.
+        pub farm_manager_addr: Addr,
+        /// How much it costs to create a pool. It helps prevent spamming of new pools.
+        pub pool_creation_fee: Coin,
+    }
+
+    const OLD_CONFIG: Item<OldConfig> = Item::new("config");
+
+    let old_config = OLD_CONFIG.load(deps.storage)?;
+
+    // referrals are disabled until the owner sets a max commission
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            fee_collector_addr: old_config.fee_collector_addr,
+            farm_manager_addr: old_config.farm_manager_addr,
+            epoch_manager_addr: None,
+            pool_creation_fee: old_config.pool_creation_fee,
+            max_referral_commission: Decimal::zero(),
+        },
+    )?;
+
+    Ok(())
+}
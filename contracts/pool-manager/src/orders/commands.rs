@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    ensure, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
+};
+
+use mantra_dex_std::coin::burn_coin_msg;
+use mantra_dex_std::pool_manager::{LimitOrder, PoolType};
+
+use crate::state::{get_pool_by_identifier, LIMIT_ORDERS, ORDER_COUNTER};
+use crate::swap::perform_swap::{perform_swap, MAX_ALLOWED_SLIPPAGE};
+use crate::{state::CONFIG, ContractError};
+
+/// Places a limit order on a constant product pool. The offer asset sent with the message is
+/// escrowed in the contract until the order is filled or cancelled.
+pub fn place_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_identifier: String,
+    ask_asset_denom: String,
+    price: Decimal,
+    keeper_tip: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let pool = get_pool_by_identifier(&deps.as_ref(), &pool_identifier)?;
+
+    ensure!(
+        pool.status.swaps_enabled,
+        ContractError::OperationDisabled("place_order".to_string())
+    );
+
+    ensure!(
+        pool.pool_type == PoolType::ConstantProduct,
+        ContractError::LimitOrdersNotSupported
+    );
+
+    let offer_asset = cw_utils::one_coin(&info)?;
+    ensure!(
+        offer_asset.denom != ask_asset_denom,
+        ContractError::SameAsset
+    );
+
+    // verify that the assets match the ones from the pool
+    ensure!(
+        [ask_asset_denom.clone(), offer_asset.denom.clone()]
+            .iter()
+            .all(|asset| pool
+                .assets
+                .iter()
+                .any(|pool_asset| pool_asset.denom == *asset)),
+        ContractError::AssetMismatch
+    );
+
+    ensure!(!price.is_zero(), ContractError::InvalidLimitOrderPrice);
+
+    let keeper_tip = keeper_tip.unwrap_or_default();
+    ensure!(
+        keeper_tip < Decimal::one(),
+        ContractError::InvalidKeeperTip { keeper_tip }
+    );
+
+    let order_id = ORDER_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(1u64)
+        .ok_or(ContractError::OrderIdOverflow)?;
+    ORDER_COUNTER.save(deps.storage, &order_id)?;
+
+    let order = LimitOrder {
+        order_id,
+        owner: info.sender,
+        pool_identifier,
+        offer_asset,
+        ask_asset_denom,
+        price,
+        keeper_tip,
+    };
+
+    LIMIT_ORDERS.save(deps.storage, order_id, &order)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "place_order".to_string()),
+        ("order_id", order_id.to_string()),
+        ("owner", order.owner.into_string()),
+        ("pool_identifier", order.pool_identifier),
+        ("offer_asset", order.offer_asset.to_string()),
+        ("ask_asset_denom", order.ask_asset_denom),
+        ("price", order.price.to_string()),
+        ("keeper_tip", order.keeper_tip.to_string()),
+    ]))
+}
+
+/// Cancels a limit order, refunding the escrowed offer asset to its owner.
+pub fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = LIMIT_ORDERS
+        .may_load(deps.storage, order_id)?
+        .ok_or(ContractError::LimitOrderNotFound { order_id })?;
+
+    ensure!(order.owner == info.sender, ContractError::Unauthorized);
+
+    LIMIT_ORDERS.remove(deps.storage, order_id)?;
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: order.owner.to_string(),
+            amount: vec![order.offer_asset.clone()],
+        })
+        .add_attributes(vec![
+            ("action", "cancel_order".to_string()),
+            ("order_id", order_id.to_string()),
+            ("owner", order.owner.into_string()),
+            ("refund", order.offer_asset.to_string()),
+        ]))
+}
+
+/// Fills a limit order by swapping the escrowed offer asset in the pool. It can be called by
+/// anyone, i.e. keepers, who get the order's tip out of the return amount. The fill only goes
+/// through if the amount the owner gets, after the pool fees and the keeper tip, satisfies the
+/// order's price.
+pub fn fill_order(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = LIMIT_ORDERS
+        .may_load(deps.storage, order_id)?
+        .ok_or(ContractError::LimitOrderNotFound { order_id })?;
+
+    let pool = get_pool_by_identifier(&deps.as_ref(), &order.pool_identifier)?;
+
+    ensure!(
+        pool.status.swaps_enabled,
+        ContractError::OperationDisabled("fill_order".to_string())
+    );
+
+    // the order's price is the slippage protection, so the max slippage is not enforced beyond the
+    // allowed cap
+    let swap_result = perform_swap(
+        deps.branch(),
+        &env,
+        order.offer_asset.clone(),
+        order.ask_asset_denom.clone(),
+        &order.pool_identifier,
+        None,
+        Some(Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?),
+        None,
+    )?;
+
+    let keeper_tip_amount = swap_result.return_asset.amount.mul_floor(order.keeper_tip);
+    let owner_amount = swap_result
+        .return_asset
+        .amount
+        .checked_sub(keeper_tip_amount)?;
+    let expected_amount = order.offer_asset.amount.mul_floor(order.price);
+
+    ensure!(
+        owner_amount >= expected_amount,
+        ContractError::LimitOrderNotFillable {
+            expected: expected_amount,
+            actual: owner_amount,
+        }
+    );
+
+    LIMIT_ORDERS.remove(deps.storage, order_id)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    if !owner_amount.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: order.owner.to_string(),
+                amount: vec![Coin {
+                    denom: order.ask_asset_denom.clone(),
+                    amount: owner_amount,
+                }],
+            }
+            .into(),
+        );
+    }
+
+    if !keeper_tip_amount.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin {
+                    denom: order.ask_asset_denom.clone(),
+                    amount: keeper_tip_amount,
+                }],
+            }
+            .into(),
+        );
+    }
+
+    if !swap_result.protocol_fee_asset.amount.is_zero() {
+        let config = CONFIG.load(deps.storage)?;
+
+        messages.push(
+            BankMsg::Send {
+                to_address: config.fee_collector_addr.to_string(),
+                amount: vec![swap_result.protocol_fee_asset.clone()],
+            }
+            .into(),
+        );
+    }
+
+    if !swap_result.burn_fee_asset.amount.is_zero() {
+        messages.push(burn_coin_msg(swap_result.burn_fee_asset.clone()));
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "fill_order".to_string()),
+            ("order_id", order_id.to_string()),
+            ("owner", order.owner.into_string()),
+            ("keeper", info.sender.into_string()),
+            ("pool_identifier", order.pool_identifier),
+            ("offer_asset", order.offer_asset.to_string()),
+            ("return_amount", owner_amount.to_string()),
+            ("keeper_tip_amount", keeper_tip_amount.to_string()),
+            (
+                "swap_fee_amount",
+                swap_result.swap_fee_asset.amount.to_string(),
+            ),
+            (
+                "protocol_fee_amount",
+                swap_result.protocol_fee_asset.amount.to_string(),
+            ),
+            (
+                "burn_fee_amount",
+                swap_result.burn_fee_asset.amount.to_string(),
+            ),
+        ]))
+}
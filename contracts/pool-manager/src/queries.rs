@@ -1,19 +1,22 @@
 use std::cmp::Ordering;
 
 use cosmwasm_std::{
-    coin, ensure, Coin, Decimal256, Deps, Fraction, Order, StdResult, Uint128, Uint256,
+    coin, ensure, Coin, Decimal, Decimal256, Deps, Fraction, Order, StdResult, Uint128, Uint256,
 };
 use cw_storage_plus::Bound;
 use mantra_dex_std::coin::aggregate_coins;
+use mantra_dex_std::fee::Fee;
 use mantra_dex_std::pool_manager::{
-    AssetDecimalsResponse, Config, PoolInfoResponse, PoolType, PoolsResponse,
+    get_total_share, AssetDecimalsResponse, Config, DcaOrder, DcaOrdersResponse, LimitOrder,
+    OrdersResponse, PoolInfoResponse, PoolStatsResponse, PoolType, PoolsResponse,
     ReverseSimulateSwapOperationsResponse, ReverseSimulationResponse,
-    SimulateSwapOperationsResponse, SimulationResponse, SwapOperation,
+    SimulateProvideLiquidityResponse, SimulateSwapOperationsResponse, SimulateWithdrawalResponse,
+    SimulationResponse, SwapOperation,
 };
 
 use crate::helpers::get_asset_indexes_in_pool;
 use crate::math::Decimal256Helper;
-use crate::state::{CONFIG, POOLS};
+use crate::state::{CONFIG, DCA_ORDERS, LIMIT_ORDERS, POOLS, POOL_EPOCH_STATS, POOL_STATS};
 use crate::{
     helpers::{self, calculate_stableswap_y, StableSwapDirection},
     state::get_pool_by_identifier,
@@ -51,10 +54,16 @@ pub fn query_simulation(
     offer_asset: Coin,
     ask_asset_denom: String,
     pool_identifier: String,
+    referral_commission: Option<Decimal>,
 ) -> Result<SimulationResponse, ContractError> {
     let pool_info = get_pool_by_identifier(&deps, &pool_identifier)?;
 
-    let swap_computation = helpers::compute_swap(&pool_info, &offer_asset, &ask_asset_denom)?;
+    let swap_computation = helpers::compute_swap(
+        &pool_info,
+        &offer_asset,
+        &ask_asset_denom,
+        referral_commission,
+    )?;
 
     Ok(SimulationResponse {
         return_amount: swap_computation.return_amount,
@@ -63,16 +72,19 @@ pub fn query_simulation(
         protocol_fee_amount: swap_computation.protocol_fee_amount,
         burn_fee_amount: swap_computation.burn_fee_amount,
         extra_fees_amount: swap_computation.extra_fees_amount,
+        referral_fee_amount: swap_computation.referral_fee_amount,
     })
 }
 
 /// Queries a swap reverse simulation. Used to derive the number of source tokens returned for
-/// the number of target tokens.
+/// the number of target tokens. The referral commission, if any, is taken into account like in
+/// the forward simulation.
 pub fn query_reverse_simulation(
     deps: Deps,
     ask_asset: Coin,
     offer_asset_denom: String,
     pool_identifier: String,
+    referral_commission: Option<Decimal>,
 ) -> Result<ReverseSimulationResponse, ContractError> {
     let pool_info = get_pool_by_identifier(&deps, &pool_identifier)?;
 
@@ -86,6 +98,7 @@ pub fn query_reverse_simulation(
                 ask_asset_pool.amount,
                 ask_asset.amount,
                 pool_info.pool_fees,
+                referral_commission,
             )?;
 
             Ok(ReverseSimulationResponse {
@@ -95,6 +108,7 @@ pub fn query_reverse_simulation(
                 protocol_fee_amount: offer_amount_computation.protocol_fee_amount,
                 burn_fee_amount: offer_amount_computation.burn_fee_amount,
                 extra_fees_amount: offer_amount_computation.extra_fees_amount,
+                referral_fee_amount: offer_amount_computation.referral_fee_amount,
             })
         }
         PoolType::StableSwap { amp } => {
@@ -107,11 +121,17 @@ pub fn query_reverse_simulation(
                 extra_fees = extra_fees.checked_add(extra_fee.to_decimal_256())?;
             }
 
+            // the referral fee is taken from the return amount together with the pool fees
+            let referral_fee = Fee {
+                share: referral_commission.unwrap_or_default(),
+            };
+
             let before_fees = (Decimal256::one()
                 .checked_sub(pool_info.pool_fees.protocol_fee.to_decimal_256())?
                 .checked_sub(pool_info.pool_fees.swap_fee.to_decimal_256())?
                 .checked_sub(pool_info.pool_fees.burn_fee.to_decimal_256())?)
             .checked_sub(extra_fees)?
+            .checked_sub(referral_fee.to_decimal_256())?
             .inv()
             .unwrap_or_else(Decimal256::one)
             .checked_mul(Decimal256::decimal_with_precision(
@@ -163,6 +183,7 @@ pub fn query_reverse_simulation(
                 extra_fees_amount =
                     extra_fees_amount.checked_add(extra_fee.compute(before_fees_ask)?)?;
             }
+            let referral_fee_amount = referral_fee.compute(before_fees_ask)?;
 
             Ok(ReverseSimulationResponse {
                 offer_amount: offer_amount.try_into()?,
@@ -171,6 +192,7 @@ pub fn query_reverse_simulation(
                 protocol_fee_amount: protocol_fee_amount.try_into()?,
                 burn_fee_amount: burn_fee_amount.try_into()?,
                 extra_fees_amount: extra_fees_amount.try_into()?,
+                referral_fee_amount: referral_fee_amount.try_into()?,
             })
         }
     }
@@ -228,6 +250,7 @@ pub fn simulate_swap_operations(
     deps: Deps,
     offer_amount: Uint128,
     operations: Vec<SwapOperation>,
+    referral_commission: Option<Decimal>,
 ) -> Result<SimulateSwapOperationsResponse, ContractError> {
     let operations_len = operations.len();
     ensure!(operations_len > 0, ContractError::NoSwapOperationsProvided);
@@ -238,19 +261,22 @@ pub fn simulate_swap_operations(
     let mut protocol_fees: Vec<Coin> = vec![];
     let mut burn_fees: Vec<Coin> = vec![];
     let mut extra_fees: Vec<Coin> = vec![];
+    let mut referral_fees: Vec<Coin> = vec![];
 
-    for operation in operations.into_iter() {
+    for (index, operation) in operations.into_iter().enumerate() {
         match operation {
             SwapOperation::MantraSwap {
                 token_in_denom,
                 token_out_denom,
                 pool_identifier,
             } => {
+                // the referral fee is taken once, from the output of the last swap
                 let res = query_simulation(
                     deps,
                     coin(amount.u128(), token_in_denom),
                     token_out_denom.clone(),
                     pool_identifier,
+                    referral_commission.filter(|_| index == operations_len - 1),
                 )?;
                 amount = res.return_amount;
 
@@ -269,6 +295,9 @@ pub fn simulate_swap_operations(
                 if res.extra_fees_amount > Uint128::zero() {
                     extra_fees.push(coin(res.extra_fees_amount.u128(), &token_out_denom));
                 }
+                if res.referral_fee_amount > Uint128::zero() {
+                    referral_fees.push(coin(res.referral_fee_amount.u128(), &token_out_denom));
+                }
             }
         }
     }
@@ -278,6 +307,7 @@ pub fn simulate_swap_operations(
     protocol_fees = aggregate_coins(protocol_fees)?;
     burn_fees = aggregate_coins(burn_fees)?;
     extra_fees = aggregate_coins(extra_fees)?;
+    referral_fees = aggregate_coins(referral_fees)?;
 
     Ok(SimulateSwapOperationsResponse {
         return_amount: amount,
@@ -286,6 +316,7 @@ pub fn simulate_swap_operations(
         protocol_fees,
         burn_fees,
         extra_fees,
+        referral_fees,
     })
 }
 
@@ -295,6 +326,7 @@ pub fn reverse_simulate_swap_operations(
     deps: Deps,
     ask_amount: Uint128,
     operations: Vec<SwapOperation>,
+    referral_commission: Option<Decimal>,
 ) -> Result<ReverseSimulateSwapOperationsResponse, ContractError> {
     let operations_len = operations.len();
     if operations_len == 0 {
@@ -307,19 +339,23 @@ pub fn reverse_simulate_swap_operations(
     let mut protocol_fees: Vec<Coin> = vec![];
     let mut burn_fees: Vec<Coin> = vec![];
     let mut extra_fees: Vec<Coin> = vec![];
+    let mut referral_fees: Vec<Coin> = vec![];
 
-    for operation in operations.into_iter().rev() {
+    for (index, operation) in operations.into_iter().rev().enumerate() {
         match operation {
             SwapOperation::MantraSwap {
                 token_in_denom,
                 token_out_denom,
                 pool_identifier,
             } => {
+                // the referral fee is taken once, from the output of the last swap, which is the
+                // first one simulated
                 let res = query_reverse_simulation(
                     deps,
                     coin(offer_in_needed.u128(), token_out_denom.clone()),
                     token_in_denom,
                     pool_identifier,
+                    referral_commission.filter(|_| index == 0),
                 )?;
 
                 if res.slippage_amount > Uint128::zero() {
@@ -337,6 +373,9 @@ pub fn reverse_simulate_swap_operations(
                 if res.extra_fees_amount > Uint128::zero() {
                     extra_fees.push(coin(res.extra_fees_amount.u128(), &token_out_denom));
                 }
+                if res.referral_fee_amount > Uint128::zero() {
+                    referral_fees.push(coin(res.referral_fee_amount.u128(), &token_out_denom));
+                }
 
                 offer_in_needed = res.offer_amount;
             }
@@ -348,6 +387,7 @@ pub fn reverse_simulate_swap_operations(
     protocol_fees = aggregate_coins(protocol_fees)?;
     burn_fees = aggregate_coins(burn_fees)?;
     extra_fees = aggregate_coins(extra_fees)?;
+    referral_fees = aggregate_coins(referral_fees)?;
 
     Ok(ReverseSimulateSwapOperationsResponse {
         offer_amount: offer_in_needed,
@@ -356,5 +396,257 @@ pub fn reverse_simulate_swap_operations(
         protocol_fees,
         burn_fees,
         extra_fees,
+        referral_fees,
+    })
+}
+
+/// Queries the cumulative swap statistics of a pool, i.e. volume in, volume out, fees and swap
+/// count. If an epoch is provided, returns the stats for that epoch only, otherwise returns the
+/// stats for the lifetime of the pool.
+pub fn query_pool_stats(
+    deps: Deps,
+    pool_identifier: String,
+    epoch: Option<u64>,
+) -> Result<PoolStatsResponse, ContractError> {
+    // make sure the pool exists
+    get_pool_by_identifier(&deps, &pool_identifier)?;
+
+    let stats = if let Some(epoch) = epoch {
+        POOL_EPOCH_STATS.may_load(deps.storage, (&pool_identifier, epoch))?
+    } else {
+        POOL_STATS.may_load(deps.storage, &pool_identifier)?
+    }
+    .unwrap_or_default();
+
+    Ok(PoolStatsResponse {
+        pool_identifier,
+        epoch,
+        stats,
+    })
+}
+
+/// Simulates a liquidity withdrawal, returning the assets that would be refunded for the given
+/// amount of LP tokens.
+pub fn query_simulate_withdrawal(
+    deps: Deps,
+    pool_identifier: String,
+    lp_amount: Uint128,
+) -> Result<SimulateWithdrawalResponse, ContractError> {
+    let pool = get_pool_by_identifier(&deps, &pool_identifier)?;
+    let total_shares = get_total_share(&deps, pool.lp_denom.clone())?;
+
+    let refund_assets = helpers::compute_withdrawal_refund(&pool, lp_amount, total_shares)?;
+
+    Ok(SimulateWithdrawalResponse { refund_assets })
+}
+
+/// Simulates a liquidity provision, returning the amount of LP tokens that would be minted and
+/// the slippage of the provision. If a single asset is provided, half of it is simulated to be
+/// swapped for the other asset in the pool first, as done by the single side liquidity provision.
+pub fn query_simulate_provide_liquidity(
+    deps: Deps,
+    pool_identifier: String,
+    assets: Vec<Coin>,
+) -> Result<SimulateProvideLiquidityResponse, ContractError> {
+    let mut pool = get_pool_by_identifier(&deps, &pool_identifier)?;
+
+    ensure!(
+        pool.status.deposits_enabled,
+        ContractError::OperationDisabled("provide_liquidity".to_string())
+    );
+
+    let mut deposits = aggregate_coins(assets)?;
+
+    ensure!(!deposits.is_empty(), ContractError::EmptyAssets);
+
+    // verify that the assets match the ones from the pool
+    ensure!(
+        deposits.iter().all(|asset| pool
+            .assets
+            .iter()
+            .any(|pool_asset| pool_asset.denom == asset.denom)),
+        ContractError::AssetMismatch
+    );
+
+    let mut swap_offer_asset = None;
+    let mut swap_simulation = None;
+
+    if deposits.len() == 1usize {
+        ensure!(
+            !pool.assets.iter().any(|asset| asset.amount.is_zero()),
+            ContractError::EmptyPoolForSingleSideLiquidityProvision
+        );
+
+        // can't provide single side liquidity on a pool with more than 2 assets
+        ensure!(
+            pool.assets.len() == 2,
+            ContractError::InvalidPoolAssetsForSingleSideLiquidityProvision
+        );
+
+        let deposit = deposits[0].clone();
+
+        let ask_asset_denom = pool
+            .assets
+            .iter()
+            .find(|pool_asset| pool_asset.denom != deposit.denom)
+            .ok_or(ContractError::AssetMismatch)?
+            .denom
+            .clone();
+
+        // swap half of the deposit asset for the other asset in the pool
+        let swap_half = Coin {
+            denom: deposit.denom.clone(),
+            amount: deposit.amount.checked_div_floor((2u64, 1u64))?,
+        };
+
+        let simulation = query_simulation(
+            deps,
+            swap_half.clone(),
+            ask_asset_denom.clone(),
+            pool_identifier,
+            None,
+        )?;
+
+        // update the pool as the swap would, so the liquidity provision is simulated against the
+        // pool state after the swap
+        let (_, _, offer_index, ask_index, _, _) =
+            get_asset_indexes_in_pool(&pool, &swap_half.denom, &ask_asset_denom)?;
+
+        pool.assets[offer_index].amount = pool.assets[offer_index]
+            .amount
+            .checked_add(swap_half.amount)?;
+        pool.assets[ask_index].amount = pool.assets[ask_index]
+            .amount
+            .checked_sub(simulation.return_amount)?
+            .checked_sub(helpers::aggregate_outgoing_fees(&simulation)?)?;
+
+        deposits = aggregate_coins(vec![
+            swap_half.clone(),
+            coin(simulation.return_amount.u128(), ask_asset_denom),
+        ])?;
+
+        swap_offer_asset = Some(swap_half);
+        swap_simulation = Some(simulation);
+    }
+
+    let total_shares = get_total_share(&deps, pool.lp_denom.clone())?;
+    let shares = helpers::compute_lp_shares(&pool, &deposits, total_shares)?;
+    let slippage = helpers::compute_liquidity_slippage(&deposits, &pool)?;
+
+    Ok(SimulateProvideLiquidityResponse {
+        lp_asset: coin(shares.u128(), pool.lp_denom),
+        slippage,
+        swap_offer_asset,
+        swap_simulation,
     })
 }
+
+/// Queries the open limit orders. If an owner is provided, returns the orders of that owner only,
+/// optionally filtered by pool and offer denom. Otherwise, if a pool identifier is provided,
+/// returns the orders placed on that pool offering the given denom, sorted by price.
+pub fn query_orders(
+    deps: Deps,
+    owner: Option<String>,
+    pool_identifier: Option<String>,
+    offer_denom: Option<String>,
+    start_after: Option<(Decimal, u64)>,
+    limit: Option<u32>,
+) -> Result<OrdersResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let orders = if let Some(owner) = owner {
+        let owner = deps.api.addr_validate(&owner)?;
+        let start = start_after.map(|(_, order_id)| Bound::exclusive(order_id));
+
+        // the owner index bounds the iteration, the pool and denom are filtered on top of it
+        LIMIT_ORDERS
+            .idx
+            .owner
+            .prefix(owner.to_string())
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, order)) => {
+                    pool_identifier
+                        .as_ref()
+                        .is_none_or(|pool_identifier| &order.pool_identifier == pool_identifier)
+                        && offer_denom
+                            .as_ref()
+                            .is_none_or(|offer_denom| &order.offer_asset.denom == offer_denom)
+                }
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|item| {
+                let (_, order) = item?;
+                Ok(order)
+            })
+            .collect::<StdResult<Vec<LimitOrder>>>()?
+    } else if let Some(pool_identifier) = pool_identifier {
+        let offer_denom = offer_denom.ok_or(ContractError::OrderOfferDenomRequired)?;
+        let start = start_after
+            .map(|(price, order_id)| Bound::exclusive((price.atomics().u128(), order_id)));
+
+        LIMIT_ORDERS
+            .idx
+            .pool_denom_price
+            .sub_prefix((pool_identifier, offer_denom))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (_, order) = item?;
+                Ok(order)
+            })
+            .collect::<StdResult<Vec<LimitOrder>>>()?
+    } else {
+        let start = start_after.map(|(_, order_id)| Bound::exclusive(order_id));
+
+        LIMIT_ORDERS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (_, order) = item?;
+                Ok(order)
+            })
+            .collect::<StdResult<Vec<LimitOrder>>>()?
+    };
+
+    Ok(OrdersResponse { orders })
+}
+
+/// Queries the open DCA orders. If an owner is provided, returns the orders of that owner only.
+pub fn query_dca_orders(
+    deps: Deps,
+    owner: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<DcaOrdersResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let orders = if let Some(owner) = owner {
+        let owner = deps.api.addr_validate(&owner)?;
+
+        DCA_ORDERS
+            .idx
+            .owner
+            .prefix(owner.to_string())
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (_, order) = item?;
+                Ok(order)
+            })
+            .collect::<StdResult<Vec<DcaOrder>>>()?
+    } else {
+        DCA_ORDERS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (_, order) = item?;
+                Ok(order)
+            })
+            .collect::<StdResult<Vec<DcaOrder>>>()?
+    };
+
+    Ok(DcaOrdersResponse { orders })
+}
@@ -1,16 +1,18 @@
 use cosmwasm_std::{
-    attr, coin, ensure, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, MessageInfo, Response, Uint128,
+    attr, coin, ensure, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo,
+    Response, Uint128,
 };
 
 use mantra_dex_std::coin::burn_coin_msg;
 use mantra_dex_std::common::validate_addr_or_default;
-use mantra_dex_std::pool_manager::SwapOperation;
+use mantra_dex_std::pool_manager::{Referral, SwapOperation};
 
+use crate::helpers::validate_referral;
 use crate::state::get_pool_by_identifier;
 use crate::{state::CONFIG, swap::perform_swap::perform_swap, ContractError};
 
 /// Checks that the output of each [`SwapOperation`] acts as the input of the next swap.
-fn assert_operations(operations: Vec<SwapOperation>) -> Result<(), ContractError> {
+pub(crate) fn assert_operations(operations: Vec<SwapOperation>) -> Result<(), ContractError> {
     // check that the output of each swap is the input of the next swap
     let mut previous_output_info = operations
         .first()
@@ -32,13 +34,16 @@ fn assert_operations(operations: Vec<SwapOperation>) -> Result<(), ContractError
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_swap_operations(
     mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     operations: Vec<SwapOperation>,
     minimum_receive: Option<Uint128>,
     receiver: Option<String>,
     max_slippage: Option<Decimal>,
+    referral: Option<Referral>,
 ) -> Result<Response, ContractError> {
     // ensure that there was at least one operation
     // and retrieve the output token info
@@ -59,20 +64,100 @@ pub fn execute_swap_operations(
 
     assert_operations(operations.clone())?;
 
+    let referral = validate_referral(deps.as_ref(), referral)?;
+
     // we return the output to the sender if no alternative recipient was specified.
     let receiver =
         validate_addr_or_default(&deps.as_ref(), receiver, info.sender.clone()).to_string();
 
+    let SwapOperationsResult {
+        return_asset,
+        fee_messages,
+        swap_attributes,
+    } = perform_swap_operations(
+        deps.branch(),
+        &env,
+        offer_asset.clone(),
+        operations,
+        max_slippage,
+        referral.as_ref(),
+    )?;
+
+    // Execute minimum amount assertion
+    let receiver_balance = return_asset.amount;
+    if let Some(minimum_receive) = minimum_receive {
+        if receiver_balance < minimum_receive {
+            return Err(ContractError::MinimumReceiveAssertion {
+                minimum_receive,
+                swap_amount: receiver_balance,
+            });
+        }
+    }
+
+    let mut bank_msg: Vec<CosmosMsg> = vec![];
+    if !receiver_balance.is_zero() {
+        bank_msg.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: receiver.clone(),
+            amount: vec![coin(receiver_balance.u128(), target_asset_denom.clone())],
+        }));
+    }
+
+    // send output to recipient
+    Ok(Response::new()
+        .add_messages(bank_msg)
+        .add_messages(fee_messages)
+        .add_attributes(vec![
+            attr("action", "execute_swap_operations".to_string()),
+            attr("sender", info.sender.to_string()),
+            attr("receiver", receiver),
+            attr("offer_info", offer_asset.denom),
+            attr("offer_amount", offer_asset.amount.to_string()),
+            attr("return_denom", target_asset_denom),
+            attr("return_amount", receiver_balance.to_string()),
+        ])
+        .add_attributes(
+            referral
+                .map(|(referral_addr, _)| vec![attr("referral", referral_addr)])
+                .unwrap_or_default(),
+        )
+        .add_attributes(swap_attributes))
+}
+
+/// The outcome of performing a sequence of swap operations.
+pub(crate) struct SwapOperationsResult {
+    /// The asset resulting from the last swap operation.
+    pub return_asset: Coin,
+    /// The messages for sending the fees of each swap, i.e. burn, protocol and referral fees.
+    pub fee_messages: Vec<CosmosMsg>,
+    /// The attributes describing each swap, to be added to the response.
+    pub swap_attributes: Vec<(&'static str, String)>,
+}
+
+/// Performs each [`SwapOperation`] in order, using the output of each swap as the input of the
+/// next one. Assumes the operations have been validated with `assert_operations`.
+pub(crate) fn perform_swap_operations(
+    mut deps: DepsMut,
+    env: &Env,
+    offer_asset: Coin,
+    operations: Vec<SwapOperation>,
+    max_slippage: Option<Decimal>,
+    referral: Option<&(Addr, Decimal)>,
+) -> Result<SwapOperationsResult, ContractError> {
     // perform each swap operation
     // we start off with the initial funds
-    let mut previous_swap_output = offer_asset.clone();
+    let mut previous_swap_output = offer_asset;
 
     // stores messages for sending fees after the swaps
     let mut fee_messages = vec![];
     // stores swap attributes to add to tx info
     let mut swap_attributes = vec![];
 
-    for operation in operations {
+    let operations_len = operations.len();
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        // the referral fee is taken once, from the output of the last swap
+        let referral = referral.filter(|_| index == operations_len - 1);
+
         match operation {
             SwapOperation::MantraSwap {
                 token_out_denom,
@@ -91,11 +176,13 @@ pub fn execute_swap_operations(
 
                 let swap_result = perform_swap(
                     deps.branch(),
+                    env,
                     previous_swap_output.clone(),
                     token_out_denom,
                     &pool_identifier,
                     None,
                     max_slippage,
+                    referral.map(|(_, commission)| *commission),
                 )?;
 
                 let pool_reserves: String = swap_result
@@ -109,12 +196,13 @@ pub fn execute_swap_operations(
                 swap_attributes.push((
                     "swap",
                     format!(
-                        "in={}, out={}, burn_fee={}, protocol_fee={}, swap_fee={}",
+                        "in={}, out={}, burn_fee={}, protocol_fee={}, swap_fee={}, referral_fee={}",
                         previous_swap_output,
                         swap_result.return_asset,
                         swap_result.burn_fee_asset,
                         swap_result.protocol_fee_asset,
-                        swap_result.swap_fee_asset
+                        swap_result.swap_fee_asset,
+                        swap_result.referral_fee_asset
                     ),
                 ));
 
@@ -138,41 +226,24 @@ pub fn execute_swap_operations(
                         .into(),
                     );
                 }
+                if let Some((referral_addr, _)) = referral {
+                    if !swap_result.referral_fee_asset.amount.is_zero() {
+                        fee_messages.push(
+                            BankMsg::Send {
+                                to_address: referral_addr.to_string(),
+                                amount: vec![swap_result.referral_fee_asset.clone()],
+                            }
+                            .into(),
+                        );
+                    }
+                }
             }
         }
     }
 
-    // Execute minimum amount assertion
-    let receiver_balance = previous_swap_output.amount;
-    if let Some(minimum_receive) = minimum_receive {
-        if receiver_balance < minimum_receive {
-            return Err(ContractError::MinimumReceiveAssertion {
-                minimum_receive,
-                swap_amount: receiver_balance,
-            });
-        }
-    }
-
-    let mut bank_msg: Vec<CosmosMsg> = vec![];
-    if !receiver_balance.is_zero() {
-        bank_msg.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: receiver.clone(),
-            amount: vec![coin(receiver_balance.u128(), target_asset_denom.clone())],
-        }));
-    }
-
-    // send output to recipient
-    Ok(Response::new()
-        .add_messages(bank_msg)
-        .add_messages(fee_messages)
-        .add_attributes(vec![
-            attr("action", "execute_swap_operations".to_string()),
-            attr("sender", info.sender.to_string()),
-            attr("receiver", receiver),
-            attr("offer_info", offer_asset.denom),
-            attr("offer_amount", offer_asset.amount.to_string()),
-            attr("return_denom", target_asset_denom),
-            attr("return_amount", receiver_balance.to_string()),
-        ])
-        .add_attributes(swap_attributes))
+    Ok(SwapOperationsResult {
+        return_asset: previous_swap_output,
+        fee_messages,
+        swap_attributes,
+    })
 }
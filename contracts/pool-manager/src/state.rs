@@ -1,8 +1,9 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, Decimal, Deps};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, UniqueIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use mantra_dex_std::epoch_manager::EpochConfig;
 pub use mantra_dex_std::pool_manager::Config;
-use mantra_dex_std::pool_manager::{PoolInfo, SwapOperation};
+use mantra_dex_std::pool_manager::{DcaOrder, LimitOrder, PoolInfo, PoolStats, SwapOperation};
 
 use crate::ContractError;
 
@@ -91,3 +92,81 @@ pub struct SwapOperations {
 }
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const POOL_COUNTER: Item<u64> = Item::new("pool_count");
+
+/// The cumulative swap statistics of a pool, bucketed by epoch.
+/// Key is a tuple of (pool_identifier, epoch_id).
+pub const POOL_EPOCH_STATS: Map<(&str, u64), PoolStats> = Map::new("pool_epoch_stats");
+
+/// The epoch configuration of the epoch manager, cached so the current epoch can be derived from the
+/// block time without querying the epoch manager on every swap.
+pub const EPOCH_CONFIG: Item<EpochConfig> = Item::new("epoch_config");
+
+/// The cumulative swap statistics of a pool since it was created. Key is the pool_identifier.
+pub const POOL_STATS: Map<&str, PoolStats> = Map::new("pool_stats");
+
+/// Counter used to assign identifiers to the limit orders.
+pub const ORDER_COUNTER: Item<u64> = Item::new("order_counter");
+
+/// The open limit orders, with their offer assets escrowed in the contract.
+/// The key is the order identifier. The orders are indexed by owner, and by pool, offer denom and
+/// price, so the orders on each side of a pool can be iterated from the lowest price up.
+pub const LIMIT_ORDERS: IndexedMap<u64, LimitOrder, LimitOrderIndexes> = IndexedMap::new(
+    "limit_orders",
+    LimitOrderIndexes {
+        owner: MultiIndex::new(
+            |_pk, o| o.owner.to_string(),
+            "limit_orders",
+            "limit_orders__owner",
+        ),
+        pool_denom_price: MultiIndex::new(
+            |_pk, o| {
+                (
+                    o.pool_identifier.clone(),
+                    o.offer_asset.denom.clone(),
+                    o.price.atomics().u128(),
+                )
+            },
+            "limit_orders",
+            "limit_orders__pool_denom_price",
+        ),
+    },
+);
+
+pub struct LimitOrderIndexes<'a> {
+    pub owner: MultiIndex<'a, String, LimitOrder, u64>,
+    pub pool_denom_price: MultiIndex<'a, (String, String, u128), LimitOrder, u64>,
+}
+
+impl IndexList<LimitOrder> for LimitOrderIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<LimitOrder>> + '_> {
+        let v: Vec<&dyn Index<LimitOrder>> = vec![&self.owner, &self.pool_denom_price];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Counter used to assign identifiers to the DCA orders.
+pub const DCA_ORDER_COUNTER: Item<u64> = Item::new("dca_order_counter");
+
+/// The open DCA orders, with their remaining offer assets escrowed in the contract.
+/// The key is the order identifier.
+pub const DCA_ORDERS: IndexedMap<u64, DcaOrder, DcaOrderIndexes> = IndexedMap::new(
+    "dca_orders",
+    DcaOrderIndexes {
+        owner: MultiIndex::new(
+            |_pk, o| o.owner.to_string(),
+            "dca_orders",
+            "dca_orders__owner",
+        ),
+    },
+);
+
+pub struct DcaOrderIndexes<'a> {
+    pub owner: MultiIndex<'a, String, DcaOrder, u64>,
+}
+
+impl IndexList<DcaOrder> for DcaOrderIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DcaOrder>> + '_> {
+        let v: Vec<&dyn Index<DcaOrder>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
@@ -1,16 +1,20 @@
 use cosmwasm_std::Decimal;
-use cosmwasm_std::{ensure, Addr, BankMsg, CosmosMsg, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{ensure, Addr, BankMsg, CosmosMsg, DepsMut, Env, MessageInfo, Response};
 
 use mantra_dex_std::coin::burn_coin_msg;
 use mantra_dex_std::common::validate_addr_or_default;
+use mantra_dex_std::pool_manager::Referral;
 
+use crate::helpers::validate_referral;
 use crate::state::get_pool_by_identifier;
 use crate::{state::CONFIG, ContractError};
 
 use super::perform_swap::perform_swap;
 
+#[allow(clippy::too_many_arguments)]
 pub fn swap(
     mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     sender: Addr,
     ask_asset_denom: String,
@@ -18,6 +22,7 @@ pub fn swap(
     max_slippage: Option<Decimal>,
     receiver: Option<String>,
     pool_identifier: String,
+    referral: Option<Referral>,
 ) -> Result<Response, ContractError> {
     let pool = get_pool_by_identifier(&deps.as_ref(), &pool_identifier)?;
 
@@ -45,13 +50,17 @@ pub fn swap(
         ContractError::AssetMismatch
     );
 
+    let referral = validate_referral(deps.as_ref(), referral)?;
+
     let swap_result = perform_swap(
         deps.branch(),
+        &env,
         offer_asset.clone(),
         ask_asset_denom,
         &pool_identifier,
         belief_price,
         max_slippage,
+        referral.as_ref().map(|(_, commission)| *commission),
     )?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
@@ -81,6 +90,19 @@ pub fn swap(
         );
     }
 
+    // the referral fee is sent alongside the protocol fee
+    let mut referral_attributes = vec![];
+    if let Some((referral_addr, _)) = referral {
+        if !swap_result.referral_fee_asset.amount.is_zero() {
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: referral_addr.to_string(),
+                amount: vec![swap_result.referral_fee_asset.clone()],
+            }));
+        }
+
+        referral_attributes.push(("referral", referral_addr.into_string()));
+    }
+
     let pool_reserves: String = swap_result
         .pool_info
         .assets
@@ -89,36 +111,43 @@ pub fn swap(
         .collect::<Vec<_>>()
         .join(",");
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "swap".to_string()),
-        ("sender", sender.into_string()),
-        ("receiver", receiver.into_string()),
-        ("offer_denom", offer_asset.denom),
-        ("ask_denom", swap_result.return_asset.denom),
-        ("offer_amount", offer_asset.amount.to_string()),
-        ("return_amount", swap_result.return_asset.amount.to_string()),
-        ("slippage_amount", swap_result.slippage_amount.to_string()),
-        (
-            "swap_fee_amount",
-            swap_result.swap_fee_asset.amount.to_string(),
-        ),
-        (
-            "protocol_fee_amount",
-            swap_result.protocol_fee_asset.amount.to_string(),
-        ),
-        (
-            "burn_fee_amount",
-            swap_result.burn_fee_asset.amount.to_string(),
-        ),
-        (
-            "extra_fees_amount",
-            swap_result.extra_fees_asset.amount.to_string(),
-        ),
-        (
-            "swap_type",
-            swap_result.pool_info.pool_type.get_label().to_string(),
-        ),
-        ("pool_identifier", pool_identifier),
-        ("pool_reserves", pool_reserves),
-    ]))
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "swap".to_string()),
+            ("sender", sender.into_string()),
+            ("receiver", receiver.into_string()),
+            ("offer_denom", offer_asset.denom),
+            ("ask_denom", swap_result.return_asset.denom),
+            ("offer_amount", offer_asset.amount.to_string()),
+            ("return_amount", swap_result.return_asset.amount.to_string()),
+            ("slippage_amount", swap_result.slippage_amount.to_string()),
+            (
+                "swap_fee_amount",
+                swap_result.swap_fee_asset.amount.to_string(),
+            ),
+            (
+                "protocol_fee_amount",
+                swap_result.protocol_fee_asset.amount.to_string(),
+            ),
+            (
+                "burn_fee_amount",
+                swap_result.burn_fee_asset.amount.to_string(),
+            ),
+            (
+                "extra_fees_amount",
+                swap_result.extra_fees_asset.amount.to_string(),
+            ),
+            (
+                "referral_fee_amount",
+                swap_result.referral_fee_asset.amount.to_string(),
+            ),
+            (
+                "swap_type",
+                swap_result.pool_info.pool_type.get_label().to_string(),
+            ),
+            ("pool_identifier", pool_identifier),
+            ("pool_reserves", pool_reserves),
+        ])
+        .add_attributes(referral_attributes))
 }
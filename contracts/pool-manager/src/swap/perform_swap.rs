@@ -1,15 +1,16 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    Coin, Decimal, Decimal256, DepsMut, Fraction, StdError, StdResult, Uint128, Uint256,
+    Coin, Decimal, Decimal256, DepsMut, Env, Fraction, StdError, StdResult, Uint128, Uint256,
 };
 
-use mantra_dex_std::pool_manager::PoolInfo;
+use mantra_dex_std::coin::aggregate_coins;
+use mantra_dex_std::pool_manager::{PoolInfo, PoolStats};
 
-use crate::helpers::{aggregate_outgoing_fees, get_asset_indexes_in_pool};
+use crate::helpers::{aggregate_outgoing_fees, get_asset_indexes_in_pool, get_current_epoch_id};
 use crate::{
     helpers,
-    state::{get_pool_by_identifier, POOLS},
+    state::{get_pool_by_identifier, POOLS, POOL_EPOCH_STATS, POOL_STATS},
     ContractError,
 };
 
@@ -25,6 +26,8 @@ pub struct SwapResult {
     pub swap_fee_asset: Coin,
     /// The extra fees of `return_asset` associated with this swap transaction.
     pub extra_fees_asset: Coin,
+    /// The referral fee of `return_asset` associated with this swap transaction.
+    pub referral_fee_asset: Coin,
     /// The pool that was traded.
     pub pool_info: PoolInfo,
     /// The amount of slippage that occurred during the swap from the original exchange rate.
@@ -39,20 +42,28 @@ pub struct SwapResult {
 /// The resulting [`SwapResult`] has actions that should be taken, as the swap has been performed.
 /// In other words, the caller of the `perform_swap` function _should_ make use
 /// of each field in [`SwapResult`] (besides fields like `slippage_amount`).
+#[allow(clippy::too_many_arguments)]
 pub fn perform_swap(
     deps: DepsMut,
+    env: &Env,
     offer_asset: Coin,
     ask_asset_denom: String,
     pool_identifier: &str,
     belief_price: Option<Decimal>,
     max_slippage: Option<Decimal>,
+    referral_commission: Option<Decimal>,
 ) -> Result<SwapResult, ContractError> {
     let mut pool_info = get_pool_by_identifier(&deps.as_ref(), pool_identifier)?;
 
     let (_, _, offer_index, ask_index, _, _) =
         get_asset_indexes_in_pool(&pool_info, &offer_asset.denom, &ask_asset_denom)?;
 
-    let swap_computation = helpers::compute_swap(&pool_info, &offer_asset, &ask_asset_denom)?;
+    let swap_computation = helpers::compute_swap(
+        &pool_info,
+        &offer_asset,
+        &ask_asset_denom,
+        referral_commission,
+    )?;
 
     let return_asset = Coin {
         denom: ask_asset_denom.clone(),
@@ -60,12 +71,15 @@ pub fn perform_swap(
     };
 
     // Assert slippage and other operations
-    // check max slippage limit if exist
+    // check max slippage limit if exist. The referral fee is chosen by the trader, so it doesn't
+    // count towards the slippage
     assert_max_slippage(
         belief_price,
         max_slippage,
         offer_asset.amount,
-        return_asset.amount,
+        return_asset
+            .amount
+            .checked_add(swap_computation.referral_fee_amount)?,
         swap_computation.slippage_amount,
     )?;
 
@@ -99,6 +113,10 @@ pub fn perform_swap(
         denom: ask_asset_denom.clone(),
         amount: swap_computation.extra_fees_amount,
     };
+    let referral_fee_asset = Coin {
+        denom: ask_asset_denom.clone(),
+        amount: swap_computation.referral_fee_amount,
+    };
 
     #[allow(clippy::redundant_clone)]
     let swap_fee_asset = Coin {
@@ -106,14 +124,81 @@ pub fn perform_swap(
         amount: swap_computation.swap_fee_amount,
     };
 
-    Ok(SwapResult {
+    let swap_result = SwapResult {
         return_asset,
         swap_fee_asset,
         burn_fee_asset,
         protocol_fee_asset,
         pool_info,
         extra_fees_asset,
+        referral_fee_asset,
         slippage_amount: swap_computation.slippage_amount,
+    };
+
+    record_pool_stats(deps, env, pool_identifier, &offer_asset, &swap_result)?;
+
+    Ok(swap_result)
+}
+
+/// Records the volume and fees of a swap in the pool stats, both for the current epoch and for
+/// the lifetime of the pool.
+fn record_pool_stats(
+    mut deps: DepsMut,
+    env: &Env,
+    pool_identifier: &str,
+    offer_asset: &Coin,
+    swap_result: &SwapResult,
+) -> Result<(), ContractError> {
+    // the epoch stats are only recorded if there's an epoch manager and the genesis epoch started
+    if let Some(current_epoch_id) = get_current_epoch_id(deps.branch(), env)? {
+        let epoch_stats = POOL_EPOCH_STATS
+            .may_load(deps.storage, (pool_identifier, current_epoch_id))?
+            .unwrap_or_default();
+        POOL_EPOCH_STATS.save(
+            deps.storage,
+            (pool_identifier, current_epoch_id),
+            &add_swap_to_stats(epoch_stats, offer_asset, swap_result)?,
+        )?;
+    }
+
+    let lifetime_stats = POOL_STATS
+        .may_load(deps.storage, pool_identifier)?
+        .unwrap_or_default();
+    POOL_STATS.save(
+        deps.storage,
+        pool_identifier,
+        &add_swap_to_stats(lifetime_stats, offer_asset, swap_result)?,
+    )?;
+
+    Ok(())
+}
+
+/// Adds the amounts of a swap to the given [PoolStats].
+fn add_swap_to_stats(
+    stats: PoolStats,
+    offer_asset: &Coin,
+    swap_result: &SwapResult,
+) -> Result<PoolStats, ContractError> {
+    let add = |total: Vec<Coin>, coin: &Coin| -> StdResult<Vec<Coin>> {
+        if coin.amount.is_zero() {
+            return Ok(total);
+        }
+
+        aggregate_coins([total, vec![coin.clone()]].concat())
+    };
+
+    Ok(PoolStats {
+        swap_count: stats
+            .swap_count
+            .checked_add(1u64)
+            .ok_or_else(|| StdError::generic_err("Overflow when increasing the pool swap count"))?,
+        volume_in: add(stats.volume_in, offer_asset)?,
+        volume_out: add(stats.volume_out, &swap_result.return_asset)?,
+        swap_fees: add(stats.swap_fees, &swap_result.swap_fee_asset)?,
+        protocol_fees: add(stats.protocol_fees, &swap_result.protocol_fee_asset)?,
+        burn_fees: add(stats.burn_fees, &swap_result.burn_fee_asset)?,
+        extra_fees: add(stats.extra_fees, &swap_result.extra_fees_asset)?,
+        referral_fees: add(stats.referral_fees, &swap_result.referral_fee_asset)?,
     })
 }
 
@@ -13,5 +13,9 @@ fn instantiate_normal() {
         ]),
     );
 
-    suite.instantiate(suite.senders[0].to_string(), suite.senders[1].to_string());
+    suite.instantiate(
+        suite.senders[0].to_string(),
+        suite.senders[1].to_string(),
+        None,
+    );
 }
@@ -0,0 +1,325 @@
+use std::cell::RefCell;
+
+use cosmwasm_std::{coin, Decimal, StdError, Uint128};
+use mantra_common_testing::multi_test::stargate_mock::StargateMock;
+use mantra_dex_std::fee::{Fee, PoolFee};
+use mantra_dex_std::pool_manager::{PoolType, SwapOperation};
+use test_utils::common_constants::{
+    DECIMALS_6, DENOM_ULUNA, DENOM_UOM, DENOM_UUSD, ONE_BILLION, ONE_HUNDRED_TRILLION, ONE_MILLION,
+    ONE_THOUSAND, STARGATE_MOCK_UOM_AMOUNT,
+};
+
+use crate::tests::suite::TestingSuite;
+use crate::ContractError;
+
+const ULUNA_UUSD_POOL_RAW: &str = "uluna.uusd";
+const ULUNA_UUSD_POOL_ID: &str = "o.uluna.uusd";
+const ONE_DAY_IN_SECONDS: u64 = 86_400;
+
+#[test]
+fn create_execute_and_cancel_dca_orders() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(ONE_HUNDRED_TRILLION, DENOM_ULUNA.to_string()),
+            coin(ONE_HUNDRED_TRILLION, DENOM_UUSD.to_string()),
+            coin(10_000u128, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let user = suite.senders[1].clone();
+    let executor = suite.senders[2].clone();
+    let pool_id = ULUNA_UUSD_POOL_ID.to_string();
+
+    let operations = vec![SwapOperation::MantraSwap {
+        token_in_denom: DENOM_ULUNA.to_string(),
+        token_out_denom: DENOM_UUSD.to_string(),
+        pool_identifier: pool_id.clone(),
+    }];
+
+    suite
+        .instantiate_default()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_ULUNA.to_string(), DENOM_UUSD.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            PoolFee {
+                protocol_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                swap_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                burn_fee: Fee {
+                    share: Decimal::zero(),
+                },
+                extra_fees: vec![],
+            },
+            PoolType::ConstantProduct,
+            Some(ULUNA_UUSD_POOL_RAW.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_UUSD),
+                coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            pool_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_BILLION, DENOM_ULUNA.to_string()),
+                coin(ONE_BILLION, DENOM_UUSD.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // invalid orders
+    suite
+        .create_dca_order(
+            &user,
+            DENOM_ULUNA.to_string(),
+            operations.clone(),
+            ONE_DAY_IN_SECONDS,
+            2,
+            None,
+            None,
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::AssetMismatch => {}
+                    _ => panic!("Wrong error type, should return ContractError::AssetMismatch"),
+                }
+            },
+        )
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations.clone(),
+            ONE_DAY_IN_SECONDS,
+            0,
+            None,
+            None,
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidDcaNumSwaps => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidDcaNumSwaps")
+                    }
+                }
+            },
+        )
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations.clone(),
+            0,
+            2,
+            None,
+            None,
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidDcaInterval => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidDcaInterval")
+                    }
+                }
+            },
+        );
+
+    // buy uusd with 10 uluna (plus 1 unit of remainder) in 2 daily tranches
+    suite
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations.clone(),
+            ONE_DAY_IN_SECONDS,
+            2,
+            None,
+            Some(Decimal::percent(1)),
+            Some(Decimal::percent(5)),
+            vec![coin(ONE_MILLION * 10 + 1, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_dca_orders(Some(user.to_string()), None, None, |result| {
+            let orders = result.unwrap().orders;
+            assert_eq!(orders.len(), 1);
+            assert_eq!(orders[0].order_id, 1);
+            assert_eq!(orders[0].tranche_amount, Uint128::new(ONE_MILLION * 5));
+            assert_eq!(orders[0].swaps_remaining, 2);
+        });
+
+    let user_balance = RefCell::new(Uint128::zero());
+    suite.query_balance(&user.to_string(), DENOM_UUSD, |result| {
+        *user_balance.borrow_mut() = result.unwrap().amount;
+    });
+
+    // the first tranche can be executed right away, the second one only after the interval
+    suite
+        .execute_dca_order(&executor, 1, |result| {
+            result.unwrap();
+        })
+        .query_dca_orders(None, None, None, |result| {
+            let orders = result.unwrap().orders;
+            assert_eq!(orders.len(), 1);
+            assert_eq!(orders[0].swaps_remaining, 1);
+            assert_eq!(
+                orders[0].remaining_offer_asset,
+                coin(ONE_MILLION * 5 + 1, DENOM_ULUNA)
+            );
+        })
+        .execute_dca_order(&executor, 1, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::DcaTrancheNotReady { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::DcaTrancheNotReady"),
+            }
+        })
+        .add_one_day()
+        .execute_dca_order(&executor, 1, |result| {
+            let response = result.unwrap();
+            let executor_tip = response
+                .events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .find(|attribute| attribute.key == "executor_tip_amount")
+                .unwrap();
+            assert_ne!(executor_tip.value, "0");
+        })
+        .query_dca_orders(Some(user.to_string()), None, None, |result| {
+            assert!(result.unwrap().orders.is_empty());
+        })
+        .query_balance(&user.to_string(), DENOM_UUSD, |result| {
+            // roughly 10 uusd minus fees and tips
+            let received = result.unwrap().amount - *user_balance.borrow();
+            assert!(received > Uint128::new(ONE_MILLION * 9));
+            assert!(received < Uint128::new(ONE_MILLION * 10));
+        })
+        .query_balance(&executor.to_string(), DENOM_UUSD, |result| {
+            assert!(result.unwrap().amount > Uint128::new(ONE_HUNDRED_TRILLION));
+        })
+        .execute_dca_order(&executor, 1, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::DcaOrderNotFound { order_id } => assert_eq!(order_id, 1),
+                _ => panic!("Wrong error type, should return ContractError::DcaOrderNotFound"),
+            }
+        });
+
+    // a tranche that doesn't meet the min price fails, and the order can be cancelled
+    let user_uluna_balance = RefCell::new(Uint128::zero());
+    suite
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations.clone(),
+            ONE_DAY_IN_SECONDS,
+            2,
+            Some(Decimal::percent(200)),
+            None,
+            Some(Decimal::percent(5)),
+            vec![coin(ONE_MILLION * 10, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .execute_dca_order(&executor, 2, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::DcaMinPriceNotMet { .. } => {}
+                _ => panic!("Wrong error type, should return ContractError::DcaMinPriceNotMet"),
+            }
+        })
+        .query_balance(&user.to_string(), DENOM_ULUNA, |result| {
+            *user_uluna_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .cancel_dca_order(&executor, 2, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .cancel_dca_order(&user, 2, |result| {
+            result.unwrap();
+        })
+        .query_balance(&user.to_string(), DENOM_ULUNA, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *user_uluna_balance.borrow() + Uint128::new(ONE_MILLION * 10)
+            );
+        });
+
+    // without a max slippage, the tranches use the default slippage, which the 2% pool fees exceed
+    suite
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations.clone(),
+            ONE_DAY_IN_SECONDS,
+            2,
+            None,
+            None,
+            None,
+            vec![coin(ONE_MILLION * 10, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_dca_orders(Some(user.to_string()), None, None, |result| {
+            let orders = result.unwrap().orders;
+            assert_eq!(orders.len(), 1);
+            assert_eq!(orders[0].max_slippage, Decimal::percent(1));
+        })
+        .execute_dca_order(&executor, 3, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            assert_eq!(
+                err,
+                ContractError::Std(StdError::generic_err("Slippage limit exceeded"))
+            );
+        })
+        .cancel_dca_order(&user, 3, |result| {
+            result.unwrap();
+        });
+
+    // the max slippage of the tranches is capped way below the one of regular swaps
+    suite
+        .create_dca_order(
+            &user,
+            DENOM_UUSD.to_string(),
+            operations,
+            ONE_DAY_IN_SECONDS,
+            2,
+            None,
+            None,
+            Some(Decimal::percent(40)),
+            vec![coin(ONE_MILLION * 10, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_dca_orders(Some(user.to_string()), None, None, |result| {
+            let orders = result.unwrap().orders;
+            assert_eq!(orders.len(), 1);
+            assert_eq!(orders[0].max_slippage, Decimal::percent(5));
+        });
+}
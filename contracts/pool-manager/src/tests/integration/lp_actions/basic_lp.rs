@@ -21,7 +21,9 @@ const PUSDC_DENOM: &str = "pusdc";
 
 const TEN_THOUSAND: Uint128 = Uint128::new(10_000u128);
 const NINE_NINE_NINE_THOUSAND: Uint128 = Uint128::new(999_000u128);
+#[allow(clippy::inconsistent_digit_grouping)]
 const LP_AMOUNT_18_DECIMALS: Uint128 = Uint128::new(300_000_000_000_000_000000000000000000u128);
+#[allow(clippy::inconsistent_digit_grouping)]
 const INITIAL_BALANCE_STABLESWAP: Uint128 = Uint128::new(1_000_00000000000000u128);
 const LIQUIDITY_ADD_UUSDC_STABLESWAP: Uint128 = Uint128::new(10u128.pow(3));
 const LIQUIDITY_ADD_UUSDT_STABLESWAP: Uint128 = Uint128::new(10u128.pow(15));
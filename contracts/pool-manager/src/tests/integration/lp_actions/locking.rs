@@ -3,10 +3,10 @@ use std::cell::RefCell;
 use cosmwasm_std::{coin, Coin, Decimal, Uint128};
 use mantra_common_testing::multi_test::stargate_mock::StargateMock;
 use mantra_dex_std::{
-    farm_manager::{Position, PositionsBy},
+    farm_manager::{FarmAction, FarmParams, Position, PositionsBy},
     fee::{Fee, PoolFee},
     lp_common::MINIMUM_LIQUIDITY_AMOUNT,
-    pool_manager::PoolType,
+    pool_manager::{PoolType, SwapOperation},
 };
 use test_utils::common_constants::{
     DECIMALS_6, DENOM_ULUNA, DENOM_UOM, DENOM_UUSD, DENOM_UWHALE, ONE_MILLION, ONE_THOUSAND,
@@ -29,6 +29,8 @@ const EXPECTED_SHARES_AFTER_1M_LIQUIDITY: u128 = 999_000u128; // LIQUIDITY_AMOUN
 const DEFAULT_ASSET_DECIMALS: u8 = DECIMALS_6;
 const WHALE_ULUNA_POOL_LABEL: &str = "whale.uluna";
 const ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA: &str = "o.whale.uluna"; // Used to derive LP token name
+const WHALE_UUSD_POOL_LABEL: &str = "whale.uusd";
+const ORIGINAL_POOL_IDENTIFIER_WHALE_UUSD: &str = "o.whale.uusd";
 
 // Locking & Position Parameters
 const UNLOCK_DURATION_ONE_DAY: u64 = 86_400u64;
@@ -711,3 +713,754 @@ fn provide_liquidity_locking_lp_reusing_position_identifier_2() {
         },
     );
 }
+
+#[test]
+fn claim_and_compound_farm_rewards_into_position() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_LARGE_BALANCE, DENOM_UWHALE.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_ULUNA.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UUSD.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    let asset_denoms = vec![DENOM_UWHALE.to_string(), DENOM_ULUNA.to_string()];
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::zero(),
+        },
+        swap_fee: Fee {
+            share: Decimal::zero(),
+        },
+        burn_fee: Fee {
+            share: Decimal::zero(),
+        },
+        extra_fees: vec![],
+    };
+
+    suite.instantiate_default().add_one_epoch().create_pool(
+        &creator,
+        asset_denoms,
+        vec![DEFAULT_ASSET_DECIMALS, DEFAULT_ASSET_DECIMALS],
+        pool_fees,
+        PoolType::ConstantProduct,
+        Some(WHALE_ULUNA_POOL_LABEL.to_string()),
+        vec![
+            coin(POOL_CREATION_FEE_UUSD_AMOUNT, DENOM_UUSD),
+            coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+        ],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    let lp_denom = suite.get_lp_denom(ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string());
+
+    suite
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            Some(UNLOCK_DURATION_ONE_DAY),
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // a farm rewarding one of the pool assets, which gets compounded
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(14_000, DENOM_UWHALE),
+                    farm_identifier: Some(FARM_IDENTIFIER.to_string()),
+                },
+            },
+            vec![coin(14_000, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // a farm rewarding an asset that is not in the pool, which is sent to the user instead.
+        // The extra uwhale sent is the refunded creation fee
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(1_400, DENOM_UUSD),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(1_400, DENOM_UUSD), coin(1, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .add_one_epoch()
+        .claim_and_compound(
+            &other,
+            POSITION_IDENTIFIER_1.to_string(),
+            None,
+            None,
+            |result| {
+                let err = result
+                    .unwrap_err()
+                    .downcast::<farm_manager::ContractError>()
+                    .unwrap();
+                match err {
+                    farm_manager::ContractError::Unauthorized => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        );
+
+    let uusd_balance = RefCell::new(Uint128::zero());
+
+    suite
+        .query_balance(&creator.to_string(), DENOM_UUSD, |result| {
+            *uusd_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            Some(Decimal::percent(5)),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the uwhale rewards were turned into LP and locked into the position
+        .query_farm_positions(
+            Some(PositionsBy::Identifier(POSITION_IDENTIFIER_1.to_string())),
+            None,
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 1);
+                assert_eq!(positions[0].lp_asset.denom, lp_denom);
+                assert!(
+                    positions[0].lp_asset.amount
+                        > Uint128::from(EXPECTED_SHARES_AFTER_1M_LIQUIDITY)
+                );
+            },
+        )
+        // the uusd rewards were sent to the user
+        .query_balance(&creator.to_string(), DENOM_UUSD, |result| {
+            assert!(result.unwrap().amount > *uusd_balance.borrow());
+        });
+}
+
+#[test]
+fn claim_and_compound_sends_rewards_too_small_to_compound() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_LARGE_BALANCE, DENOM_UWHALE.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_ULUNA.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UUSD.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::zero(),
+        },
+        swap_fee: Fee {
+            share: Decimal::zero(),
+        },
+        burn_fee: Fee {
+            share: Decimal::zero(),
+        },
+        extra_fees: vec![],
+    };
+
+    suite.instantiate_default().add_one_epoch().create_pool(
+        &creator,
+        vec![DENOM_UWHALE.to_string(), DENOM_ULUNA.to_string()],
+        vec![DEFAULT_ASSET_DECIMALS, DEFAULT_ASSET_DECIMALS],
+        pool_fees,
+        PoolType::ConstantProduct,
+        Some(WHALE_ULUNA_POOL_LABEL.to_string()),
+        vec![
+            coin(POOL_CREATION_FEE_UUSD_AMOUNT, DENOM_UUSD),
+            coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+        ],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    let lp_denom = suite.get_lp_denom(ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string());
+
+    // the creator's position is tiny compared to the other's, so it earns a couple of uwhale
+    suite
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            Some(UNLOCK_DURATION_ONE_DAY),
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_2K * 50, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_2K * 50, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &other,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            Some(UNLOCK_DURATION_ONE_DAY),
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M * 5, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M * 5, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(ONE_THOUSAND, DENOM_UWHALE),
+                    farm_identifier: Some(FARM_IDENTIFIER.to_string()),
+                },
+            },
+            vec![coin(ONE_THOUSAND, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .add_one_epoch();
+
+    let position_amount = RefCell::new(Uint128::zero());
+    let uwhale_balance = RefCell::new(Uint128::zero());
+
+    suite
+        .query_farm_positions(
+            Some(PositionsBy::Identifier(POSITION_IDENTIFIER_1.to_string())),
+            None,
+            None,
+            None,
+            |result| {
+                *position_amount.borrow_mut() = result.unwrap().positions[0].lp_asset.amount;
+            },
+        )
+        .query_balance(&creator.to_string(), DENOM_UWHALE, |result| {
+            *uwhale_balance.borrow_mut() = result.unwrap().amount;
+        })
+        // half of the reward would be swapped into nothing, so it is sent to the creator instead of
+        // reverting
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            None,
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_farm_positions(
+            Some(PositionsBy::Identifier(POSITION_IDENTIFIER_1.to_string())),
+            None,
+            None,
+            None,
+            |result| {
+                assert_eq!(
+                    result.unwrap().positions[0].lp_asset.amount,
+                    *position_amount.borrow()
+                );
+            },
+        )
+        .query_balance(&creator.to_string(), DENOM_UWHALE, |result| {
+            assert!(result.unwrap().amount > *uwhale_balance.borrow());
+        });
+}
+
+#[test]
+fn query_farm_apr_values_lp_with_pool_reserves() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_LARGE_BALANCE, DENOM_UWHALE.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_ULUNA.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UUSD.to_string()),
+            coin(INITIAL_SMALL_BALANCE, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+
+    let asset_denoms = vec![DENOM_UWHALE.to_string(), DENOM_ULUNA.to_string()];
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::zero(),
+        },
+        swap_fee: Fee {
+            share: Decimal::zero(),
+        },
+        burn_fee: Fee {
+            share: Decimal::zero(),
+        },
+        extra_fees: vec![],
+    };
+
+    suite.instantiate_default().add_one_epoch().create_pool(
+        &creator,
+        asset_denoms,
+        vec![DEFAULT_ASSET_DECIMALS, DEFAULT_ASSET_DECIMALS],
+        pool_fees,
+        PoolType::ConstantProduct,
+        Some(WHALE_ULUNA_POOL_LABEL.to_string()),
+        vec![
+            coin(POOL_CREATION_FEE_UUSD_AMOUNT, DENOM_UUSD),
+            coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+        ],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    let lp_denom = suite.get_lp_denom(ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string());
+
+    suite
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            Some(UNLOCK_DURATION_ONE_DAY),
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(14_000, DENOM_UWHALE),
+                    farm_identifier: Some(FARM_IDENTIFIER.to_string()),
+                },
+            },
+            vec![coin(14_000, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(1_400, DENOM_UUSD),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(1_400, DENOM_UUSD), coin(1, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // a farm paying in the LP denom itself, like the penalty farms do. The LP for it is not
+        // locked, so it doesn't add to the weight
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_2K * 10, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_2K * 10, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(14_000, &lp_denom),
+                    farm_identifier: Some(USER_FARM_IDENTIFIER.to_string()),
+                },
+            },
+            vec![coin(14_000, &lp_denom), coin(1, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .query_farm_apr(&lp_denom, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.lp_denom, lp_denom);
+            assert_eq!(
+                response.total_lp_weight,
+                Uint128::new(EXPECTED_SHARES_AFTER_1M_LIQUIDITY)
+            );
+            assert_eq!(response.farms.len(), 3);
+
+            // 1_000 uwhale a day over ~1M LP, each worth 2 uwhale given the 1:1 reserves
+            let uwhale_farm = response
+                .farms
+                .iter()
+                .find(|farm| farm.emission.denom == DENOM_UWHALE)
+                .unwrap();
+            assert_eq!(uwhale_farm.emission.amount, Uint128::new(1_000));
+            let apr = uwhale_farm.apr.unwrap();
+            assert!(apr > Decimal::percent(18) && apr < Decimal::percent(19));
+
+            // uusd is not in the pool, so the LP can't be valued in terms of it
+            let uusd_farm = response
+                .farms
+                .iter()
+                .find(|farm| farm.emission.denom == DENOM_UUSD)
+                .unwrap();
+            assert_eq!(uusd_farm.emission.amount, Uint128::new(100));
+            assert_eq!(uusd_farm.apr, None);
+
+            // 1_000 LP a day over ~1M LP, each LP being worth one LP
+            let lp_farm = response
+                .farms
+                .iter()
+                .find(|farm| farm.emission.denom == lp_denom)
+                .unwrap();
+            assert_eq!(lp_farm.emission.amount, Uint128::new(1_000));
+            let apr = lp_farm.apr.unwrap();
+            assert!(apr > Decimal::percent(36) && apr < Decimal::percent(37));
+        });
+}
+
+#[test]
+fn claim_and_compound_swaps_non_pool_rewards_into_pool_assets() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_LARGE_BALANCE, DENOM_UWHALE.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_ULUNA.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_UUSD.to_string()),
+            coin(INITIAL_LARGE_BALANCE, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::zero(),
+        },
+        swap_fee: Fee {
+            share: Decimal::zero(),
+        },
+        burn_fee: Fee {
+            share: Decimal::zero(),
+        },
+        extra_fees: vec![],
+    };
+
+    suite
+        .instantiate_default()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_UWHALE.to_string(), DENOM_ULUNA.to_string()],
+            vec![DEFAULT_ASSET_DECIMALS, DEFAULT_ASSET_DECIMALS],
+            pool_fees.clone(),
+            PoolType::ConstantProduct,
+            Some(WHALE_ULUNA_POOL_LABEL.to_string()),
+            vec![
+                coin(POOL_CREATION_FEE_UUSD_AMOUNT, DENOM_UUSD),
+                coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_pool(
+            &creator,
+            vec![DENOM_UWHALE.to_string(), DENOM_UUSD.to_string()],
+            vec![DEFAULT_ASSET_DECIMALS, DEFAULT_ASSET_DECIMALS],
+            pool_fees,
+            PoolType::ConstantProduct,
+            Some(WHALE_UUSD_POOL_LABEL.to_string()),
+            vec![
+                coin(POOL_CREATION_FEE_UUSD_AMOUNT, DENOM_UUSD),
+                coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let lp_denom = suite.get_lp_denom(ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string());
+
+    suite
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_ULUNA.to_string(),
+            Some(UNLOCK_DURATION_ONE_DAY),
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_ULUNA),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            ORIGINAL_POOL_IDENTIFIER_WHALE_UUSD.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UWHALE),
+                coin(LIQUIDITY_AMOUNT_1M, DENOM_UUSD),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // a farm rewarding an asset that is not in the pool. The extra uwhale sent is the refunded
+        // creation fee
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(14_000, DENOM_UUSD),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(14_000, DENOM_UUSD), coin(1, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .add_one_epoch();
+
+    let uusd_to_uwhale = vec![SwapOperation::MantraSwap {
+        token_in_denom: DENOM_UUSD.to_string(),
+        token_out_denom: DENOM_UWHALE.to_string(),
+        pool_identifier: ORIGINAL_POOL_IDENTIFIER_WHALE_UUSD.to_string(),
+    }];
+    let uwhale_to_uusd = vec![SwapOperation::MantraSwap {
+        token_in_denom: DENOM_UWHALE.to_string(),
+        token_out_denom: DENOM_UUSD.to_string(),
+        pool_identifier: ORIGINAL_POOL_IDENTIFIER_WHALE_UUSD.to_string(),
+    }];
+
+    // the rewards can only be swapped into an asset of the pool, and only once
+    suite
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            None,
+            Some(vec![uwhale_to_uusd]),
+            |result| {
+                let err = result
+                    .unwrap_err()
+                    .downcast::<farm_manager::ContractError>()
+                    .unwrap();
+                match err {
+                    farm_manager::ContractError::InvalidRewardSwapOperations { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidRewardSwapOperations"
+                    ),
+                }
+            },
+        )
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            None,
+            Some(vec![uusd_to_uwhale.clone(), uusd_to_uwhale.clone()]),
+            |result| {
+                let err = result
+                    .unwrap_err()
+                    .downcast::<farm_manager::ContractError>()
+                    .unwrap();
+                match err {
+                    farm_manager::ContractError::InvalidRewardSwapOperations { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidRewardSwapOperations"
+                    ),
+                }
+            },
+        );
+
+    let uusd_balance = RefCell::new(Uint128::zero());
+    let farm_manager_uwhale_balance = RefCell::new(Uint128::zero());
+    let farm_manager = suite.farm_manager_addr.to_string();
+
+    suite
+        .query_balance(&creator.to_string(), DENOM_UUSD, |result| {
+            *uusd_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .query_balance(&farm_manager, DENOM_UWHALE, |result| {
+            *farm_manager_uwhale_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            None,
+            Some(vec![uusd_to_uwhale.clone()]),
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the uusd rewards were swapped into uwhale, turned into LP and locked into the position
+        .query_farm_positions(
+            Some(PositionsBy::Identifier(POSITION_IDENTIFIER_1.to_string())),
+            None,
+            None,
+            None,
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 1);
+                assert!(
+                    positions[0].lp_asset.amount
+                        > Uint128::from(EXPECTED_SHARES_AFTER_1M_LIQUIDITY)
+                );
+            },
+        )
+        .query_balance(&creator.to_string(), DENOM_UUSD, |result| {
+            assert_eq!(result.unwrap().amount, *uusd_balance.borrow());
+        })
+        // nothing is left behind in the farm manager
+        .query_balance(&farm_manager, DENOM_UWHALE, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *farm_manager_uwhale_balance.borrow()
+            );
+        });
+
+    // claim operators can't compound with a slippage higher than the default one. The rewards of
+    // the bigger farm move the price of the pool way more than that
+    suite
+        .approve_claim_operator(&creator, &other, |result| {
+            result.unwrap();
+        })
+        .add_one_epoch()
+        .manage_farm(
+            &creator,
+            FarmAction::Create {
+                params: FarmParams {
+                    lp_denom: lp_denom.clone(),
+                    start_epoch: None,
+                    preliminary_end_epoch: None,
+                    curve: None,
+                    farm_asset: coin(LIQUIDITY_AMOUNT_1M, DENOM_UUSD),
+                    farm_identifier: None,
+                },
+            },
+            vec![coin(LIQUIDITY_AMOUNT_1M, DENOM_UUSD), coin(1, DENOM_UWHALE)],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .add_one_epoch()
+        .add_one_epoch()
+        .claim_and_compound(
+            &other,
+            POSITION_IDENTIFIER_1.to_string(),
+            Some(Decimal::percent(50)),
+            Some(vec![uusd_to_uwhale.clone()]),
+            |result| {
+                let err = result.unwrap_err().root_cause().to_string();
+                assert_eq!(err, "Generic error: Slippage limit exceeded");
+            },
+        )
+        // while the receiver of the position can
+        .claim_and_compound(
+            &creator,
+            POSITION_IDENTIFIER_1.to_string(),
+            Some(Decimal::percent(50)),
+            Some(vec![uusd_to_uwhale]),
+            |result| {
+                result.unwrap();
+            },
+        );
+}
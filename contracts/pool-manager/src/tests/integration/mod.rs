@@ -1,6 +1,8 @@
 pub mod basic_tests;
+pub mod dca;
 pub mod helpers;
 pub mod lp_actions;
+pub mod orders;
 pub mod ownership;
 pub mod pool_management;
 pub mod query;
@@ -0,0 +1,417 @@
+use std::cell::RefCell;
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use mantra_common_testing::multi_test::stargate_mock::StargateMock;
+use mantra_dex_std::fee::{Fee, PoolFee};
+use mantra_dex_std::pool_manager::PoolType;
+use test_utils::common_constants::{
+    DECIMALS_6, DENOM_ULUNA, DENOM_UOM, DENOM_UUSD, ONE_BILLION, ONE_HUNDRED_TRILLION, ONE_MILLION,
+    ONE_THOUSAND, STARGATE_MOCK_UOM_AMOUNT,
+};
+
+use crate::tests::suite::TestingSuite;
+use crate::ContractError;
+
+const ULUNA_UUSD_POOL_RAW: &str = "uluna.uusd";
+const ULUNA_UUSD_POOL_ID: &str = "o.uluna.uusd";
+
+#[test]
+fn place_fill_and_cancel_limit_orders() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(ONE_HUNDRED_TRILLION, DENOM_ULUNA.to_string()),
+            coin(ONE_HUNDRED_TRILLION, DENOM_UUSD.to_string()),
+            coin(10_000u128, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let user = suite.senders[1].clone();
+    let keeper = suite.senders[2].clone();
+    let pool_id = ULUNA_UUSD_POOL_ID.to_string();
+
+    suite
+        .instantiate_default()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_ULUNA.to_string(), DENOM_UUSD.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            PoolFee {
+                protocol_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                swap_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                burn_fee: Fee {
+                    share: Decimal::zero(),
+                },
+                extra_fees: vec![],
+            },
+            PoolType::ConstantProduct,
+            Some(ULUNA_UUSD_POOL_RAW.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_UUSD),
+                coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            pool_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_BILLION, DENOM_ULUNA.to_string()),
+                coin(ONE_BILLION, DENOM_UUSD.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // invalid orders
+    suite
+        .place_order(
+            &user,
+            pool_id.clone(),
+            DENOM_UUSD.to_string(),
+            Decimal::zero(),
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidLimitOrderPrice => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidLimitOrderPrice"
+                    ),
+                }
+            },
+        )
+        .place_order(
+            &user,
+            pool_id.clone(),
+            DENOM_UUSD.to_string(),
+            Decimal::one(),
+            Some(Decimal::one()),
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidKeeperTip { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidKeeperTip"),
+                }
+            },
+        )
+        .place_order(
+            &user,
+            pool_id.clone(),
+            DENOM_ULUNA.to_string(),
+            Decimal::one(),
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::SameAsset => {}
+                    _ => panic!("Wrong error type, should return ContractError::SameAsset"),
+                }
+            },
+        );
+
+    // sell 1 uluna for at least 1.2 uusd, with a 0.1% tip for the keeper
+    suite
+        .place_order(
+            &user,
+            pool_id.clone(),
+            DENOM_UUSD.to_string(),
+            Decimal::percent(120),
+            Some(Decimal::permille(1)),
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_orders(Some(user.to_string()), None, None, None, None, |result| {
+            let orders = result.unwrap().orders;
+            assert_eq!(orders.len(), 1);
+            assert_eq!(orders[0].order_id, 1);
+            assert_eq!(orders[0].owner, user);
+            assert_eq!(orders[0].offer_asset, coin(ONE_MILLION, DENOM_ULUNA));
+            assert_eq!(orders[0].price, Decimal::percent(120));
+        })
+        .query_orders(Some(keeper.to_string()), None, None, None, None, |result| {
+            assert!(result.unwrap().orders.is_empty());
+        });
+
+    // the pool price is ~1, so the order can't be filled yet
+    suite.fill_order(&keeper, 1, |result| {
+        let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+        match err {
+            ContractError::LimitOrderNotFillable { .. } => {}
+            _ => panic!("Wrong error type, should return ContractError::LimitOrderNotFillable"),
+        }
+    });
+
+    // someone buys uluna, moving the price across the order's level
+    suite.swap(
+        &creator,
+        DENOM_ULUNA.to_string(),
+        None,
+        Some(Decimal::percent(50)),
+        None,
+        pool_id.clone(),
+        vec![coin(ONE_BILLION / 5, DENOM_UUSD.to_string())],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    let user_balance = RefCell::new(Uint128::zero());
+    let keeper_balance = RefCell::new(Uint128::zero());
+    suite
+        .query_balance(&user.to_string(), DENOM_UUSD, |result| {
+            *user_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .query_balance(&keeper.to_string(), DENOM_UUSD, |result| {
+            *keeper_balance.borrow_mut() = result.unwrap().amount;
+        });
+
+    let filled_amounts = RefCell::new((Uint128::zero(), Uint128::zero()));
+    suite
+        .fill_order(&keeper, 1, |result| {
+            for event in result.unwrap().events {
+                if event.ty == "wasm" {
+                    for attribute in event.attributes {
+                        match attribute.key.as_str() {
+                            "return_amount" => {
+                                filled_amounts.borrow_mut().0 = attribute.value.parse().unwrap()
+                            }
+                            "keeper_tip_amount" => {
+                                filled_amounts.borrow_mut().1 = attribute.value.parse().unwrap()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        })
+        .query_orders(None, None, None, None, None, |result| {
+            assert!(result.unwrap().orders.is_empty());
+        });
+
+    let (owner_amount, keeper_tip_amount) = filled_amounts.into_inner();
+    assert!(owner_amount >= Uint128::new(ONE_MILLION).mul_floor(Decimal::percent(120)));
+    assert!(!keeper_tip_amount.is_zero());
+
+    suite
+        .query_balance(&user.to_string(), DENOM_UUSD, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *user_balance.borrow() + owner_amount
+            );
+        })
+        .query_balance(&keeper.to_string(), DENOM_UUSD, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *keeper_balance.borrow() + keeper_tip_amount
+            );
+        })
+        .fill_order(&keeper, 1, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::LimitOrderNotFound { order_id } => assert_eq!(order_id, 1),
+                _ => panic!("Wrong error type, should return ContractError::LimitOrderNotFound"),
+            }
+        });
+
+    // cancelling an order refunds the escrowed asset to the owner only
+    let user_uluna_balance = RefCell::new(Uint128::zero());
+    suite
+        .place_order(
+            &user,
+            pool_id.clone(),
+            DENOM_UUSD.to_string(),
+            Decimal::percent(500),
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(&user.to_string(), DENOM_ULUNA, |result| {
+            *user_uluna_balance.borrow_mut() = result.unwrap().amount;
+        })
+        .cancel_order(&keeper, 2, |result| {
+            let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+            match err {
+                ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .cancel_order(&user, 2, |result| {
+            result.unwrap();
+        })
+        .query_balance(&user.to_string(), DENOM_ULUNA, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *user_uluna_balance.borrow() + Uint128::new(ONE_MILLION)
+            );
+        })
+        .query_orders(Some(user.to_string()), None, None, None, None, |result| {
+            assert!(result.unwrap().orders.is_empty());
+        });
+}
+
+#[test]
+fn query_limit_orders_by_pool() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(ONE_HUNDRED_TRILLION, DENOM_ULUNA.to_string()),
+            coin(ONE_HUNDRED_TRILLION, DENOM_UUSD.to_string()),
+            coin(10_000u128, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM.to_string())]),
+    );
+    let creator = suite.creator();
+    let user = suite.senders[1].clone();
+    let other = suite.senders[2].clone();
+    let pool_id = ULUNA_UUSD_POOL_ID.to_string();
+
+    suite.instantiate_default().add_one_epoch().create_pool(
+        &creator,
+        vec![DENOM_ULUNA.to_string(), DENOM_UUSD.to_string()],
+        vec![DECIMALS_6, DECIMALS_6],
+        PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::percent(1),
+            },
+            swap_fee: Fee {
+                share: Decimal::percent(1),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![],
+        },
+        PoolType::ConstantProduct,
+        Some(ULUNA_UUSD_POOL_RAW.to_string()),
+        vec![
+            coin(ONE_THOUSAND, DENOM_UUSD),
+            coin(STARGATE_MOCK_UOM_AMOUNT, DENOM_UOM),
+        ],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    for (sender, price) in [
+        (&user, Decimal::percent(150)),
+        (&other, Decimal::percent(110)),
+        (&user, Decimal::percent(130)),
+    ] {
+        suite.place_order(
+            sender,
+            pool_id.clone(),
+            DENOM_UUSD.to_string(),
+            price,
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    // the orders of a pool are sorted by price
+    suite
+        .query_orders(
+            None,
+            Some(pool_id.clone()),
+            Some(DENOM_ULUNA.to_string()),
+            None,
+            None,
+            |result| {
+                let orders = result.unwrap().orders;
+                let order_ids = orders.iter().map(|o| o.order_id).collect::<Vec<_>>();
+                assert_eq!(order_ids, vec![2, 3, 1]);
+            },
+        )
+        .query_orders(
+            None,
+            Some(pool_id.clone()),
+            Some(DENOM_ULUNA.to_string()),
+            Some((Decimal::percent(110), 2)),
+            Some(1),
+            |result| {
+                let orders = result.unwrap().orders;
+                assert_eq!(orders.len(), 1);
+                assert_eq!(orders[0].order_id, 3);
+            },
+        )
+        // the orders on the other side of the pool are not mixed in
+        .query_orders(
+            None,
+            Some(pool_id.clone()),
+            Some(DENOM_UUSD.to_string()),
+            None,
+            None,
+            |result| {
+                assert!(result.unwrap().orders.is_empty());
+            },
+        )
+        .query_orders(None, Some(pool_id.clone()), None, None, None, |result| {
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "Generic error: Querier contract error: The offer denom must be provided to query the orders of a pool"
+            );
+        })
+        // when filtering by owner, the orders are sorted by id
+        .query_orders(
+            Some(user.to_string()),
+            Some(pool_id.clone()),
+            None,
+            None,
+            None,
+            |result| {
+                let orders = result.unwrap().orders;
+                let order_ids = orders.iter().map(|o| o.order_id).collect::<Vec<_>>();
+                assert_eq!(order_ids, vec![1, 3]);
+            },
+        )
+        .query_orders(
+            None,
+            Some("o.uluna.uom".to_string()),
+            Some(DENOM_ULUNA.to_string()),
+            None,
+            None,
+            |result| {
+                assert!(result.unwrap().orders.is_empty());
+            },
+        );
+
+    // the cursor doesn't need the order to be open anymore
+    suite
+        .cancel_order(&other, 2, |result| {
+            result.unwrap();
+        })
+        .query_orders(
+            None,
+            Some(pool_id.clone()),
+            Some(DENOM_ULUNA.to_string()),
+            Some((Decimal::percent(110), 2)),
+            None,
+            |result| {
+                let orders = result.unwrap().orders;
+                let order_ids = orders.iter().map(|o| o.order_id).collect::<Vec<_>>();
+                assert_eq!(order_ids, vec![3, 1]);
+            },
+        );
+}
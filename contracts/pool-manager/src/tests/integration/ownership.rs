@@ -75,9 +75,15 @@ fn checks_ownership_when_updating_config() {
     );
     let unauthorized = suite.senders[2].clone();
 
-    suite
-        .instantiate_default()
-        .update_config(&unauthorized, None, None, None, None, |result| {
+    suite.instantiate_default().update_config(
+        &unauthorized,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        |result| {
             let err = result.unwrap_err().downcast::<ContractError>().unwrap();
 
             match err {
@@ -86,7 +92,8 @@ fn checks_ownership_when_updating_config() {
                     panic!("Wrong error type, should return ContractError::OwnershipError")
                 }
             }
-        });
+        },
+    );
 }
 
 #[test]
@@ -98,6 +105,7 @@ fn updates_config_fields() {
     let creator = suite.creator();
     let other = suite.senders[1].clone();
     let another = suite.senders[2].clone();
+    let yet_another = suite.senders[3].clone();
 
     suite.instantiate_default();
     let current_pool_creation_fee = suite.query_config().pool_creation_fee;
@@ -107,6 +115,7 @@ fn updates_config_fields() {
         &creator,
         Some(other),
         Some(another),
+        Some(yet_another),
         Some(coin(
             current_pool_creation_fee
                 .amount
@@ -116,6 +125,7 @@ fn updates_config_fields() {
             current_pool_creation_fee.denom,
         )),
         None,
+        None,
         |res| {
             res.unwrap();
         },
@@ -125,4 +135,5 @@ fn updates_config_fields() {
     assert_ne!(config.fee_collector_addr, initial_config.fee_collector_addr);
     assert_ne!(config.pool_creation_fee, initial_config.pool_creation_fee);
     assert_ne!(config.farm_manager_addr, initial_config.farm_manager_addr);
+    assert_ne!(config.epoch_manager_addr, initial_config.epoch_manager_addr);
 }
@@ -1894,12 +1894,14 @@ fn lock_single_pool() {
         None,
         None,
         None,
+        None,
         Some(FeatureToggle {
             pool_identifier: LOCK_POOL_PREFIX_1.to_string(),
             withdrawals_enabled: Some(false),
             deposits_enabled: Some(false),
             swaps_enabled: Some(false),
         }),
+        None,
         |res| {
             res.unwrap();
         },
@@ -2023,12 +2025,14 @@ fn lock_single_pool() {
         None,
         None,
         None,
+        None,
         Some(FeatureToggle {
             pool_identifier: LOCK_POOL_PREFIX_1.to_string(),
             withdrawals_enabled: None,
             deposits_enabled: None,
             swaps_enabled: Some(true),
         }),
+        None,
         |res| {
             res.unwrap();
         },
@@ -2142,12 +2146,14 @@ fn cant_toggle_unexisting_pool() {
         None,
         None,
         None,
+        None,
         Some(FeatureToggle {
             pool_identifier: "xxx".to_string(),
             withdrawals_enabled: Some(false),
             deposits_enabled: Some(false),
             swaps_enabled: Some(false),
         }),
+        None,
         |res| {
             let err = res.unwrap_err().downcast::<ContractError>().unwrap();
             match err {
@@ -1,10 +1,10 @@
 use std::cell::RefCell;
 
 use super::super::suite::TestingSuite;
-use cosmwasm_std::{assert_approx_eq, coin, Coin, Decimal, Uint128};
+use cosmwasm_std::{assert_approx_eq, coin, Coin, Decimal, Decimal256, Uint128};
 use mantra_common_testing::multi_test::stargate_mock::StargateMock;
 use mantra_dex_std::fee::{Fee, PoolFee};
-use mantra_dex_std::pool_manager::{PoolType, SwapOperation};
+use mantra_dex_std::pool_manager::{FeatureToggle, PoolType, SwapOperation};
 use test_utils::common_constants::{
     DECIMALS_6, DENOM_ULUNA as DENOM_LUNA, DENOM_UOM as DENOM_OM, DENOM_UUSD as DENOM_USD,
     DENOM_UUSDC as DENOM_USDC, DENOM_UUSDT as DENOM_USDT, DENOM_UWHALE as DENOM_WHALE,
@@ -1067,3 +1067,572 @@ fn reverse_simulate_swap_operations_query_verification() {
         },
     );
 }
+
+#[test]
+fn pool_stats_are_tracked_per_epoch() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_WHALE.to_string()),
+            coin(INITIAL_BALANCE, DENOM_LUNA.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_USD.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_OM.to_string()),
+        ],
+        StargateMock::new(vec![coin(OM_STARGATE_BALANCE, DENOM_OM.to_string())]),
+    );
+    let creator = suite.creator();
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::percent(PROTOCOL_FEE_PERCENT),
+        },
+        swap_fee: Fee {
+            share: Decimal::percent(SWAP_FEE_PERCENT),
+        },
+        burn_fee: Fee {
+            share: Decimal::percent(BURN_FEE_PERCENT),
+        },
+        extra_fees: vec![Fee {
+            share: Decimal::percent(EXTRA_FEE_PERCENT),
+        }],
+    };
+
+    suite
+        .instantiate_default()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_WHALE.to_string(), DENOM_LUNA.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            pool_fees,
+            PoolType::ConstantProduct,
+            Some(POOL_ID_WHALE_LUNA.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_USD),
+                coin(OM_STARGATE_BALANCE, DENOM_OM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_MILLION, DENOM_WHALE.to_string()),
+                coin(ONE_MILLION, DENOM_LUNA.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // no swaps yet, the stats are empty
+    suite.query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), None, |result| {
+        let response = result.unwrap();
+        assert_eq!(response.stats.swap_count, 0);
+        assert!(response.stats.volume_in.is_empty());
+        assert!(response.stats.volume_out.is_empty());
+    });
+
+    let simulation = RefCell::new(None);
+    suite.query_simulation(
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        coin(ONE_THOUSAND, DENOM_WHALE),
+        DENOM_LUNA.to_string(),
+        |result| {
+            *simulation.borrow_mut() = Some(result.unwrap());
+        },
+    );
+    let simulation = simulation.into_inner().unwrap();
+
+    // the pool fees add up to 10%, leave room for the spread on top of them
+    suite.swap(
+        &creator,
+        DENOM_LUNA.to_string(),
+        None,
+        Some(Decimal::percent(20)),
+        None,
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        vec![coin(ONE_THOUSAND, DENOM_WHALE.to_string())],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    suite.query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), Some(1), |result| {
+        let response = result.unwrap();
+        assert_eq!(response.epoch, Some(1));
+        assert_eq!(response.stats.swap_count, 1);
+        assert_eq!(
+            response.stats.volume_in,
+            vec![coin(ONE_THOUSAND, DENOM_WHALE)]
+        );
+        assert_eq!(
+            response.stats.volume_out,
+            vec![coin(simulation.return_amount.u128(), DENOM_LUNA)]
+        );
+        assert_eq!(
+            response.stats.swap_fees,
+            vec![coin(simulation.swap_fee_amount.u128(), DENOM_LUNA)]
+        );
+        assert_eq!(
+            response.stats.protocol_fees,
+            vec![coin(simulation.protocol_fee_amount.u128(), DENOM_LUNA)]
+        );
+        assert_eq!(
+            response.stats.burn_fees,
+            vec![coin(simulation.burn_fee_amount.u128(), DENOM_LUNA)]
+        );
+        assert_eq!(
+            response.stats.extra_fees,
+            vec![coin(simulation.extra_fees_amount.u128(), DENOM_LUNA)]
+        );
+    });
+
+    // swap in the other direction on the next epoch
+    suite.add_one_epoch().swap(
+        &creator,
+        DENOM_WHALE.to_string(),
+        None,
+        Some(Decimal::percent(20)),
+        None,
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        vec![coin(ONE_THOUSAND, DENOM_LUNA.to_string())],
+        |result| {
+            result.unwrap();
+        },
+    );
+
+    suite
+        .query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), Some(2), |result| {
+            let response = result.unwrap();
+            assert_eq!(response.stats.swap_count, 1);
+            assert_eq!(
+                response.stats.volume_in,
+                vec![coin(ONE_THOUSAND, DENOM_LUNA)]
+            );
+        })
+        .query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.epoch, None);
+            assert_eq!(response.stats.swap_count, 2);
+            assert_eq!(response.stats.volume_in.len(), 2);
+            assert!(response
+                .stats
+                .volume_in
+                .contains(&coin(ONE_THOUSAND, DENOM_LUNA)));
+            assert!(response
+                .stats
+                .volume_in
+                .contains(&coin(ONE_THOUSAND, DENOM_WHALE)));
+        })
+        .query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), Some(3), |result| {
+            assert_eq!(result.unwrap().stats.swap_count, 0);
+        })
+        .query_pool_stats("o.non.existent".to_string(), None, |result| {
+            assert!(result.is_err());
+        });
+}
+
+#[test]
+fn pool_stats_are_not_tracked_per_epoch_without_epoch_manager() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_WHALE.to_string()),
+            coin(INITIAL_BALANCE, DENOM_LUNA.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_USD.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_OM.to_string()),
+        ],
+        StargateMock::new(vec![coin(OM_STARGATE_BALANCE, DENOM_OM.to_string())]),
+    );
+    let creator = suite.creator();
+
+    suite
+        .instantiate_default_without_epoch_manager()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_WHALE.to_string(), DENOM_LUNA.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            PoolFee {
+                protocol_fee: Fee {
+                    share: Decimal::zero(),
+                },
+                swap_fee: Fee {
+                    share: Decimal::zero(),
+                },
+                burn_fee: Fee {
+                    share: Decimal::zero(),
+                },
+                extra_fees: vec![],
+            },
+            PoolType::ConstantProduct,
+            Some(POOL_ID_WHALE_LUNA.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_USD),
+                coin(OM_STARGATE_BALANCE, DENOM_OM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_MILLION, DENOM_WHALE.to_string()),
+                coin(ONE_MILLION, DENOM_LUNA.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .swap(
+            &creator,
+            DENOM_LUNA.to_string(),
+            None,
+            Some(Decimal::percent(20)),
+            None,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            vec![coin(ONE_THOUSAND, DENOM_WHALE.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // only the lifetime stats are recorded
+    suite
+        .query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.stats.swap_count, 1);
+            assert_eq!(
+                response.stats.volume_in,
+                vec![coin(ONE_THOUSAND, DENOM_WHALE)]
+            );
+        })
+        .query_pool_stats(POOL_IDENTIFIER_WHALE_LUNA.to_string(), Some(1), |result| {
+            assert_eq!(result.unwrap().stats.swap_count, 0);
+        });
+}
+
+#[test]
+fn simulate_liquidity_queries_match_execution() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_WHALE.to_string()),
+            coin(INITIAL_BALANCE, DENOM_LUNA.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_USD.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_OM.to_string()),
+        ],
+        StargateMock::new(vec![coin(OM_STARGATE_BALANCE, DENOM_OM.to_string())]),
+    );
+    let creator = suite.creator();
+    let other = suite.senders[1].clone();
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::percent(PROTOCOL_FEE_PERCENT),
+        },
+        swap_fee: Fee {
+            share: Decimal::percent(SWAP_FEE_PERCENT),
+        },
+        burn_fee: Fee {
+            share: Decimal::percent(BURN_FEE_PERCENT),
+        },
+        extra_fees: vec![],
+    };
+
+    suite
+        .instantiate_default()
+        .create_pool(
+            &creator,
+            vec![DENOM_WHALE.to_string(), DENOM_LUNA.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            pool_fees,
+            PoolType::ConstantProduct,
+            Some(POOL_ID_WHALE_LUNA.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_USD),
+                coin(OM_STARGATE_BALANCE, DENOM_OM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_withdrawal(
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            Uint128::new(ONE_THOUSAND),
+            |result| {
+                // nothing to withdraw from an empty pool
+                assert!(result.is_err());
+            },
+        );
+
+    let lp_denom = suite.get_lp_denom(POOL_IDENTIFIER_WHALE_LUNA.to_string());
+
+    // the initial provision mints the minimum liquidity amount to the contract
+    let simulation = RefCell::new(None);
+    suite.query_simulate_provide_liquidity(
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        vec![
+            coin(ONE_MILLION, DENOM_WHALE.to_string()),
+            coin(ONE_MILLION, DENOM_LUNA.to_string()),
+        ],
+        |result| {
+            *simulation.borrow_mut() = Some(result.unwrap());
+        },
+    );
+    let simulation = simulation.into_inner().unwrap();
+    assert_eq!(simulation.slippage, Decimal256::zero());
+    assert!(simulation.swap_offer_asset.is_none());
+
+    suite
+        .provide_liquidity(
+            &creator,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_MILLION, DENOM_WHALE.to_string()),
+                coin(ONE_MILLION, DENOM_LUNA.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(&creator.to_string(), lp_denom.clone(), |result| {
+            assert_eq!(result.unwrap(), simulation.lp_asset);
+        });
+
+    // unbalanced provision, the slippage is reported
+    suite.query_simulate_provide_liquidity(
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        vec![
+            coin(ONE_THOUSAND, DENOM_WHALE.to_string()),
+            coin(ONE_THOUSAND * 2, DENOM_LUNA.to_string()),
+        ],
+        |result| {
+            assert_eq!(result.unwrap().slippage, Decimal256::percent(50));
+        },
+    );
+
+    // single asset provision, half of the deposit is swapped first
+    let simulation = RefCell::new(None);
+    suite.query_simulate_provide_liquidity(
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        vec![coin(ONE_THOUSAND * 10, DENOM_WHALE.to_string())],
+        |result| {
+            *simulation.borrow_mut() = Some(result.unwrap());
+        },
+    );
+    let simulation = simulation.into_inner().unwrap();
+    assert_eq!(
+        simulation.swap_offer_asset,
+        Some(coin(ONE_THOUSAND * 5, DENOM_WHALE))
+    );
+    assert!(simulation.swap_simulation.is_some());
+
+    suite
+        .provide_liquidity(
+            &other,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            None,
+            None,
+            None,
+            // the pool fees add up to 6%
+            Some(Decimal::percent(10)),
+            None,
+            vec![coin(ONE_THOUSAND * 10, DENOM_WHALE.to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_balance(&other.to_string(), lp_denom.clone(), |result| {
+            assert_eq!(result.unwrap(), simulation.lp_asset);
+        });
+
+    // the withdrawal simulation matches the assets refunded on withdrawal
+    let lp_amount = simulation.lp_asset.amount;
+    let refund_assets = RefCell::new(vec![]);
+    suite.query_simulate_withdrawal(
+        POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+        lp_amount,
+        |result| {
+            *refund_assets.borrow_mut() = result.unwrap().refund_assets;
+        },
+    );
+    let refund_assets = refund_assets.into_inner();
+    assert_eq!(refund_assets.len(), 2);
+
+    let balances_before = RefCell::new(vec![]);
+    suite.query_all_balances(&other.to_string(), |result| {
+        *balances_before.borrow_mut() = result.unwrap();
+    });
+
+    suite
+        .withdraw_liquidity(
+            &other,
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            vec![coin(lp_amount.u128(), lp_denom.clone())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_all_balances(&other.to_string(), |result| {
+            let balances = result.unwrap();
+            for refund in refund_assets.iter() {
+                let before = balances_before
+                    .borrow()
+                    .iter()
+                    .find(|c| c.denom == refund.denom)
+                    .unwrap()
+                    .amount;
+                let after = balances
+                    .iter()
+                    .find(|c| c.denom == refund.denom)
+                    .unwrap()
+                    .amount;
+                assert_eq!(after - before, refund.amount);
+            }
+        });
+
+    suite.query_simulate_withdrawal(
+        "o.non.existent".to_string(),
+        Uint128::new(ONE_THOUSAND),
+        |result| {
+            assert!(result.is_err());
+        },
+    );
+
+    // deposits disabled, the provision can't be simulated either
+    suite
+        .update_config(
+            &creator,
+            None,
+            None,
+            None,
+            None,
+            Some(FeatureToggle {
+                pool_identifier: POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+                withdrawals_enabled: None,
+                deposits_enabled: Some(false),
+                swaps_enabled: None,
+            }),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_simulate_provide_liquidity(
+            POOL_IDENTIFIER_WHALE_LUNA.to_string(),
+            vec![
+                coin(ONE_THOUSAND, DENOM_WHALE.to_string()),
+                coin(ONE_THOUSAND, DENOM_LUNA.to_string()),
+            ],
+            |result| {
+                let err = result.unwrap_err().to_string();
+                assert!(err.contains("provide_liquidity"), "{err}");
+            },
+        );
+}
+
+#[test]
+fn simulate_liquidity_slippage_on_stableswap_is_a_fraction() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_USD.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_USDC.to_string()),
+            coin(INITIAL_BALANCE_PLUS_ONE, DENOM_OM.to_string()),
+        ],
+        StargateMock::new(vec![coin(OM_STARGATE_BALANCE, DENOM_OM.to_string())]),
+    );
+    let creator = suite.creator();
+
+    let pool_fees = PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::zero(),
+        },
+        swap_fee: Fee {
+            share: Decimal::zero(),
+        },
+        burn_fee: Fee {
+            share: Decimal::zero(),
+        },
+        extra_fees: vec![],
+    };
+
+    suite
+        .instantiate_default()
+        .create_pool(
+            &creator,
+            vec![DENOM_USD.to_string(), DENOM_USDC.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            pool_fees,
+            PoolType::StableSwap {
+                amp: STABLESWAP_AMP_FACTOR,
+            },
+            Some(POOL_ID_USD_USDC.to_string()),
+            vec![
+                coin(ONE_THOUSAND, DENOM_USD),
+                coin(OM_STARGATE_BALANCE, DENOM_OM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            POOL_IDENTIFIER_USD_USDC.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_MILLION, DENOM_USD.to_string()),
+                coin(ONE_MILLION, DENOM_USDC.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // a balanced provision doesn't slip
+    suite.query_simulate_provide_liquidity(
+        POOL_IDENTIFIER_USD_USDC.to_string(),
+        vec![
+            coin(ONE_THOUSAND, DENOM_USD.to_string()),
+            coin(ONE_THOUSAND, DENOM_USDC.to_string()),
+        ],
+        |result| {
+            assert!(result.unwrap().slippage < Decimal256::permille(1));
+        },
+    );
+
+    // an unbalanced provision slips, but the slippage remains a fraction of the deposit
+    suite.query_simulate_provide_liquidity(
+        POOL_IDENTIFIER_USD_USDC.to_string(),
+        vec![
+            coin(ONE_THOUSAND * 100, DENOM_USD.to_string()),
+            coin(ONE_THOUSAND, DENOM_USDC.to_string()),
+        ],
+        |result| {
+            let slippage = result.unwrap().slippage;
+            assert!(slippage > Decimal256::zero());
+            assert!(slippage < Decimal256::one());
+        },
+    );
+}
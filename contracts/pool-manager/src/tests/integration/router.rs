@@ -1067,6 +1067,21 @@ fn query_swap_operations() {
             assert_eq!(result.return_amount.u128(), EXPECTED_SWAP_RESULT_USD);
         },
     );
+    // the referral fee is taken once, from the output of the last swap
+    suite.query_simulate_swap_operations_with_referral(
+        Uint128::new(ONE_THOUSAND),
+        swap_operations.clone(),
+        Some(Decimal::percent(1)),
+        |result| {
+            let result = result.unwrap();
+            assert_eq!(result.referral_fees.len(), 1);
+            assert_eq!(result.referral_fees[0].denom, DENOM_USD);
+            assert_eq!(
+                result.return_amount + result.referral_fees[0].amount,
+                Uint128::new(EXPECTED_SWAP_RESULT_USD)
+            );
+        },
+    );
     suite.query_reverse_simulate_swap_operations(
         Uint128::new(EXPECTED_SWAP_RESULT_USD),
         swap_operations.clone(),
@@ -1075,6 +1090,18 @@ fn query_swap_operations() {
             assert_approx_eq!(result.offer_amount.u128(), ONE_THOUSAND, "0.006");
         },
     );
+    // the reverse simulation takes the referral fee once too, so more whale is needed
+    suite.query_reverse_simulate_swap_operations_with_referral(
+        Uint128::new(EXPECTED_SWAP_RESULT_USD),
+        swap_operations.clone(),
+        Some(Decimal::percent(1)),
+        |result| {
+            let result = result.unwrap();
+            assert_eq!(result.referral_fees.len(), 1);
+            assert_eq!(result.referral_fees[0].denom, DENOM_USD);
+            assert_approx_eq!(result.offer_amount.u128(), 1_010, "0.006");
+        },
+    );
 
     // execute the swap operations to unbalance the pools
     // sold 10_000 whale for some uusd, so the price of whale should go down
@@ -4,11 +4,12 @@ use std::sync::LazyLock;
 
 use crate::tests::integration::helpers::extract_pool_reserves;
 use crate::tests::suite::TestingSuite;
+use crate::ContractError;
 use cosmwasm_std::{assert_approx_eq, coin, Coin, Decimal, Uint128};
 use mantra_common_testing::multi_test::stargate_mock::StargateMock;
 use mantra_dex_std::fee::{Fee, PoolFee};
 use mantra_dex_std::lp_common::MINIMUM_LIQUIDITY_AMOUNT;
-use mantra_dex_std::pool_manager::{PoolType, SimulationResponse};
+use mantra_dex_std::pool_manager::{PoolType, Referral, SimulationResponse};
 use test_utils::common_constants::{
     DECIMALS_12, DECIMALS_18, DECIMALS_6, DENOM_ULUNA, DENOM_UOM, DENOM_UOSMO, DENOM_UUSD,
     DENOM_UUSDC, DENOM_UUSDT, DENOM_UWHALE, ONE_BILLION, ONE_HUNDRED_TRILLION, ONE_MILLION,
@@ -60,12 +61,10 @@ const BALANCE_UOM_150T_XYK: Uint128 = Uint128::new(150_000_000_000_000_000000u12
 #[allow(clippy::inconsistent_digit_grouping)]
 const BALANCE_AUSDY_100Q_XYK: Uint128 = Uint128::new(100_000_000_000_000_000000000000000000u128);
 #[allow(clippy::inconsistent_digit_grouping)]
-#[allow(clippy::inconsistent_digit_grouping)]
 const SWAP_2Q_AUSDY_XYK: Uint128 = Uint128::new(2_000_000_000_000_000000000000000000u128);
 #[allow(clippy::inconsistent_digit_grouping)]
 const SWAP_10T_UOM_XYK: Uint128 = Uint128::new(10_000_000_000_000_000000u128);
 
-#[allow(clippy::inconsistent_digit_grouping)]
 #[allow(clippy::inconsistent_digit_grouping)]
 const SIMULATED_RETURN_2_852T_UOM_XYK: Uint128 = Uint128::new(2_852_941_176_470_588236u128);
 #[allow(clippy::inconsistent_digit_grouping)]
@@ -77,7 +76,6 @@ const SIMULATED_RETURN_6_296Q_AUSDY_XYK: Uint128 =
 const BALANCE_UUSDC_100T_STABLE: Uint128 = Uint128::new(100_000_000_000_000_000000u128);
 #[allow(clippy::inconsistent_digit_grouping)]
 const BALANCE_AUSDY_100Q_STABLE: Uint128 = Uint128::new(100_000_000_000_000_000000000000000000u128);
-#[allow(clippy::inconsistent_digit_grouping)]
 
 const LIQUIDITY_UUSDC_100T_STABLE: Uint128 = BALANCE_UUSDC_100T_STABLE;
 const LIQUIDITY_AUSDY_100Q_STABLE: Uint128 = BALANCE_AUSDY_100Q_STABLE;
@@ -581,7 +579,7 @@ fn basic_swapping_pool_reserves_event_test() {
             swap_operations_single,
             None,
             None,
-            Some(POOL_RESERVES_DECIMAL_PERCENT_10.clone()),
+            Some(*POOL_RESERVES_DECIMAL_PERCENT_10),
             vec![coin(1_000u128, DENOM_UOM.to_string())],
             |result| {
                 for event in result.unwrap().events {
@@ -645,7 +643,7 @@ fn basic_swapping_pool_reserves_event_test() {
             swap_operations_multi,
             None,
             None,
-            Some(POOL_RESERVES_DECIMAL_PERCENT_10.clone()),
+            Some(*POOL_RESERVES_DECIMAL_PERCENT_10),
             vec![coin(1_000u128, DENOM_UOM.to_string())],
             |result| {
                 let mut pool_identifiers = vec![];
@@ -2573,6 +2571,7 @@ fn simulation_vs_reverse_simulation_3pool() {
 }
 
 #[test]
+#[allow(clippy::inconsistent_digit_grouping)]
 fn belief_price_works_decimals_independent() {
     let mut suite = TestingSuite::default_with_balances(
         vec![
@@ -2830,12 +2829,244 @@ fn compute_offer_amount_floor() {
         );
 }
 
+#[test]
+fn swap_with_referral_fee() {
+    let mut suite = TestingSuite::default_with_balances(
+        vec![
+            coin(ONE_HUNDRED_TRILLION, DENOM_ULUNA.to_string()),
+            coin(ONE_HUNDRED_TRILLION, DENOM_UUSD.to_string()),
+            coin(10_000u128, DENOM_UOM.to_string()),
+        ],
+        StargateMock::new(vec![coin(
+            EIGHT_EIGHT_EIGHT_EIGHT_U128.u128(),
+            DENOM_UOM.to_string(),
+        )]),
+    );
+    let creator = suite.creator();
+    let user = suite.senders[1].clone();
+    let referrer = suite.senders[2].clone();
+    let pool_id = ULUNA_UUSD_POOL_ID.to_string();
+
+    suite
+        .instantiate_default()
+        .add_one_epoch()
+        .create_pool(
+            &creator,
+            vec![DENOM_ULUNA.to_string(), DENOM_UUSD.to_string()],
+            vec![DECIMALS_6, DECIMALS_6],
+            PoolFee {
+                protocol_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                swap_fee: Fee {
+                    share: Decimal::percent(1),
+                },
+                burn_fee: Fee {
+                    share: *DECIMAL_ZERO,
+                },
+                extra_fees: vec![],
+            },
+            PoolType::ConstantProduct,
+            Some(ULUNA_UUSD_POOL_RAW.to_string()),
+            vec![
+                coin(ONE_THOUSAND_U128.u128(), DENOM_UUSD),
+                coin(EIGHT_EIGHT_EIGHT_EIGHT_U128.u128(), DENOM_UOM),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .provide_liquidity(
+            &creator,
+            pool_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                coin(ONE_BILLION, DENOM_ULUNA.to_string()),
+                coin(ONE_BILLION, DENOM_UUSD.to_string()),
+            ],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let referral = Referral {
+        address: referrer.to_string(),
+        commission: Decimal::percent(2),
+    };
+
+    // referrals are disabled until the owner sets a max commission
+    suite
+        .swap_with_referral(
+            &user,
+            DENOM_UUSD.to_string(),
+            pool_id.clone(),
+            Some(referral.clone()),
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ReferralCommissionTooHigh { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::ReferralCommissionTooHigh"),
+                }
+            },
+        )
+        .update_config(
+            &creator,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(101)),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::InvalidMaxReferralCommission { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidMaxReferralCommission"),
+                }
+            },
+        )
+        .update_config(
+            &creator,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(1)),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .swap_with_referral(
+            &user,
+            DENOM_UUSD.to_string(),
+            pool_id.clone(),
+            Some(referral.clone()),
+            None,
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+                match err {
+                    ContractError::ReferralCommissionTooHigh { .. } => {}
+                    _ => panic!("Wrong error type, should return ContractError::ReferralCommissionTooHigh"),
+                }
+            },
+        );
+
+    let referral = Referral {
+        commission: Decimal::percent(1),
+        ..referral
+    };
+
+    let simulation = RefCell::new(None);
+    let simulation_without_referral = RefCell::new(None);
+    suite
+        .query_simulation_with_referral(
+            pool_id.clone(),
+            coin(ONE_MILLION, DENOM_ULUNA),
+            DENOM_UUSD.to_string(),
+            Some(referral.commission),
+            |result| {
+                *simulation.borrow_mut() = Some(result.unwrap());
+            },
+        )
+        .query_simulation(
+            pool_id.clone(),
+            coin(ONE_MILLION, DENOM_ULUNA),
+            DENOM_UUSD.to_string(),
+            |result| {
+                *simulation_without_referral.borrow_mut() = Some(result.unwrap());
+            },
+        );
+    let simulation = simulation.into_inner().unwrap();
+    let simulation_without_referral = simulation_without_referral.into_inner().unwrap();
+
+    // the referral fee is taken from the return amount
+    assert!(!simulation.referral_fee_amount.is_zero());
+    assert!(simulation_without_referral.referral_fee_amount.is_zero());
+    assert_eq!(
+        simulation.return_amount + simulation.referral_fee_amount,
+        simulation_without_referral.return_amount
+    );
+
+    // the reverse simulation takes the referral fee into account too
+    suite.query_reverse_simulation_with_referral(
+        pool_id.clone(),
+        coin(simulation.return_amount.u128(), DENOM_UUSD),
+        DENOM_ULUNA.to_string(),
+        Some(referral.commission),
+        |result| {
+            let result = result.unwrap();
+            assert_approx_eq!(result.offer_amount.u128(), ONE_MILLION, "0.001");
+            assert_approx_eq!(
+                result.referral_fee_amount.u128(),
+                simulation.referral_fee_amount.u128(),
+                "0.001"
+            );
+        },
+    );
+
+    let referrer_balance = RefCell::new(Uint128::zero());
+    suite.query_balance(&referrer.to_string(), DENOM_UUSD, |result| {
+        *referrer_balance.borrow_mut() = result.unwrap().amount;
+    });
+
+    suite
+        .swap_with_referral(
+            &user,
+            DENOM_UUSD.to_string(),
+            pool_id.clone(),
+            Some(referral),
+            // the pool fees add up to 2%
+            Some(Decimal::percent(5)),
+            vec![coin(ONE_MILLION, DENOM_ULUNA.to_string())],
+            |result| {
+                let mut referral_attribute_found = false;
+                for event in result.unwrap().events {
+                    if event.ty == EVENT_TYPE_WASM {
+                        for attribute in event.attributes {
+                            match attribute.key.as_str() {
+                                "return_amount" => assert_eq!(
+                                    attribute.value,
+                                    simulation.return_amount.to_string()
+                                ),
+                                "referral_fee_amount" => assert_eq!(
+                                    attribute.value,
+                                    simulation.referral_fee_amount.to_string()
+                                ),
+                                "referral" => {
+                                    referral_attribute_found = true;
+                                    assert_eq!(attribute.value, referrer.to_string());
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                assert!(referral_attribute_found);
+            },
+        )
+        .query_balance(&referrer.to_string(), DENOM_UUSD, |result| {
+            assert_eq!(
+                result.unwrap().amount,
+                *referrer_balance.borrow() + simulation.referral_fee_amount
+            );
+        });
+}
+
 // This function is used to setup a 3pool with different decimals.
 // Default values are used if not provided.
 // -- Default decimals: 6, 12, 18
 // -- Default amp: 85
 // -- Default initial balances: 300T for each token
 // -- Default initial liquidity: 100T for each token
+#[allow(clippy::inconsistent_digit_grouping)]
 fn setup_3pool_different_decimals(
     asset_decimals: Option<Vec<u8>>,
     initial_balances: Option<Vec<Uint128>>,
@@ -1,7 +1,9 @@
 use cosmwasm_std::testing::MockStorage;
 use mantra_dex_std::pool_manager::{
-    Config, FeatureToggle, PoolsResponse, ReverseSimulateSwapOperationsResponse,
-    ReverseSimulationResponse, SimulateSwapOperationsResponse, SimulationResponse, SwapOperation,
+    Config, DcaOrdersResponse, FeatureToggle, OrdersResponse, PoolStatsResponse, PoolsResponse,
+    Referral, ReverseSimulateSwapOperationsResponse, ReverseSimulationResponse,
+    SimulateProvideLiquidityResponse, SimulateSwapOperationsResponse, SimulateWithdrawalResponse,
+    SimulationResponse, SwapOperation,
 };
 use mantra_dex_std::pool_manager::{InstantiateMsg, PoolType};
 use std::cell::RefCell;
@@ -16,7 +18,7 @@ use cw_multi_test::{
 use mantra_common_testing::multi_test::stargate_mock::StargateMock;
 use mantra_dex_std::constants::{LP_SYMBOL, MONTH_IN_SECONDS};
 use mantra_dex_std::epoch_manager::EpochConfig;
-use mantra_dex_std::farm_manager::PositionsResponse;
+use mantra_dex_std::farm_manager::{FarmAction, FarmAprResponse, PositionsResponse};
 use mantra_dex_std::fee::PoolFee;
 
 /// Creates the pool manager contract
@@ -62,6 +64,7 @@ pub fn farm_manager_contract() -> Box<dyn Contract<Empty>> {
         farm_manager::contract::instantiate,
         farm_manager::contract::query,
     )
+    .with_reply(farm_manager::contract::reply)
     .with_migrate(farm_manager::contract::migrate);
 
     Box::new(contract)
@@ -169,10 +172,12 @@ impl TestingSuite {
         &mut self,
         fee_collector_addr: String,
         farm_manager_addr: String,
+        epoch_manager_addr: Option<String>,
     ) -> &mut Self {
         let msg = InstantiateMsg {
             fee_collector_addr,
             farm_manager_addr,
+            epoch_manager_addr,
             pool_creation_fee: coin(1_000, "uusd"),
         };
 
@@ -210,6 +215,32 @@ impl TestingSuite {
         self.instantiate(
             self.fee_collector_addr.to_string(),
             self.farm_manager_addr.to_string(),
+            Some(self.epoch_manager_addr.to_string()),
+        );
+
+        self.update_farm_manager_config(&creator, self.pool_manager_addr.clone(), |res| {
+            assert!(res.is_ok());
+        })
+    }
+
+    /// Same as [Self::instantiate_default], but without setting the epoch manager on the pool
+    /// manager, so the pool stats are not bucketed by epoch.
+    #[track_caller]
+    pub(crate) fn instantiate_default_without_epoch_manager(&mut self) -> &mut Self {
+        self.create_epoch_manager();
+        self.create_fee_collector();
+        self.create_farm_manager();
+
+        // 25 April 2024 15:00:00 UTC
+        let timestamp = Timestamp::from_seconds(1_714_057_200);
+        self.set_time(timestamp);
+
+        let creator = self.creator().clone();
+
+        self.instantiate(
+            self.fee_collector_addr.to_string(),
+            self.farm_manager_addr.to_string(),
+            None,
         );
 
         self.update_farm_manager_config(&creator, self.pool_manager_addr.clone(), |res| {
@@ -378,6 +409,138 @@ impl TestingSuite {
             max_slippage,
             receiver,
             pool_identifier,
+            referral: None,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &funds,
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn swap_with_referral(
+        &mut self,
+        sender: &Addr,
+        ask_asset_denom: String,
+        pool_identifier: String,
+        referral: Option<Referral>,
+        max_slippage: Option<Decimal>,
+        funds: Vec<Coin>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::Swap {
+            ask_asset_denom,
+            belief_price: None,
+            max_slippage,
+            receiver: None,
+            pool_identifier,
+            referral,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &funds,
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn place_order(
+        &mut self,
+        sender: &Addr,
+        pool_identifier: String,
+        ask_asset_denom: String,
+        price: Decimal,
+        keeper_tip: Option<Decimal>,
+        funds: Vec<Coin>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::PlaceOrder {
+            pool_identifier,
+            ask_asset_denom,
+            price,
+            keeper_tip,
+        };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &funds,
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn cancel_order(
+        &mut self,
+        sender: &Addr,
+        order_id: u64,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::CancelOrder { order_id };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn fill_order(
+        &mut self,
+        sender: &Addr,
+        order_id: u64,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::FillOrder { order_id };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn create_dca_order(
+        &mut self,
+        sender: &Addr,
+        ask_asset_denom: String,
+        operations: Vec<SwapOperation>,
+        interval: u64,
+        num_swaps: u64,
+        min_price: Option<Decimal>,
+        executor_tip: Option<Decimal>,
+        max_slippage: Option<Decimal>,
+        funds: Vec<Coin>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::CreateDcaOrder {
+            ask_asset_denom,
+            operations,
+            interval,
+            num_swaps,
+            min_price,
+            executor_tip,
+            max_slippage,
         };
 
         result(self.app.execute_contract(
@@ -390,6 +553,44 @@ impl TestingSuite {
         self
     }
 
+    #[track_caller]
+    pub(crate) fn execute_dca_order(
+        &mut self,
+        sender: &Addr,
+        order_id: u64,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::ExecuteDcaOrder { order_id };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn cancel_dca_order(
+        &mut self,
+        sender: &Addr,
+        order_id: u64,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = mantra_dex_std::pool_manager::ExecuteMsg::CancelDcaOrder { order_id };
+
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.pool_manager_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
     #[track_caller]
     pub(crate) fn execute_swap_operations(
         &mut self,
@@ -406,6 +607,7 @@ impl TestingSuite {
             minimum_receive,
             receiver,
             max_slippage,
+            referral: None,
         };
 
         result(self.app.execute_contract(
@@ -473,14 +675,17 @@ impl TestingSuite {
     ///
     /// Any parameters which are set to `None` when passed will not update
     /// the current configuration.
+    #[allow(clippy::too_many_arguments)]
     #[track_caller]
     pub(crate) fn update_config(
         &mut self,
         sender: &Addr,
         new_fee_collector_addr: Option<Addr>,
         new_farm_manager_addr: Option<Addr>,
+        new_epoch_manager_addr: Option<Addr>,
         new_pool_creation_fee: Option<Coin>,
         new_feature_toggle: Option<FeatureToggle>,
+        new_max_referral_commission: Option<Decimal>,
         result: impl Fn(Result<AppResponse, anyhow::Error>),
     ) -> &mut Self {
         result(self.app.execute_contract(
@@ -489,8 +694,10 @@ impl TestingSuite {
             &mantra_dex_std::pool_manager::ExecuteMsg::UpdateConfig {
                 fee_collector_addr: new_fee_collector_addr.map(|addr| addr.to_string()),
                 farm_manager_addr: new_farm_manager_addr.map(|addr| addr.to_string()),
+                epoch_manager_addr: new_epoch_manager_addr.map(|addr| addr.to_string()),
                 pool_creation_fee: new_pool_creation_fee,
                 feature_toggle: new_feature_toggle,
+                max_referral_commission: new_max_referral_commission,
             },
             &[],
         ));
@@ -523,6 +730,69 @@ impl TestingSuite {
                 max_unlocking_duration: None,
                 farm_expiration_time: None,
                 emergency_unlock_penalty: None,
+                penalty_split: None,
+            },
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn manage_farm(
+        &mut self,
+        sender: &Addr,
+        action: FarmAction,
+        funds: Vec<Coin>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &mantra_dex_std::farm_manager::ExecuteMsg::ManageFarm { action },
+            &funds,
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn claim_and_compound(
+        &mut self,
+        sender: &Addr,
+        identifier: String,
+        swap_max_slippage: Option<Decimal>,
+        reward_swap_operations: Option<Vec<Vec<SwapOperation>>>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &mantra_dex_std::farm_manager::ExecuteMsg::ClaimAndCompound {
+                identifier,
+                swap_max_slippage,
+                reward_swap_operations,
+            },
+            &[],
+        ));
+
+        self
+    }
+
+    #[track_caller]
+    pub(crate) fn approve_claim_operator(
+        &mut self,
+        sender: &Addr,
+        operator: &Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        result(self.app.execute_contract(
+            sender.clone(),
+            self.farm_manager_addr.clone(),
+            &mantra_dex_std::farm_manager::ExecuteMsg::ApproveClaimOperator {
+                operator: operator.to_string(),
+                expires: cw_utils::Expiration::Never {},
+                receiver: None,
             },
             &[],
         ));
@@ -595,6 +865,110 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn query_pool_stats(
+        &self,
+        pool_identifier: String,
+        epoch: Option<u64>,
+        result: impl Fn(StdResult<PoolStatsResponse>),
+    ) -> &Self {
+        let pool_stats_response: StdResult<PoolStatsResponse> = self.app.wrap().query_wasm_smart(
+            &self.pool_manager_addr,
+            &mantra_dex_std::pool_manager::QueryMsg::PoolStats {
+                pool_identifier,
+                epoch,
+            },
+        );
+
+        result(pool_stats_response);
+
+        self
+    }
+
+    pub(crate) fn query_simulate_withdrawal(
+        &self,
+        pool_identifier: String,
+        lp_amount: Uint128,
+        result: impl Fn(StdResult<SimulateWithdrawalResponse>),
+    ) -> &Self {
+        let response: StdResult<SimulateWithdrawalResponse> = self.app.wrap().query_wasm_smart(
+            &self.pool_manager_addr,
+            &mantra_dex_std::pool_manager::QueryMsg::SimulateWithdrawal {
+                pool_identifier,
+                lp_amount,
+            },
+        );
+
+        result(response);
+
+        self
+    }
+
+    pub(crate) fn query_simulate_provide_liquidity(
+        &self,
+        pool_identifier: String,
+        assets: Vec<Coin>,
+        result: impl Fn(StdResult<SimulateProvideLiquidityResponse>),
+    ) -> &Self {
+        let response: StdResult<SimulateProvideLiquidityResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.pool_manager_addr,
+                &mantra_dex_std::pool_manager::QueryMsg::SimulateProvideLiquidity {
+                    pool_identifier,
+                    assets,
+                },
+            );
+
+        result(response);
+
+        self
+    }
+
+    pub(crate) fn query_orders(
+        &mut self,
+        owner: Option<String>,
+        pool_identifier: Option<String>,
+        offer_denom: Option<String>,
+        start_after: Option<(Decimal, u64)>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<OrdersResponse>),
+    ) -> &mut Self {
+        let orders_response: StdResult<OrdersResponse> = self.app.wrap().query_wasm_smart(
+            &self.pool_manager_addr,
+            &mantra_dex_std::pool_manager::QueryMsg::Orders {
+                owner,
+                pool_identifier,
+                offer_denom,
+                start_after,
+                limit,
+            },
+        );
+
+        result(orders_response);
+
+        self
+    }
+
+    pub(crate) fn query_dca_orders(
+        &mut self,
+        owner: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<DcaOrdersResponse>),
+    ) -> &mut Self {
+        let orders_response: StdResult<DcaOrdersResponse> = self.app.wrap().query_wasm_smart(
+            &self.pool_manager_addr,
+            &mantra_dex_std::pool_manager::QueryMsg::DcaOrders {
+                owner,
+                start_after,
+                limit,
+            },
+        );
+
+        result(orders_response);
+
+        self
+    }
+
     pub(crate) fn query_simulation(
         &mut self,
         pool_identifier: String,
@@ -608,6 +982,7 @@ impl TestingSuite {
                 offer_asset,
                 ask_asset_denom,
                 pool_identifier,
+                referral_commission: None,
             },
         );
 
@@ -616,6 +991,29 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn query_simulation_with_referral(
+        &mut self,
+        pool_identifier: String,
+        offer_asset: Coin,
+        ask_asset_denom: String,
+        referral_commission: Option<Decimal>,
+        result: impl Fn(StdResult<SimulationResponse>),
+    ) -> &mut Self {
+        let simulation_response: StdResult<SimulationResponse> = self.app.wrap().query_wasm_smart(
+            &self.pool_manager_addr,
+            &mantra_dex_std::pool_manager::QueryMsg::Simulation {
+                offer_asset,
+                ask_asset_denom,
+                pool_identifier,
+                referral_commission,
+            },
+        );
+
+        result(simulation_response);
+
+        self
+    }
+
     pub(crate) fn query_reverse_simulation(
         &mut self,
         pool_identifier: String,
@@ -630,6 +1028,31 @@ impl TestingSuite {
                     ask_asset,
                     offer_asset_denom,
                     pool_identifier,
+                    referral_commission: None,
+                },
+            );
+
+        result(pool_info_response);
+
+        self
+    }
+
+    pub(crate) fn query_reverse_simulation_with_referral(
+        &mut self,
+        pool_identifier: String,
+        ask_asset: Coin,
+        offer_asset_denom: String,
+        referral_commission: Option<Decimal>,
+        result: impl Fn(StdResult<ReverseSimulationResponse>),
+    ) -> &mut Self {
+        let pool_info_response: StdResult<ReverseSimulationResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.pool_manager_addr,
+                &mantra_dex_std::pool_manager::QueryMsg::ReverseSimulation {
+                    ask_asset,
+                    offer_asset_denom,
+                    pool_identifier,
+                    referral_commission,
                 },
             );
 
@@ -650,6 +1073,29 @@ impl TestingSuite {
                 &mantra_dex_std::pool_manager::QueryMsg::SimulateSwapOperations {
                     offer_amount,
                     operations,
+                    referral_commission: None,
+                },
+            );
+
+        result(pool_info_response);
+
+        self
+    }
+
+    pub(crate) fn query_simulate_swap_operations_with_referral(
+        &mut self,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+        referral_commission: Option<Decimal>,
+        result: impl Fn(StdResult<SimulateSwapOperationsResponse>),
+    ) -> &mut Self {
+        let pool_info_response: StdResult<SimulateSwapOperationsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.pool_manager_addr,
+                &mantra_dex_std::pool_manager::QueryMsg::SimulateSwapOperations {
+                    offer_amount,
+                    operations,
+                    referral_commission,
                 },
             );
 
@@ -670,6 +1116,29 @@ impl TestingSuite {
                 &mantra_dex_std::pool_manager::QueryMsg::ReverseSimulateSwapOperations {
                     ask_amount,
                     operations,
+                    referral_commission: None,
+                },
+            );
+
+        result(pool_info_response);
+
+        self
+    }
+
+    pub(crate) fn query_reverse_simulate_swap_operations_with_referral(
+        &mut self,
+        ask_amount: Uint128,
+        operations: Vec<SwapOperation>,
+        referral_commission: Option<Decimal>,
+        result: impl Fn(StdResult<ReverseSimulateSwapOperationsResponse>),
+    ) -> &mut Self {
+        let pool_info_response: StdResult<ReverseSimulateSwapOperationsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.pool_manager_addr,
+                &mantra_dex_std::pool_manager::QueryMsg::ReverseSimulateSwapOperations {
+                    ask_amount,
+                    operations,
+                    referral_commission,
                 },
             );
 
@@ -746,6 +1215,24 @@ impl TestingSuite {
         self
     }
 
+    #[track_caller]
+    pub(crate) fn query_farm_apr(
+        &mut self,
+        lp_denom: &str,
+        result: impl Fn(StdResult<FarmAprResponse>),
+    ) -> &mut Self {
+        let farm_apr_response: StdResult<FarmAprResponse> = self.app.wrap().query_wasm_smart(
+            &self.farm_manager_addr,
+            &mantra_dex_std::farm_manager::QueryMsg::FarmApr {
+                lp_denom: lp_denom.to_string(),
+            },
+        );
+
+        result(farm_apr_response);
+
+        self
+    }
+
     #[track_caller]
     pub(crate) fn query_lp_supply(
         &mut self,
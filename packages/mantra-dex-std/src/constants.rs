@@ -0,0 +1,3 @@
+pub const LP_SYMBOL: &str = "LP";
+pub const DAY_IN_SECONDS: u64 = 86_400u64;
+pub const MONTH_IN_SECONDS: u64 = 2_629_746u64;
@@ -0,0 +1,855 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw_utils::Expiration;
+
+use crate::pool_manager::SwapOperation;
+
+/// The instantiation message
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The owner of the contract
+    pub owner: String,
+    /// The epoch manager address, where the epochs are managed
+    pub epoch_manager_addr: String,
+    /// The fee collector address, where protocol fees are stored
+    pub fee_collector_addr: String,
+    /// The pool manager address, where pools are created
+    pub pool_manager_addr: String,
+    /// The fee that must be paid to create a farm.
+    pub create_farm_fee: Coin,
+    /// The maximum amount of farms that can exist for a single LP token at a time.
+    pub max_concurrent_farms: u32,
+    /// New farms are allowed to start up to `current_epoch + start_epoch_buffer` into the future.
+    pub max_farm_epoch_buffer: u32,
+    /// The minimum amount of time that a user can lock their tokens for. In seconds.
+    pub min_unlocking_duration: u64,
+    /// The maximum amount of time that a user can lock their tokens for. In seconds.
+    pub max_unlocking_duration: u64,
+    /// The amount of time after which a farm is considered to be expired after it ended. In seconds.
+    /// Once a farm is expired it cannot be expanded, and expired farms can be closed
+    pub farm_expiration_time: u64,
+    /// The penalty for unlocking a position before the unlocking duration finishes. In percentage.
+    pub emergency_unlock_penalty: Decimal,
+}
+
+/// The execution messages
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Manages a farm based on the action, which can be:
+    /// - Fill: Creates or expands a farm.
+    /// - Close: Closes an existing farm.
+    ManageFarm { action: FarmAction },
+    /// Manages a position based on the action, which can be:
+    /// - Fill: Creates or expands a position.
+    /// - Close: Closes an existing position.
+    ManagePosition { action: PositionAction },
+    /// Claims the rewards for the user
+    Claim {
+        /// The epoch until which the rewards should be claimed. If none is provided,
+        /// it will claim until the current epoch.
+        until_epoch: Option<EpochId>,
+        /// The LP denoms to claim the rewards of. If none is provided, it will claim the rewards of
+        /// all the LP denoms.
+        lp_denoms: Option<Vec<String>>,
+        /// The farms to claim the rewards of. If none is provided, it will claim the rewards of all
+        /// the farms.
+        farm_identifiers: Option<Vec<String>>,
+        /// The address to claim the rewards of, if the sender is one of its claim operators. If
+        /// none is provided, it will claim the rewards of the sender.
+        owner: Option<String>,
+        /// The address to send the rewards to. If none is provided, it will send them to the owner.
+        /// Claim operators can only send them to the owner or to the receiver allowed in their
+        /// approval.
+        receiver: Option<String>,
+    },
+    /// Claims the rewards of the given position's LP denom, swaps them into the assets of the
+    /// position's pool and provides liquidity with them, expanding the position.
+    ClaimAndCompound {
+        /// The identifier of the position to compound the rewards into.
+        identifier: String,
+        /// The maximum slippage allowed on the swaps of the rewards into the pool assets, and on
+        /// the liquidity provision. It can't go over the default slippage of the pool manager when
+        /// compounding on behalf of someone else.
+        swap_max_slippage: Option<Decimal>,
+        /// The swap operations to swap the rewards that are not assets of the pool into one of
+        /// them, one route per reward denom. The rewards without a route are sent to the receiver
+        /// of the position.
+        reward_swap_operations: Option<Vec<Vec<SwapOperation>>>,
+    },
+    /// Approves an operator to claim rewards on behalf of the sender.
+    ApproveClaimOperator {
+        /// The address of the operator.
+        operator: String,
+        /// When the approval expires.
+        expires: Expiration,
+        /// The address, other than the sender, the operator is allowed to send the claimed rewards
+        /// to. If none is provided, the operator can only send them to the sender.
+        receiver: Option<String>,
+    },
+    /// Revokes a claim operator previously approved by the sender.
+    RevokeClaimOperator {
+        /// The address of the operator.
+        operator: String,
+    },
+    /// Prunes the LP weight history of an address for the epochs it has claimed already. Anyone
+    /// can prune the history of any address.
+    PruneHistory {
+        /// The address to prune the LP weight history of.
+        address: String,
+        /// The maximum amount of entries to prune. If unspecified, will default to a value
+        /// specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Updates the config of the contract
+    UpdateConfig {
+        /// The fee collector address, where protocol fees are stored
+        fee_collector_addr: Option<String>,
+        /// The epoch manager address, where the epochs are managed
+        epoch_manager_addr: Option<String>,
+        /// The pool manager address, where pools are created
+        pool_manager_addr: Option<String>,
+        /// The fee that must be paid to create a farm.
+        create_farm_fee: Option<Coin>,
+        /// The maximum amount of farms that can exist for a single LP token at a time.
+        max_concurrent_farms: Option<u32>,
+        /// The maximum amount of epochs in the future a new farm is allowed to start in.
+        max_farm_epoch_buffer: Option<u32>,
+        /// The minimum amount of time that a user can lock their tokens for. In seconds.
+        min_unlocking_duration: Option<u64>,
+        /// The maximum amount of time that a user can lock their tokens for. In seconds.
+        max_unlocking_duration: Option<u64>,
+        /// The amount of time after which a farm is considered to be expired after it ended. In seconds.
+        /// Once a farm is expired it cannot be expanded, and expired farms can be closed
+        farm_expiration_time: Option<u64>,
+        /// The penalty for unlocking a position before the unlocking duration finishes. In percentage.
+        emergency_unlock_penalty: Option<Decimal>,
+        /// How the emergency unlock penalty is split between the farm owners, the fee collector
+        /// and the remaining stakers of the LP denom.
+        penalty_split: Option<PenaltySplit>,
+    },
+    /// Funds the gauge emission budget with the coin sent, setting the amount to distribute
+    /// across the LP denoms every epoch. Only the owner can fund the gauges.
+    FundGauge {
+        /// The amount of the budget to distribute every epoch.
+        emission_per_epoch: Uint128,
+    },
+    /// Casts the gauge votes of the sender for the current epoch, directing the gauge emissions
+    /// of the next epoch.
+    GaugeVote {
+        /// The share of the sender's voting power given to each LP denom.
+        votes: Vec<GaugeVote>,
+    },
+    /// Distributes the gauge emissions of the epochs that haven't been distributed yet.
+    DistributeGauges {},
+    /// Updates the denoms allowed to be used as farm assets. Once a denom is added, only the listed
+    /// denoms can be used to create farms. Only the owner can update the farm assets.
+    UpdateFarmAssets {
+        /// The denoms to allow, with the minimum amount a farm has to be created with.
+        add: Vec<Coin>,
+        /// The denoms to disallow.
+        remove: Vec<String>,
+    },
+    /// Evicts the given farms, which stop emitting from the next epoch and no longer take up a slot
+    /// of their LP denom. The emissions of the upcoming epochs are refunded, while the rewards
+    /// already accrued remain claimable. Only the owner can evict farms.
+    EvictFarms {
+        /// The identifiers of the farms to evict.
+        farm_identifiers: Vec<String>,
+    },
+    /// Updates the boost applied to the weights of the addresses staking the native token. Passing
+    /// None disables the boost. Only the owner can update the boost config.
+    UpdateBoostConfig {
+        /// The new boost config.
+        boost_config: Option<BoostConfig>,
+    },
+    /// Recomputes the boost of an address for an LP denom, e.g. after its stake changed. Anyone
+    /// can refresh the boost of any address.
+    RefreshBoost {
+        /// The address to refresh the boost of.
+        address: String,
+        /// The LP denom to refresh the boost for.
+        lp_denom: String,
+    },
+    /// Updates the denoms that can be used to open positions, on top of the LP denoms of the pool
+    /// manager. Only the owner can update the stakeable denoms.
+    UpdateStakeableDenoms {
+        /// The denoms to add.
+        add: Vec<String>,
+        /// The denoms to remove.
+        remove: Vec<String>,
+    },
+}
+
+/// The migrate message
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// The query messages
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Retrieves the configuration of the manager.
+    #[returns(Config)]
+    Config {},
+    /// Retrieves farms in the contract. It is possible to filter by [FarmsBy] and to paginate the results.
+    #[returns(FarmsResponse)]
+    Farms {
+        /// An optional parameter specifying what to filter farms by.
+        /// Can be either the farm identifier, lp denom or the farm asset.
+        filter_by: Option<FarmsBy>,
+        /// An optional parameter specifying what farm (identifier) to start searching after.
+        start_after: Option<String>,
+        /// The amount of farms to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the positions for an address.
+    #[returns(PositionsResponse)]
+    Positions {
+        /// An optional parameter specifying what to filter positions by.
+        filter_by: Option<PositionsBy>,
+        /// An optional parameter specifying to return only positions that match the given open state.
+        /// if true, it will return open positions. If false, it will return closed positions.
+        open_state: Option<bool>,
+        /// An optional parameter specifying what position (identifier) to start searching after.
+        start_after: Option<String>,
+        /// The amount of positions to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the rewards for an address.
+    #[returns(RewardsResponse)]
+    Rewards {
+        /// The address to get all the farm rewards for.
+        address: String,
+        /// The epoch until which the rewards should be queried. If none is provided,
+        /// it will query until the current epoch.
+        until_epoch: Option<EpochId>,
+        /// An optional parameter specifying the LP denom to get the rewards for.
+        lp_denom: Option<String>,
+        /// An optional parameter specifying the farm to get the rewards for.
+        farm_identifier: Option<String>,
+    },
+    /// Retrieves the rewards for an address broken down by farm and epoch.
+    #[returns(DetailedRewardsResponse)]
+    DetailedRewards {
+        /// The address to get the farm rewards for.
+        address: String,
+        /// The epoch until which the rewards should be queried. If none is provided,
+        /// it will query until the current epoch.
+        until_epoch: Option<EpochId>,
+        /// An optional parameter specifying the LP denom to get the rewards for.
+        lp_denom: Option<String>,
+        /// An optional parameter specifying the farm to get the rewards for.
+        farm_identifier: Option<String>,
+    },
+    /// Retrieves the total LP weight in the contract for a given denom on a given epoch.
+    #[returns(LpWeightResponse)]
+    LpWeight {
+        /// The address to get the LP weight for.
+        address: String,
+        /// The denom to get the total LP weight for.
+        denom: String,
+        /// The epoch id to get the LP weight for.
+        epoch_id: EpochId,
+    },
+    /// Retrieves the total LP weight in the contract for a given denom on a given epoch.
+    #[returns(LpWeightResponse)]
+    TotalLpWeight {
+        /// The denom to get the total LP weight for.
+        denom: String,
+        /// The epoch id to get the total LP weight for.
+        epoch_id: EpochId,
+    },
+    /// Retrieves the LP weight snapshots of an address for a given denom.
+    #[returns(LpWeightHistoryResponse)]
+    LpWeightHistory {
+        /// The address to get the LP weight snapshots for.
+        address: String,
+        /// The denom to get the LP weight snapshots for.
+        denom: String,
+        /// An optional parameter specifying what epoch to start searching after.
+        start_after: Option<EpochId>,
+        /// The amount of snapshots to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the operators approved by an address to claim rewards on its behalf.
+    #[returns(ClaimOperatorsResponse)]
+    ClaimOperators {
+        /// The address that approved the operators.
+        address: String,
+        /// An optional parameter specifying what operator to start searching after.
+        start_after: Option<String>,
+        /// The amount of operators to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the lifetime rewards an address claimed, in total and from each farm.
+    #[returns(ClaimHistoryResponse)]
+    ClaimHistory {
+        /// The address to get the claimed rewards for.
+        address: String,
+        /// An optional parameter specifying what farm (identifier) to start searching after.
+        start_after: Option<String>,
+        /// The amount of farms to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the gauge emission budget.
+    #[returns(GaugeResponse)]
+    Gauge {},
+    /// Retrieves the gauge votes cast on the given epoch.
+    #[returns(GaugeVotesResponse)]
+    GaugeVotes {
+        /// The epoch to get the votes for.
+        epoch_id: EpochId,
+        /// An optional parameter specifying the voter to get the votes of. If not provided, it
+        /// returns the total votes of each LP denom.
+        voter: Option<String>,
+    },
+    /// Retrieves the denoms allowed to be used as farm assets, with the minimum amount a farm has
+    /// to be created with.
+    #[returns(FarmAssetsResponse)]
+    FarmAssets {
+        /// An optional parameter specifying what denom to start searching after.
+        start_after: Option<String>,
+        /// The amount of denoms to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the boost config.
+    #[returns(BoostConfigResponse)]
+    BoostConfig {},
+    /// Retrieves the denoms that can be used to open positions besides the LP denoms.
+    #[returns(StakeableDenomsResponse)]
+    StakeableDenoms {
+        /// An optional parameter specifying what denom to start searching after.
+        start_after: Option<String>,
+        /// The amount of denoms to return.
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the emissions of a farm per epoch.
+    #[returns(FarmEmissionsResponse)]
+    FarmEmissions {
+        /// The identifier of the farm.
+        farm_identifier: String,
+        /// The first epoch to get the emission of. If not provided, it starts at the start epoch
+        /// of the farm.
+        from_epoch: Option<EpochId>,
+        /// The last epoch to get the emission of, inclusive. If not provided, it ends at the last
+        /// epoch of the farm.
+        to_epoch: Option<EpochId>,
+    },
+    /// Retrieves the yield the farms of the given denom pay on the current epoch.
+    #[returns(FarmAprResponse)]
+    FarmApr {
+        /// The denom to get the farms yield for.
+        lp_denom: String,
+    },
+}
+
+/// Enum to filter farms by identifier, lp denom or the farm asset. Used in the farms query.
+#[cw_serde]
+pub enum FarmsBy {
+    Identifier(String),
+    LpDenom(String),
+    FarmAsset(String),
+}
+
+/// Enum to filter positions by identifier or receiver. Used in the positions query.
+#[cw_serde]
+pub enum PositionsBy {
+    Identifier(String),
+    Receiver(String),
+}
+
+/// Configuration for the contract (manager)
+#[cw_serde]
+pub struct Config {
+    /// The fee collector address, where protocol fees are stored
+    pub fee_collector_addr: Addr,
+    /// The epoch manager address, where the epochs are managed
+    pub epoch_manager_addr: Addr,
+    /// The pool manager address, where pools are created
+    pub pool_manager_addr: Addr,
+    /// The fee that must be paid to create a farm.
+    pub create_farm_fee: Coin,
+    /// The maximum amount of farms that can exist for a single LP token at a time.
+    pub max_concurrent_farms: u32,
+    /// The maximum amount of epochs in the future a new farm is allowed to start in.
+    pub max_farm_epoch_buffer: u32,
+    /// The minimum amount of time that a user can lock their tokens for. In seconds.
+    pub min_unlocking_duration: u64,
+    /// The maximum amount of time that a user can lock their tokens for. In seconds.
+    pub max_unlocking_duration: u64,
+    /// The amount of time after which a farm is considered to be expired after it ended. In seconds.
+    /// Once a farm is expired it cannot be expanded, and expired farms can be closed
+    pub farm_expiration_time: u64,
+    /// The penalty for unlocking a position before the unlocking duration finishes. In percentage.
+    pub emergency_unlock_penalty: Decimal,
+    /// How the emergency unlock penalty is split between the farm owners, the fee collector
+    /// and the remaining stakers of the LP denom.
+    pub penalty_split: PenaltySplit,
+}
+
+/// The boost applied to the weights of the addresses staking the native token.
+#[cw_serde]
+pub struct BoostConfig {
+    /// The maximum multiplier applied to the weight of an address.
+    pub max_boost: Decimal,
+    /// The amount of native token to stake per unit of weight to get the maximum boost.
+    pub stake_per_weight: Decimal,
+}
+
+/// The split of the emergency unlock penalty. The shares must add up to 100%.
+#[cw_serde]
+pub struct PenaltySplit {
+    /// The share of the penalty that goes to the owners of the active farms of the LP denom.
+    pub farm_owners: Decimal,
+    /// The share of the penalty that goes to the fee collector.
+    pub fee_collector: Decimal,
+    /// The share of the penalty that is redistributed to the remaining stakers of the LP denom.
+    pub redistribution: Decimal,
+}
+
+/// Parameters for creating farms
+#[cw_serde]
+pub struct FarmParams {
+    /// The LP asset denom to create the farm for.
+    pub lp_denom: String,
+    /// The epoch at which the farm will start. If unspecified, it will start at the
+    /// current epoch.
+    pub start_epoch: Option<u64>,
+    /// The epoch at which the farm should preliminarily end (if it's not expanded). If
+    /// unspecified, the farm will default to end at 14 epochs from the current one.
+    pub preliminary_end_epoch: Option<u64>,
+    /// The type of distribution curve. If unspecified, the distribution will be linear.
+    pub curve: Option<Curve>,
+    /// The asset to be distributed in this farm.
+    pub farm_asset: Coin,
+    /// If set, it  will be used to identify the farm.
+    pub farm_identifier: Option<String>,
+}
+
+#[cw_serde]
+pub enum FarmAction {
+    /// Creates a new farm with the given parameters.
+    Create {
+        /// The parameters for the farm to create.
+        params: FarmParams,
+    },
+    /// Expands an existing farm. The farm must already exist and the sender must be the
+    /// farm owner. The farm_identifier must be provided in the params.
+    Expand {
+        /// The parameters for the farm to expand.
+        params: FarmParams,
+    },
+    //// Closes a farm with the given identifier. If the farm has expired, anyone can
+    // close it. Otherwise, only the farm creator or the owner of the contract can close a farm.
+    Close {
+        /// The farm identifier to close.
+        farm_identifier: String,
+    },
+    /// Pauses the emissions of a farm from the next epoch. Only the farm creator can pause a farm.
+    Pause {
+        /// The farm identifier to pause.
+        farm_identifier: String,
+    },
+    /// Resumes the emissions of a paused farm from the next epoch, pushing back its end by the
+    /// number of epochs it was paused for. Only the farm creator can resume a farm.
+    Resume {
+        /// The farm identifier to resume.
+        farm_identifier: String,
+    },
+    /// Cancels a farm, refunding the emissions of the upcoming epochs while keeping the rewards
+    /// accrued so far claimable. Only the farm creator can cancel a farm.
+    Cancel {
+        /// The farm identifier to cancel.
+        farm_identifier: String,
+    },
+    /// Contributes to an existing farm, topping it up the same way expanding it does. Anyone can
+    /// contribute to a farm, and contributors get their share of the refunds when the farm is
+    /// closed or cancelled.
+    Contribute {
+        /// The farm identifier to contribute to.
+        farm_identifier: String,
+        /// The asset to contribute. It must be the farm asset, of at least [MIN_FARM_AMOUNT].
+        asset: Coin,
+    },
+}
+
+#[cw_serde]
+pub enum PositionAction {
+    /// Creates a position.
+    Create {
+        /// The identifier of the position.
+        identifier: Option<String>,
+        /// The time it takes in seconds to unlock this position. This is used to identify the position to fill.
+        unlocking_duration: u64,
+        /// The receiver for the position.
+        /// If left empty, defaults to the message sender.
+        receiver: Option<String>,
+    },
+    /// Expands a position.
+    Expand {
+        /// The identifier of the position.
+        identifier: String,
+    },
+    /// Closes an existing position. The position stops earning farm rewards.
+    Close {
+        /// The identifier of the position.
+        identifier: String,
+        /// The asset to add to the position. If not set, the position will be closed in full. If not, it could be partially closed.
+        lp_asset: Option<Coin>,
+    },
+    /// Withdraws the LP tokens from a position after the position has been closed and the unlocking duration has passed.
+    Withdraw {
+        /// The identifier of the position.
+        identifier: String,
+        /// Whether to unlock the position in an emergency. If set to true, the position will be
+        /// unlocked immediately. If the position has not expired, it will pay a penalty.
+        emergency_unlock: Option<bool>,
+    },
+    /// Extends the unlocking duration of an open position, increasing its weight.
+    ExtendLock {
+        /// The identifier of the position.
+        identifier: String,
+        /// The new unlocking duration of the position, in seconds. It must be longer than the
+        /// current one.
+        new_unlocking_duration: u64,
+    },
+    /// Transfers an open position to a new receiver, moving its LP weight along with it.
+    Transfer {
+        /// The identifier of the position.
+        identifier: String,
+        /// The address to transfer the position to.
+        new_receiver: String,
+    },
+    /// Splits an open position in two, moving the given amount of LP tokens to a new position
+    /// with the same unlocking duration.
+    Split {
+        /// The identifier of the position to split.
+        identifier: String,
+        /// The amount of LP tokens to move to the new position.
+        amount: Uint128,
+        /// The identifier of the new position. If not set, one will be generated.
+        new_identifier: Option<String>,
+    },
+    /// Merges open positions of the same LP denom into the first one of them.
+    Merge {
+        /// The identifiers of the positions to merge.
+        identifiers: Vec<String>,
+        /// The unlocking duration of the merged position. If not set, the longest unlocking
+        /// duration of the merged positions is used.
+        unlocking_duration: Option<u64>,
+    },
+}
+
+// type for the epoch id
+pub type EpochId = u64;
+
+/// Represents a farm.
+#[cw_serde]
+pub struct Farm {
+    /// The ID of the farm.
+    pub identifier: String,
+    /// The account which opened the farm and can manage it.
+    pub owner: Addr,
+    /// The LP asset denom to create the farm for.
+    pub lp_denom: String,
+    /// The asset the farm was created to distribute.
+    pub farm_asset: Coin,
+    /// The amount of the `farm_asset` that has been claimed so far.
+    pub claimed_amount: Uint128,
+    /// The amount of the `farm_asset` that is to be distributed every epoch.
+    pub emission_rate: Uint128,
+    /// The type of curve the farm has.
+    pub curve: Curve,
+    /// The epoch at which the farm starts.
+    pub start_epoch: EpochId,
+    /// The epoch at which the farm will preliminary end (in case it's not expanded).
+    pub preliminary_end_epoch: EpochId,
+}
+
+#[cw_serde]
+pub enum Curve {
+    /// A linear curve that releases assets uniformly over time.
+    Linear,
+    /// A curve that halves the emission every `half_life_epochs` epochs.
+    ExponentialDecay {
+        /// The number of epochs after which the emission halves.
+        half_life_epochs: u64,
+    },
+    /// A curve that emits the amount of each step on every epoch until the next step.
+    Step {
+        /// The (epoch_id, amount) steps of the curve. It must begin at the start epoch of the farm.
+        schedule: Vec<(EpochId, Uint128)>,
+    },
+    /// A curve that releases the whole farm on its last epoch.
+    Cliff,
+}
+
+impl std::fmt::Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Curve::Linear => write!(f, "linear"),
+            Curve::ExponentialDecay { half_life_epochs } => {
+                write!(f, "exponential_decay({half_life_epochs})")
+            }
+            Curve::Step { .. } => write!(f, "step"),
+            Curve::Cliff => write!(f, "cliff"),
+        }
+    }
+}
+
+/// Represents an LP position.
+#[cw_serde]
+pub struct Position {
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The amount of LP tokens that are put up to farm rewards.
+    pub lp_asset: Coin,
+    /// Represents the amount of time in seconds the user must wait after unlocking for the LP tokens to be released.
+    pub unlocking_duration: u64,
+    /// If true, the position is open. If false, the position is closed.
+    pub open: bool,
+    /// The block height at which the position, after being closed, can be withdrawn.
+    pub expiring_at: Option<u64>,
+    /// The owner of the position.
+    pub receiver: Addr,
+}
+
+impl Position {
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        self.expiring_at.is_some() && self.expiring_at.unwrap() <= current_time
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Position: {} - LP Asset: {} - Unlocking Duration: {} - Open: {} - Receiver: {} - Expiring At: {}",
+            self.identifier, self.lp_asset, self.unlocking_duration, self.open, self.receiver, self.expiring_at.unwrap_or(u64::MAX)
+        )
+    }
+}
+
+#[cw_serde]
+pub enum RewardsResponse {
+    /// The rewards response
+    RewardsResponse {
+        /// The rewards that is available to a user if they executed the `claim` function at this point.
+        total_rewards: Vec<Coin>,
+        /// The rewards per LP denom that is available to a user if they executed the `claim` function at this point.
+        rewards_per_lp_denom: Vec<(String, Vec<Coin>)>,
+    },
+    /// Rewards response used internally when querying the rewards
+    QueryRewardsResponse {
+        /// The rewards that is available to a user if they executed the `claim` function at this point.
+        rewards: Vec<Coin>,
+    },
+    /// Returned when claiming rewards
+    ClaimRewards {
+        /// The rewards that is available to a user if they executed the `claim` function at this point.
+        rewards: Vec<Coin>,
+        /// The rewards that were claimed on each farm, if any.
+        modified_farms: HashMap<String, Uint128>,
+    },
+}
+
+/// The budget the gauges distribute across the LP denoms.
+#[cw_serde]
+pub struct GaugeBudget {
+    /// The denom of the budget.
+    pub denom: String,
+    /// The amount left to distribute.
+    pub balance: Uint128,
+    /// The amount distributed every epoch.
+    pub emission_per_epoch: Uint128,
+}
+
+/// A gauge vote, giving a share of the voter's voting power to an LP denom.
+#[cw_serde]
+pub struct GaugeVote {
+    /// The LP denom voted for.
+    pub lp_denom: String,
+    /// The share of the voting power given to the LP denom.
+    pub weight: Decimal,
+}
+
+/// Minimum amount of an asset to create a farm with
+pub const MIN_FARM_AMOUNT: Uint128 = Uint128::new(1_000u128);
+
+/// Default farm duration in epochs
+pub const DEFAULT_FARM_DURATION: u64 = 14u64;
+
+/// The response for the farms query
+#[cw_serde]
+pub struct FarmsResponse {
+    /// The list of farms
+    pub farms: Vec<Farm>,
+}
+
+#[cw_serde]
+pub struct PositionsResponse {
+    /// All the positions a user has.
+    pub positions: Vec<Position>,
+}
+
+/// The rewards of an address on a farm for a given epoch
+#[cw_serde]
+pub struct EpochRewards {
+    /// The epoch id
+    pub epoch_id: EpochId,
+    /// The emission of the farm on the epoch
+    pub emission: Uint128,
+    /// The LP weight of the address on the epoch
+    pub lp_weight: Uint128,
+    /// The total LP weight of the LP denom on the epoch
+    pub total_lp_weight: Uint128,
+    /// The reward of the address, i.e. its share of the emission
+    pub reward: Uint128,
+}
+
+/// The rewards of an address on a farm
+#[cw_serde]
+pub struct FarmRewards {
+    /// The identifier of the farm
+    pub farm_identifier: String,
+    /// The LP denom of the farm
+    pub lp_denom: String,
+    /// The total reward of the address on the farm
+    pub reward: Coin,
+    /// The rewards per epoch
+    pub epochs: Vec<EpochRewards>,
+}
+
+/// The response for the detailed rewards query
+#[cw_serde]
+pub struct DetailedRewardsResponse {
+    /// The rewards of the address per farm
+    pub rewards: Vec<FarmRewards>,
+}
+
+/// The response for the LP weight query
+#[cw_serde]
+pub struct LpWeightResponse {
+    /// The total lp weight in the contract
+    pub lp_weight: Uint128,
+    /// The epoch id corresponding to the lp weight in the contract
+    pub epoch_id: EpochId,
+}
+
+/// The response for the LP weight history query
+#[cw_serde]
+pub struct LpWeightHistoryResponse {
+    /// The LP weight snapshots, as (epoch_id, lp_weight)
+    pub weights: Vec<(EpochId, Uint128)>,
+}
+
+/// The response for the gauge query
+#[cw_serde]
+pub struct GaugeResponse {
+    /// The gauge emission budget, if funded
+    pub budget: Option<GaugeBudget>,
+}
+
+/// The response for the gauge votes query
+#[cw_serde]
+pub struct GaugeVotesResponse {
+    /// The epoch the votes were cast on
+    pub epoch_id: EpochId,
+    /// The votes, as (lp_denom, vote weight)
+    pub votes: Vec<(String, Uint128)>,
+}
+
+/// An operator approved to claim rewards on behalf of an address
+#[cw_serde]
+pub struct ClaimOperator {
+    /// The address of the operator
+    pub operator: String,
+    /// When the approval expires
+    pub expires: Expiration,
+    /// The address, other than the owner, the operator is allowed to send the claimed rewards to
+    pub receiver: Option<String>,
+}
+
+/// The response for the claim operators query
+#[cw_serde]
+pub struct ClaimOperatorsResponse {
+    /// The operators approved by the address
+    pub operators: Vec<ClaimOperator>,
+}
+
+/// The response for the stakeable denoms query
+#[cw_serde]
+pub struct StakeableDenomsResponse {
+    /// The denoms that can be used to open positions besides the LP denoms
+    pub denoms: Vec<String>,
+}
+
+/// The response for the farm emissions query
+#[cw_serde]
+pub struct FarmEmissionsResponse {
+    /// The identifier of the farm
+    pub farm_identifier: String,
+    /// The emissions of the farm, as (epoch_id, emission)
+    pub emissions: Vec<(EpochId, Coin)>,
+}
+
+/// The yield of a farm on the current epoch
+#[cw_serde]
+pub struct FarmApr {
+    /// The identifier of the farm
+    pub farm_identifier: String,
+    /// The emission of the farm on the current epoch
+    pub emission: Coin,
+    /// The annualized yield of the farm for a position with a weight multiplier of one. None if
+    /// the LP can't be valued in terms of the farm asset.
+    pub apr: Option<Decimal>,
+}
+
+/// The response for the farm APR query
+#[cw_serde]
+pub struct FarmAprResponse {
+    /// The denom the farms are for
+    pub lp_denom: String,
+    /// The current epoch id
+    pub epoch_id: EpochId,
+    /// The total LP weight of the denom on the current epoch
+    pub total_lp_weight: Uint128,
+    /// The yield of each farm of the denom
+    pub farms: Vec<FarmApr>,
+}
+
+/// The response for the claim history query
+#[cw_serde]
+pub struct ClaimHistoryResponse {
+    /// The total rewards claimed by the address
+    pub total_claimed: Vec<Coin>,
+    /// The rewards claimed by the address from each farm, as (farm_identifier, rewards)
+    pub farms: Vec<(String, Vec<Coin>)>,
+}
+
+/// The response for the boost config query
+#[cw_serde]
+pub struct BoostConfigResponse {
+    /// The boost config, if the boost is enabled
+    pub boost_config: Option<BoostConfig>,
+}
+
+/// The response for the farm assets query
+#[cw_serde]
+pub struct FarmAssetsResponse {
+    /// The denoms allowed to be used as farm assets, with the minimum amount to create a farm with
+    pub assets: Vec<Coin>,
+}
@@ -0,0 +1,17 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {}
+
+#[cw_serde]
+pub enum MigrateMsg {}
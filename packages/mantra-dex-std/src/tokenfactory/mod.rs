@@ -0,0 +1,7 @@
+pub mod burn;
+pub mod common;
+pub mod create_denom;
+pub mod mint;
+pub mod responses;
+pub mod set_before_send_hook;
+pub mod utils;
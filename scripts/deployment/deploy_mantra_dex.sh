@@ -90,10 +90,12 @@ function init_epoch_manager() {
 function init_pool_manager() {
 	fee_collector_addr=$(jq -r '.contracts[] | select (.wasm == "fee_collector.wasm") | .contract_address' $output_file)
 	farm_manager_addr=$(jq -r '.contracts[] | select (.wasm == "farm_manager.wasm") | .contract_address' $output_file)
+	epoch_manager_addr=$(jq -r '.contracts[] | select (.wasm == "epoch_manager.wasm") | .contract_address' $output_file)
 
 	init_msg='{
               "fee_collector_addr": "'$fee_collector_addr'",
               "farm_manager_addr": "'$farm_manager_addr'",
+              "epoch_manager_addr": "'$epoch_manager_addr'",
               "pool_creation_fee": {
                 "denom": "uom",
                 "amount": "10000000"